@@ -0,0 +1,75 @@
+//! Schema migrations of the feed tables.
+//!
+//! Every migration is idempotent so it can be applied to databases that were set up by hand
+//! from `sql/` before this crate existed: the baseline only creates missing tables and the
+//! later ones use `IF NOT EXISTS` DDL. Run them with `cargo run -p migration -- up` or start
+//! the server with `--migrate`.
+
+pub use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{ConnectionTrait, DatabaseConnection, TransactionTrait};
+
+mod m20261016_000001_baseline;
+mod m20261016_000003_raw_data_retention;
+mod m20261016_000004_user_api_usage;
+mod m20261016_000005_interest_presets;
+mod m20261016_000006_subscription_folders;
+mod m20261016_000007_verify_schedules;
+mod m20261016_000010_paper_notes;
+mod m20261016_000011_paper_stars;
+mod m20261016_000015_audit_log;
+
+pub struct Migrator;
+
+/// Table recording the applied feed migrations. The default `seaql_migrations` belongs to
+/// WisAgent's migrator, which shares the database: each migrator refuses to run next to rows
+/// of migrations it does not know.
+pub const MIGRATION_TABLE: &str = "feed_seaql_migrations";
+
+#[async_trait::async_trait]
+impl MigratorTrait for Migrator {
+    fn migration_table_name() -> DynIden {
+        Alias::new(MIGRATION_TABLE).into_iden()
+    }
+
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        vec![
+            Box::new(m20261016_000001_baseline::Migration),
+            Box::new(m20261016_000003_raw_data_retention::Migration),
+            Box::new(m20261016_000004_user_api_usage::Migration),
+            Box::new(m20261016_000005_interest_presets::Migration),
+            Box::new(m20261016_000006_subscription_folders::Migration),
+            Box::new(m20261016_000007_verify_schedules::Migration),
+            Box::new(m20261016_000010_paper_notes::Migration),
+            Box::new(m20261016_000011_paper_stars::Migration),
+            Box::new(m20261016_000015_audit_log::Migration),
+        ]
+    }
+}
+
+/// Names of the migrations not yet applied to `db`
+pub async fn pending_migrations<'c, C>(db: C) -> Result<Vec<String>, DbErr>
+where
+    C: IntoSchemaManagerConnection<'c>,
+{
+    Ok(Migrator::get_pending_migrations(db)
+        .await?
+        .iter()
+        .map(|migration| migration.name().to_string())
+        .collect())
+}
+
+/// Key of the Postgres advisory lock held by `up_exclusively`
+pub const MIGRATION_LOCK_KEY: i64 = 0x5753_4c46_4d49_4752;
+
+/// Apply pending migrations holding a transaction-level advisory lock, so server replicas
+/// starting together migrate one at a time: the ones that waited find nothing pending. The
+/// migrations run in that transaction and are rolled back together on failure.
+pub async fn up_exclusively(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let txn = db.begin().await?;
+    txn.execute_unprepared(&format!(
+        "SELECT pg_advisory_xact_lock({MIGRATION_LOCK_KEY})"
+    ))
+    .await?;
+    Migrator::up(&txn, None).await?;
+    txn.commit().await
+}
@@ -0,0 +1,63 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, EntityTrait, Schema},
+};
+use seaorm_db::entities::feed::{
+    rss_job_logs, rss_papers, rss_sources, rss_subscriptions, user_interests,
+    user_paper_verifications,
+};
+
+/// The feed schema as described by the `seaorm_db` entities.
+///
+/// Tables that already exist are left untouched, so the baseline can be recorded on databases
+/// created from `sql/` by hand.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+async fn create_if_missing<E>(manager: &SchemaManager<'_>, entity: E) -> Result<(), DbErr>
+where
+    E: EntityTrait,
+{
+    if manager.has_table(entity.table_name()).await? {
+        return Ok(());
+    }
+    let schema = Schema::new(manager.get_database_backend());
+    for enum_type in schema.create_enum_from_entity(entity) {
+        manager.create_type(enum_type).await?;
+    }
+    manager
+        .create_table(
+            schema
+                .create_table_from_entity(entity)
+                .if_not_exists()
+                .to_owned(),
+        )
+        .await?;
+    for index in schema.create_index_from_entity(entity) {
+        manager
+            .get_connection()
+            .execute(manager.get_database_backend().build(&index))
+            .await?;
+    }
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        create_if_missing(manager, rss_sources::Entity).await?;
+        create_if_missing(manager, rss_subscriptions::Entity).await?;
+        create_if_missing(manager, rss_papers::Entity).await?;
+        create_if_missing(manager, user_interests::Entity).await?;
+        create_if_missing(manager, user_paper_verifications::Entity).await?;
+        create_if_missing(manager, rss_job_logs::Entity).await?;
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        // The baseline owns user data; dropping it is never done by a migration.
+        Err(DbErr::Migration(
+            "the baseline migration cannot be reverted".to_string(),
+        ))
+    }
+}
@@ -0,0 +1,29 @@
+use sea_orm_migration::prelude::*;
+
+/// `sql/20261016_raw_data_retention.sql`
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(include_str!("../../../sql/20261016_raw_data_retention.sql"))
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+DROP INDEX IF EXISTS idx_rss_papers_raw_data_created_at;
+ALTER TABLE rss_sources DROP COLUMN IF EXISTS keep_raw_data;
+"#,
+            )
+            .await?;
+        Ok(())
+    }
+}
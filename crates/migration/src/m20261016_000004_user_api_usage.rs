@@ -0,0 +1,29 @@
+use sea_orm_migration::prelude::*;
+
+/// `sql/20261016_user_api_usage.sql`
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(include_str!("../../../sql/20261016_user_api_usage.sql"))
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+DROP TABLE IF EXISTS user_api_usage_rollups;
+DROP TABLE IF EXISTS user_api_usage;
+"#,
+            )
+            .await?;
+        Ok(())
+    }
+}
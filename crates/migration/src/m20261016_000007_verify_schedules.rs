@@ -0,0 +1,24 @@
+use sea_orm_migration::prelude::*;
+
+/// `sql/20261016_verify_schedules.sql`
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(include_str!("../../../sql/20261016_verify_schedules.sql"))
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS verify_schedules;")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+use sea_orm_migration::prelude::*;
+
+#[tokio::main]
+async fn main() {
+    dotenvy::dotenv().ok();
+    cli::run_cli(migration::Migrator).await;
+}
@@ -0,0 +1,196 @@
+//! Applies every migration to a fresh schema and checks the result against the entities.
+//!
+//! Needs `DATABASE_URL` pointing at a PostgreSQL server; skipped otherwise, except under CI
+//! (see `test_support::infra_required`).
+
+use migration::{MIGRATION_TABLE, Migrator, MigratorTrait, pending_migrations, up_exclusively};
+use sea_orm::{
+    ConnectOptions, ConnectionTrait, Database, DatabaseConnection, EntityName, EntityTrait,
+    FromQueryResult, IdenStatic, Iterable, Statement,
+};
+use seaorm_db::entities::feed::{
+    rss_job_logs, rss_papers, rss_sources, rss_subscriptions, user_interests,
+    user_paper_verifications,
+};
+use test_support::skip;
+
+#[derive(Debug, FromQueryResult)]
+struct ColumnRow {
+    column_name: String,
+}
+
+/// Connect with `search_path` set to a new, empty schema, one per test
+async fn fresh_schema(test: &str) -> Option<(DatabaseConnection, String)> {
+    dotenvy::dotenv().ok();
+    let Ok(url) = std::env::var("DATABASE_URL") else {
+        return skip("DATABASE_URL not set");
+    };
+    let schema = format!("migration_test_{test}_{}", std::process::id());
+
+    let admin = match Database::connect(&url).await {
+        Ok(conn) => conn,
+        Err(e) => return skip(format!("database unavailable ({e})")),
+    };
+    admin
+        .execute_unprepared(&format!(
+            "DROP SCHEMA IF EXISTS {schema} CASCADE; CREATE SCHEMA {schema};"
+        ))
+        .await
+        .expect("create test schema");
+
+    let conn = connect_schema(&url, &schema).await;
+    Some((conn, schema))
+}
+
+async fn connect_schema(url: &str, schema: &str) -> DatabaseConnection {
+    let mut options = ConnectOptions::new(url);
+    options
+        .max_connections(1)
+        .set_schema_search_path(schema.to_string());
+    Database::connect(options)
+        .await
+        .expect("connect to test schema")
+}
+
+async fn table_columns(conn: &DatabaseConnection, schema: &str, table: &str) -> Vec<String> {
+    let mut columns: Vec<String> = ColumnRow::find_by_statement(Statement::from_sql_and_values(
+        conn.get_database_backend(),
+        "SELECT column_name::TEXT AS column_name FROM information_schema.columns \
+         WHERE table_schema = $1 AND table_name = $2",
+        [schema.into(), table.into()],
+    ))
+    .all(conn)
+    .await
+    .expect("list columns")
+    .into_iter()
+    .map(|row| row.column_name)
+    .collect();
+    columns.sort();
+    columns
+}
+
+/// Every entity column exists in the migrated table
+async fn assert_entity_matches<E>(conn: &DatabaseConnection, schema: &str, entity: E)
+where
+    E: EntityTrait,
+{
+    let table = entity.table_name();
+    let actual = table_columns(conn, schema, table).await;
+    assert!(!actual.is_empty(), "table {table} was not created");
+    for column in E::Column::iter() {
+        assert!(
+            actual.iter().any(|c| c == column.as_str()),
+            "column {table}.{} missing after migrations, got {actual:?}",
+            column.as_str()
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_migrations_apply_to_fresh_database() {
+    let Some((conn, schema)) = fresh_schema("fresh").await else {
+        return;
+    };
+
+    Migrator::up(&conn, None).await.expect("apply migrations");
+    assert!(pending_migrations(&conn).await.unwrap().is_empty());
+
+    assert_entity_matches(&conn, &schema, rss_sources::Entity).await;
+    assert_entity_matches(&conn, &schema, rss_subscriptions::Entity).await;
+    assert_entity_matches(&conn, &schema, rss_papers::Entity).await;
+    assert_entity_matches(&conn, &schema, user_interests::Entity).await;
+    assert_entity_matches(&conn, &schema, user_paper_verifications::Entity).await;
+    assert_entity_matches(&conn, &schema, rss_job_logs::Entity).await;
+
+    // columns added on top of the baseline
+    let sources = table_columns(&conn, &schema, "rss_sources").await;
+    assert!(sources.contains(&"keep_raw_data".to_string()));
+    let usage = table_columns(&conn, &schema, "user_api_usage").await;
+    assert_eq!(usage, ["count", "day", "event", "user_id"]);
+    let presets = table_columns(&conn, &schema, "interest_presets").await;
+    assert!(presets.contains(&"channel_hints".to_string()));
+    let folders = table_columns(&conn, &schema, "subscription_folders").await;
+    assert_eq!(folders, ["folder", "source_id", "updated_at", "user_id"]);
+    let schedules = table_columns(&conn, &schema, "verify_schedules").await;
+    assert!(schedules.contains(&"last_run_at".to_string()));
+
+    conn.execute_unprepared(&format!("DROP SCHEMA {schema} CASCADE"))
+        .await
+        .expect("drop test schema");
+}
+
+#[tokio::test]
+async fn test_migrations_are_idempotent_on_existing_schema() {
+    let Some((conn, schema)) = fresh_schema("existing").await else {
+        return;
+    };
+
+    // a database set up by hand: tables and the sql/ changes exist, but nothing is recorded
+    Migrator::up(&conn, None).await.expect("apply migrations");
+    conn.execute_unprepared(&format!("DROP TABLE {MIGRATION_TABLE}"))
+        .await
+        .expect("forget applied migrations");
+    assert!(!pending_migrations(&conn).await.unwrap().is_empty());
+
+    Migrator::up(&conn, None)
+        .await
+        .expect("re-apply migrations over the existing schema");
+    assert!(pending_migrations(&conn).await.unwrap().is_empty());
+
+    conn.execute_unprepared(&format!("DROP SCHEMA {schema} CASCADE"))
+        .await
+        .expect("drop test schema");
+}
+
+#[tokio::test]
+async fn test_concurrent_exclusive_migrations() {
+    let Some((conn, schema)) = fresh_schema("exclusive").await else {
+        return;
+    };
+    let url = std::env::var("DATABASE_URL").unwrap();
+    let other = connect_schema(&url, &schema).await;
+
+    // two replicas starting at once: the second waits for the lock, then has nothing to do
+    let (a, b) = tokio::join!(up_exclusively(&conn), up_exclusively(&other));
+    a.expect("first replica migrates");
+    b.expect("second replica migrates");
+    assert!(pending_migrations(&conn).await.unwrap().is_empty());
+
+    conn.execute_unprepared(&format!("DROP SCHEMA {schema} CASCADE"))
+        .await
+        .expect("drop test schema");
+}
+
+#[tokio::test]
+async fn test_migrations_ignore_wisagent_rows() {
+    let Some((conn, schema)) = fresh_schema("shared").await else {
+        return;
+    };
+
+    // the WisAgent migrator's table, as sql/20250908.sql leaves it
+    conn.execute_unprepared(
+        "CREATE TABLE seaql_migrations (version VARCHAR PRIMARY KEY, applied_at BIGINT NOT NULL); \
+         INSERT INTO seaql_migrations (version, applied_at) \
+         VALUES ('m20250403_100502_chat_sessions', 1756985879);",
+    )
+    .await
+    .expect("seed WisAgent migrations");
+
+    assert!(!pending_migrations(&conn).await.unwrap().is_empty());
+    up_exclusively(&conn).await.expect("apply migrations");
+    assert!(pending_migrations(&conn).await.unwrap().is_empty());
+
+    // WisAgent's table is left as it was
+    let rows = conn
+        .query_all(Statement::from_string(
+            conn.get_database_backend(),
+            "SELECT version FROM seaql_migrations",
+        ))
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+
+    conn.execute_unprepared(&format!("DROP SCHEMA {schema} CASCADE"))
+        .await
+        .expect("drop test schema");
+}
@@ -2,10 +2,15 @@ use crate::{
     middlewares::*,
     routers::{
         // feed::{self},
+        audit::audit_routers,
+        authz::{ROUTE_CAPABILITIES, authz_routers},
         feed::feed_routers,
         health::{self, handler_404},
+        kill_switch::kill_switch_routers,
+        metrics::metrics_routers,
+        usage::usage_routers,
     },
-    state::app_state::AppState,
+    state::{app_state::AppState, usage, verify_schedule},
 };
 use ::feed::dispatch;
 use ::feed::workers::verify_user_scheduler::VerifyUserSchedulerInput;
@@ -36,28 +41,95 @@ pub async fn build_app() -> Result<(Router, AppState), ApiError> {
     let state = AppState::new().await;
 
     start_verify_user_scheduler_worker(state.redis.apalis_conn.clone()).await?;
+    tokio::spawn(usage::run_usage_rollup(state.clone()));
+    tokio::spawn(verify_schedule::run_verify_scheduler(state.clone()));
 
-    // build the router with OpenAPI documentation
-    let url_prefix = config.server.api_prefix.trim_end_matches('/');
-    let (router, api) = OpenApiRouter::with_openapi(ApiDoc::openapi())
+    Ok((build_router(state.clone()), state))
+}
+
+/// All documented routes, nested under `url_prefix`
+pub fn api_routers(url_prefix: &str) -> OpenApiRouter<AppState> {
+    OpenApiRouter::with_openapi(ApiDoc::openapi())
         .nest(url_prefix, health::health_routers())
+        .nest(url_prefix, metrics_routers())
         .nest(url_prefix, feed_routers())
-        .split_for_parts();
+        .nest(url_prefix, authz_routers())
+        .nest(url_prefix, kill_switch_routers())
+        .nest(url_prefix, usage_routers())
+        .nest(url_prefix, audit_routers())
+}
+
+/// Build the full router (routes, docs and middlewares) on top of an existing state
+pub fn build_router(state: AppState) -> Router {
+    // build the router with OpenAPI documentation
+    let url_prefix = state
+        .config
+        .server
+        .api_prefix
+        .trim_end_matches('/')
+        .to_string();
+    let (router, api) = api_routers(&url_prefix).split_for_parts();
+
+    let unassigned = unassigned_routes(&api, &url_prefix);
+    assert!(
+        unassigned.is_empty(),
+        "routes without a capability in ROUTE_CAPABILITIES: {unassigned:?}"
+    );
 
     // build the final router with Swagger UI and Scalar documentation
-    let router = router
+    router
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            read_after_write::read_after_write,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            idempotency::idempotency,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::rate_limit,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            authz::authorize,
+        ))
         .merge(
             SwaggerUi::new(format!("{url_prefix}/swagger-ui"))
                 .url(format!("{url_prefix}/openapi.json"), api.clone()),
         )
         .merge(Scalar::with_url(format!("{url_prefix}/docs"), api))
         .layer(CatchPanicLayer::custom(PanicHandler)) // panic handler
+        .layer(middleware::from_fn(metrics::track_metrics))
         // .layer(middleware::from_fn(log::log_response))
         .layer(middleware::from_fn(log::log_request))
-        .with_state(state.clone())
-        .fallback(handler_404);
+        .layer(middleware::from_fn(log::request_id))
+        .with_state(state)
+        .fallback(handler_404)
+}
 
-    Ok((router, state))
+/// `METHOD path` of every documented route missing from `ROUTE_CAPABILITIES`
+pub fn unassigned_routes(api: &utoipa::openapi::OpenApi, url_prefix: &str) -> Vec<String> {
+    let mut unassigned = Vec::new();
+    for (path, item) in api.paths.paths.iter() {
+        let path = path.strip_prefix(url_prefix).unwrap_or(path);
+        let operations = [
+            ("GET", item.get.is_some()),
+            ("POST", item.post.is_some()),
+            ("PUT", item.put.is_some()),
+            ("PATCH", item.patch.is_some()),
+            ("DELETE", item.delete.is_some()),
+        ];
+        for (method, _) in operations.into_iter().filter(|(_, present)| *present) {
+            if !ROUTE_CAPABILITIES
+                .iter()
+                .any(|(m, p, _)| *m == method && *p == path)
+            {
+                unassigned.push(format!("{method} {path}"));
+            }
+        }
+    }
+    unassigned
 }
 
 pub async fn start_verify_user_scheduler_worker(
@@ -0,0 +1,262 @@
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use sea_orm::ConnectOptions;
+use serde::Deserialize;
+use settings::figment;
+
+use crate::model::feed_url::FeedFetchLimits;
+
+/// Server settings that live under `[rss]` but are not part of `conf::config::RssConfig`.
+///
+/// Loaded through `settings::figment()`, with the same layering as `app_config()`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServerRssConfig {
+    /// Seconds a "mark all as read" can be undone
+    #[serde(default = "default_mark_read_undo_window_secs")]
+    pub mark_read_undo_window_secs: u64,
+    /// Max verification ids stored in an undo snapshot before falling back to id ranges
+    #[serde(default = "default_mark_read_undo_max_ids")]
+    pub mark_read_undo_max_ids: usize,
+    /// Timeout of the fetch that checks a new source's URL is an RSS/Atom feed
+    #[serde(default = "default_feed_validation_timeout_secs")]
+    pub feed_validation_timeout_secs: u64,
+    /// Bytes of the feed read at most while looking for its root element
+    #[serde(default = "default_feed_validation_max_bytes")]
+    pub feed_validation_max_bytes: usize,
+    #[serde(default = "default_feed_validation_max_redirects")]
+    pub feed_validation_max_redirects: usize,
+    /// Seconds between two looks for users whose verify schedule is due
+    #[serde(default = "default_verify_schedule_interval_secs")]
+    pub verify_schedule_interval_secs: u64,
+    /// Messages buffered per `stream-verify` connection before a slow client starts missing events
+    #[serde(default = "default_verify_stream_channel_capacity")]
+    pub verify_stream_channel_capacity: usize,
+    /// Verifications (`/verify` and `/stream-verify` together) a user may queue per minute; 0 disables the limit
+    #[serde(default = "default_verify_rate_limit_per_minute")]
+    pub verify_rate_limit_per_minute: u32,
+    /// Seconds a queued `/verify` job blocks another one for the same user
+    #[serde(default = "default_verify_job_dedupe_ttl_secs")]
+    pub verify_job_dedupe_ttl_secs: u64,
+    /// Seconds the response of a request sent with an `Idempotency-Key` is replayed
+    #[serde(default = "default_idempotency_key_ttl_secs")]
+    pub idempotency_key_ttl_secs: u64,
+    /// Seconds a running request with an `Idempotency-Key` blocks duplicates, should it never finish
+    #[serde(default = "default_idempotency_in_flight_ttl_secs")]
+    pub idempotency_in_flight_ttl_secs: u64,
+    /// Largest `days` window of `GET /verify-stats`
+    #[serde(default = "default_verify_stats_max_days")]
+    pub verify_stats_max_days: u32,
+    /// Seconds the user context is read uncached after an interest or subscription update was
+    /// queued, until the worker has applied it
+    #[serde(default = "default_update_apply_window_secs")]
+    pub update_apply_window_secs: u64,
+}
+
+impl ServerRssConfig {
+    /// Limits of the feed URL check; only public addresses are fetched
+    pub fn feed_fetch_limits(&self) -> FeedFetchLimits {
+        FeedFetchLimits {
+            timeout: Duration::from_secs(self.feed_validation_timeout_secs),
+            max_bytes: self.feed_validation_max_bytes.max(1),
+            max_redirects: self.feed_validation_max_redirects,
+            allow_private_addresses: false,
+        }
+    }
+}
+
+fn default_mark_read_undo_window_secs() -> u64 {
+    5 * 60
+}
+
+fn default_mark_read_undo_max_ids() -> usize {
+    5000
+}
+
+fn default_feed_validation_timeout_secs() -> u64 {
+    10
+}
+
+fn default_feed_validation_max_bytes() -> usize {
+    256 * 1024
+}
+
+fn default_feed_validation_max_redirects() -> usize {
+    3
+}
+
+fn default_verify_schedule_interval_secs() -> u64 {
+    5 * 60
+}
+
+fn default_verify_stream_channel_capacity() -> usize {
+    1000
+}
+
+fn default_verify_rate_limit_per_minute() -> u32 {
+    10
+}
+
+fn default_verify_job_dedupe_ttl_secs() -> u64 {
+    10 * 60
+}
+
+fn default_idempotency_key_ttl_secs() -> u64 {
+    24 * 60 * 60
+}
+
+fn default_idempotency_in_flight_ttl_secs() -> u64 {
+    60
+}
+
+fn default_verify_stats_max_days() -> u32 {
+    90
+}
+
+fn default_update_apply_window_secs() -> u64 {
+    30
+}
+
+pub fn server_rss_config() -> &'static ServerRssConfig {
+    static CONFIG: OnceLock<ServerRssConfig> = OnceLock::new();
+    CONFIG.get_or_init(|| {
+        figment()
+            .extract_inner::<ServerRssConfig>("rss")
+            .expect("Invalid [rss] server configuration")
+    })
+}
+
+/// `[authz]`: who gets the admin and service capabilities
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AuthzConfig {
+    /// User ids granted the admin capability
+    #[serde(default)]
+    pub admin_user_ids: Vec<i64>,
+    /// Shared secret of internal callers (`x-service-token`); unset disables the service capability
+    #[serde(default)]
+    pub service_token: Option<String>,
+}
+
+pub fn authz_config() -> AuthzConfig {
+    figment()
+        .extract_inner::<AuthzConfig>("authz")
+        .unwrap_or_default()
+}
+
+/// `[migration]`: schema migrations applied by the server itself
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MigrationConfig {
+    /// Apply pending migrations at startup, like the `--migrate` flag
+    #[serde(default)]
+    pub auto_migrate: bool,
+}
+
+pub fn migration_config() -> MigrationConfig {
+    figment()
+        .extract_inner::<MigrationConfig>("migration")
+        .unwrap_or_default()
+}
+
+/// `[database]` settings of the server beyond `conf::config::DatabaseConfig`
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReadReplicaConfig {
+    /// Postgres URL of a read replica for the heavy listings; unset or blank sends every query
+    /// to the primary `url`
+    #[serde(default)]
+    pub read_url: Option<String>,
+    /// Pool settings, shared with the primary
+    pub max_connections: Option<u32>,
+    pub min_connections: Option<u32>,
+    pub timeout_ms: Option<u64>,
+    pub acquire_timeout_ms: Option<u64>,
+    pub idle_timeout_ms: Option<u64>,
+    /// Seconds a user's listings are read from the primary after a request of theirs that may
+    /// have written, while the replica catches up
+    #[serde(default = "default_read_after_write_secs")]
+    pub read_after_write_secs: u64,
+}
+
+fn default_read_after_write_secs() -> u64 {
+    10
+}
+
+impl Default for ReadReplicaConfig {
+    fn default() -> Self {
+        ReadReplicaConfig {
+            read_url: None,
+            max_connections: None,
+            min_connections: None,
+            timeout_ms: None,
+            acquire_timeout_ms: None,
+            idle_timeout_ms: None,
+            read_after_write_secs: default_read_after_write_secs(),
+        }
+    }
+}
+
+impl ReadReplicaConfig {
+    /// Options to connect the replica with, `None` when no replica is configured
+    pub fn connect_options(&self) -> Option<ConnectOptions> {
+        let url = self.read_url.as_deref().map(str::trim)?;
+        if url.is_empty() {
+            return None;
+        }
+        let mut options = ConnectOptions::new(url);
+        if let Some(max) = self.max_connections {
+            options.max_connections(max);
+        }
+        if let Some(min) = self.min_connections {
+            options.min_connections(min);
+        }
+        if let Some(ms) = self.timeout_ms {
+            options.connect_timeout(Duration::from_millis(ms));
+        }
+        if let Some(ms) = self.acquire_timeout_ms {
+            options.acquire_timeout(Duration::from_millis(ms));
+        }
+        if let Some(ms) = self.idle_timeout_ms {
+            options.idle_timeout(Duration::from_millis(ms));
+        }
+        Some(options)
+    }
+}
+
+pub fn read_replica_config() -> ReadReplicaConfig {
+    figment()
+        .extract_inner::<ReadReplicaConfig>("database")
+        .unwrap_or_default()
+}
+
+/// `[usage]`: per-user API usage accounting
+#[derive(Debug, Clone, Deserialize)]
+pub struct UsageConfig {
+    /// How often buffered usage events are flushed to Redis
+    #[serde(default = "default_usage_flush_interval_ms")]
+    pub flush_interval_ms: u64,
+    /// How often finished days are rolled up from Redis into `user_api_usage`
+    #[serde(default = "default_usage_rollup_interval_secs")]
+    pub rollup_interval_secs: u64,
+}
+
+fn default_usage_flush_interval_ms() -> u64 {
+    1000
+}
+
+fn default_usage_rollup_interval_secs() -> u64 {
+    60 * 60
+}
+
+impl Default for UsageConfig {
+    fn default() -> Self {
+        UsageConfig {
+            flush_interval_ms: default_usage_flush_interval_ms(),
+            rollup_interval_secs: default_usage_rollup_interval_secs(),
+        }
+    }
+}
+
+pub fn usage_config() -> UsageConfig {
+    figment()
+        .extract_inner::<UsageConfig>("usage")
+        .unwrap_or_default()
+}
@@ -1,2 +1,95 @@
+use common::prelude::ApiCode;
+
 pub const WIS_TOKEN: &str = "X-User-Info";
 pub const WIS_TOKEN_LOWERCASE: &str = "x-user-info";
+
+/// Response header carrying the token for `POST /mark-as-read/undo`
+pub const UNDO_TOKEN_HEADER: &str = "x-undo-token";
+/// Response header with the seconds left to use the undo token
+pub const UNDO_EXPIRES_IN_HEADER: &str = "x-undo-expires-in";
+
+/// The undo token is unknown or its window has passed
+pub const MARK_READ_UNDO_EXPIRED: ApiCode = ApiCode {
+    http_code: 410,
+    code: 200410,
+};
+
+/// A query parameter could not be parsed
+pub const INVALID_QUERY_PARAM: ApiCode = ApiCode {
+    http_code: 400,
+    code: 200400,
+};
+
+/// The URL of a new RSS source is unreachable or not an RSS/Atom feed
+pub const INVALID_FEED_URL: ApiCode = ApiCode {
+    http_code: 400,
+    code: 200400,
+};
+
+/// A submitted RSS source id does not exist
+pub const UNKNOWN_SOURCE: ApiCode = ApiCode {
+    http_code: 400,
+    code: 200400,
+};
+
+/// A `POST /rss/batch` request has more than `MAX_RSS_BATCH` items
+pub const RSS_BATCH_TOO_LARGE: ApiCode = ApiCode {
+    http_code: 400,
+    code: 200400,
+};
+
+/// Header carrying the shared secret of internal (service) callers
+pub const SERVICE_TOKEN: &str = "x-service-token";
+
+/// The caller lacks the capability the route requires
+pub const FORBIDDEN: ApiCode = ApiCode {
+    http_code: 403,
+    code: 200403,
+};
+
+/// The subsystem is disabled by a kill switch
+pub const FEED_TEMPORARILY_DISABLED: ApiCode = ApiCode {
+    http_code: 503,
+    code: 200503,
+};
+
+/// The requested resource does not exist
+pub const RESOURCE_NOT_FOUND: ApiCode = ApiCode {
+    http_code: 404,
+    code: 200404,
+};
+
+/// The caller exceeded the rate limit of the route; `Retry-After` says when to try again
+pub const RATE_LIMITED: ApiCode = ApiCode {
+    http_code: 429,
+    code: 200429,
+};
+
+/// The request conflicts with the current state (duplicate name, limit exceeded, ...)
+pub const CONFLICT: ApiCode = ApiCode {
+    http_code: 409,
+    code: 200409,
+};
+
+/// Request header making a retried mutation replay the first response instead of running again
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+/// Response header set on replayed responses
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// The `Idempotency-Key` header is empty, too long or not printable ASCII
+pub const INVALID_IDEMPOTENCY_KEY: ApiCode = ApiCode {
+    http_code: 400,
+    code: 200400,
+};
+
+/// The `Idempotency-Key` was already used with a different request body
+pub const IDEMPOTENCY_KEY_REUSED: ApiCode = ApiCode {
+    http_code: 422,
+    code: 200422,
+};
+
+/// The body of a request sent with an `Idempotency-Key` is too large to fingerprint
+pub const IDEMPOTENT_REQUEST_TOO_LARGE: ApiCode = ApiCode {
+    http_code: 413,
+    code: 200413,
+};
@@ -1,6 +1,8 @@
 pub mod app;
+pub mod config;
 pub mod consts;
 pub mod middlewares;
 pub mod model;
+pub mod query;
 pub mod routers;
 pub mod state;
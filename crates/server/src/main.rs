@@ -1,7 +1,9 @@
 use axum::BoxError;
 use conf::config::app_config;
 use dotenvy::dotenv;
-use server::{app::build_app, state::app_state::graceful_shutdown};
+use migration::up_exclusively;
+use seaorm_db::connection::get_db;
+use server::{app::build_app, config::migration_config, state::app_state::graceful_shutdown};
 use std::net::{IpAddr, SocketAddr};
 use tracing::*;
 
@@ -13,6 +15,11 @@ async fn main() -> Result<(), BoxError> {
     // Initialize logging
     let _guard = config.init_log(true);
 
+    if std::env::args().any(|arg| arg == "--migrate") || migration_config().auto_migrate {
+        info!("applying pending migrations");
+        up_exclusively(get_db().await).await?;
+    }
+
     let (router, state) = build_app().await?;
     info!("init server successfully");
 
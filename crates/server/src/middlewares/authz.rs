@@ -0,0 +1,143 @@
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::HeaderMap,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use common::{error::api_error::*, prelude::ApiCode};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::auth::UserInfo;
+use crate::{
+    config::AuthzConfig,
+    consts::{FORBIDDEN, SERVICE_TOKEN, WIS_TOKEN, WIS_TOKEN_LOWERCASE},
+    routers::authz::route_capability,
+    state::app_state::AppState,
+};
+
+/// What a route requires from its caller
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    /// Anyone, authenticated or not
+    Public,
+    /// Any authenticated user
+    User,
+    /// Users listed in `authz.admin_user_ids`
+    Admin,
+    /// Internal callers presenting `authz.service_token`
+    Service,
+}
+
+/// The caller of the current request, resolved once by `authorize` and stored in the
+/// request extensions
+#[derive(Debug, Clone, Default)]
+pub struct Caller {
+    pub user: Option<UserInfo>,
+    pub is_admin: bool,
+    pub is_service: bool,
+}
+
+impl Caller {
+    pub fn resolve(headers: &HeaderMap, config: &AuthzConfig) -> Self {
+        let user = headers
+            .get(WIS_TOKEN)
+            .or_else(|| headers.get(WIS_TOKEN_LOWERCASE))
+            .and_then(|token| token.to_str().ok())
+            .and_then(|payload| serde_json::from_str::<UserInfo>(payload).ok());
+        let is_admin = user
+            .as_ref()
+            .is_some_and(|user| config.admin_user_ids.contains(&user.id));
+        let is_service = match (&config.service_token, headers.get(SERVICE_TOKEN)) {
+            (Some(expected), Some(given)) => {
+                !expected.is_empty() && given.as_bytes() == expected.as_bytes()
+            }
+            _ => false,
+        };
+        Caller {
+            user,
+            is_admin,
+            is_service,
+        }
+    }
+
+    /// `Ok` when the caller may use a route requiring `required`
+    pub fn check(&self, required: Capability) -> Result<(), ApiError> {
+        let allowed = match required {
+            Capability::Public => true,
+            Capability::User => self.user.is_some(),
+            Capability::Admin => self.is_admin,
+            Capability::Service => self.is_service,
+        };
+        if allowed {
+            return Ok(());
+        }
+        if self.user.is_none() && !self.is_service {
+            return Err(ApiError::AuthErr {
+                msg: "No Auth Token Found In Request Herders".to_string(),
+                stage: "authorize".to_string(),
+                code: ApiCode::NO_AUTH_TOKEN,
+            });
+        }
+        Err(forbidden(format!(
+            "requires {} capability",
+            required.as_str()
+        )))
+    }
+}
+
+impl Capability {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Capability::Public => "public",
+            Capability::User => "user",
+            Capability::Admin => "admin",
+            Capability::Service => "service",
+        }
+    }
+}
+
+fn forbidden(message: String) -> ApiError {
+    ApiError::CustomError {
+        message: format!("Forbidden: {message}"),
+        code: FORBIDDEN,
+    }
+}
+
+/// Resolve the caller and enforce the route's capability from the authz registry.
+///
+/// Installed with `route_layer`, so `MatchedPath` is always present; routes missing from the
+/// registry are rejected (and refused at startup by `build_router`).
+pub async fn authorize(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let caller = Caller::resolve(request.headers(), &state.authz);
+
+    let prefix = state.config.server.api_prefix.trim_end_matches('/');
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|matched| matched.as_str())
+        .unwrap_or_default();
+    let path = path.strip_prefix(prefix).unwrap_or(path);
+    let required = route_capability(request.method(), path);
+
+    let result = match required {
+        Some(required) => caller.check(required),
+        None => {
+            tracing::error!(method = %request.method(), path, "route has no capability assigned");
+            Err(forbidden(
+                "no capability assigned to this route".to_string(),
+            ))
+        }
+    };
+    if let Err(e) = result {
+        return e.into_response();
+    }
+
+    request.extensions_mut().insert(caller);
+    next.run(request).await
+}
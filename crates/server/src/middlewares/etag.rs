@@ -0,0 +1,103 @@
+use std::convert::Infallible;
+use std::future::Future;
+
+use axum::{
+    extract::FromRequestParts,
+    http::{
+        HeaderValue, StatusCode,
+        header::{ETAG, IF_NONE_MATCH},
+        request::Parts,
+    },
+    response::{IntoResponse, Response},
+};
+use common::error::api_error::ApiError;
+use serde::Serialize;
+
+/// The request's `If-None-Match` header, if any
+#[derive(Debug, Clone, Default)]
+pub struct IfNoneMatch(pub Option<String>);
+
+impl<S> FromRequestParts<S> for IfNoneMatch
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(IfNoneMatch(
+            parts
+                .headers
+                .get(IF_NONE_MATCH)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string),
+        ))
+    }
+}
+
+impl IfNoneMatch {
+    /// Weak comparison (RFC 9110 §13.1.2): `*` or any listed tag equal to `etag`, ignoring `W/`
+    pub fn matches(&self, etag: &str) -> bool {
+        let Some(header) = &self.0 else {
+            return false;
+        };
+        let etag = etag.trim_start_matches("W/");
+        header
+            .split(',')
+            .map(str::trim)
+            .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
+    }
+
+    /// Run `load`, tag its response with `content_etag` and answer with 304 when the client
+    /// already has that tag.
+    ///
+    /// The tag is derived from the data actually read, so a write that no version counter saw
+    /// still changes it. A response that cannot be hashed is sent without `ETag`.
+    pub async fn respond<T, F, Fut>(&self, load: F) -> Result<Conditional<T>, ApiError>
+    where
+        T: Serialize,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, ApiError>>,
+    {
+        let body = load().await?;
+        let etag = content_etag(&body);
+        if let Some(etag) = etag.as_deref()
+            && self.matches(etag)
+        {
+            return Ok(Conditional::NotModified(etag.to_string()));
+        }
+        Ok(Conditional::Fresh { etag, body })
+    }
+}
+
+/// Strong entity tag of a response body: the MD5 of its JSON form with object keys sorted, so
+/// maps iterated in any order get the same tag
+pub fn content_etag<T: Serialize>(body: &T) -> Option<String> {
+    let canonical = serde_json::to_value(body).ok()?;
+    let bytes = serde_json::to_vec(&canonical).ok()?;
+    Some(format!("\"{:x}\"", md5::compute(bytes)))
+}
+
+/// A response that may be answered with `304 Not Modified`
+pub enum Conditional<T> {
+    NotModified(String),
+    Fresh { etag: Option<String>, body: T },
+}
+
+fn etag_header(etag: &str) -> Option<[(axum::http::HeaderName, HeaderValue); 1]> {
+    HeaderValue::from_str(etag)
+        .ok()
+        .map(|value| [(ETAG, value)])
+}
+
+impl<T: IntoResponse> IntoResponse for Conditional<T> {
+    fn into_response(self) -> Response {
+        match self {
+            Conditional::NotModified(etag) => {
+                (StatusCode::NOT_MODIFIED, etag_header(&etag)).into_response()
+            }
+            Conditional::Fresh { etag, body } => {
+                (etag.as_deref().and_then(etag_header), body).into_response()
+            }
+        }
+    }
+}
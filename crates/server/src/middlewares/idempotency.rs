@@ -0,0 +1,205 @@
+use axum::{
+    body::{Body, HttpBody, to_bytes},
+    extract::{MatchedPath, Request, State},
+    http::{HeaderName, HeaderValue, StatusCode, header::CONTENT_LENGTH},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use common::error::api_error::ApiError;
+use tracing::warn;
+
+use super::authz::Caller;
+use crate::{
+    config::server_rss_config,
+    consts::{
+        CONFLICT, IDEMPOTENCY_KEY_HEADER, IDEMPOTENCY_KEY_REUSED, IDEMPOTENT_REPLAYED_HEADER,
+        IDEMPOTENT_REQUEST_TOO_LARGE, INVALID_IDEMPOTENCY_KEY,
+    },
+    state::{
+        app_state::AppState,
+        idempotency::{IdempotencyClaim, IdempotencyStore, StoredResponse, request_hash},
+    },
+};
+
+/// Routes (method, path without the API prefix) honoring the `Idempotency-Key` header
+pub const IDEMPOTENT_ROUTES: &[(&str, &str)] = &[
+    ("POST", "/rss"),
+    ("POST", "/subscriptions/one"),
+    ("POST", "/batch-delete"),
+    ("POST", "/mark-as-read"),
+];
+
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// Largest body of a request sent with an `Idempotency-Key`; larger ones are refused with 413
+pub const MAX_IDEMPOTENT_REQUEST_BYTES: usize = 64 * 1024;
+
+/// Largest response kept for replay; a retry of a request with a larger response runs again
+pub const MAX_STORED_RESPONSE_BYTES: usize = 256 * 1024;
+
+fn valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= MAX_IDEMPOTENCY_KEY_LEN
+        && key.bytes().all(|b| b.is_ascii_graphic())
+}
+
+fn replay(stored: StoredResponse) -> Response {
+    let mut response = Response::new(Body::from(stored.body));
+    *response.status_mut() = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    for (name, value) in stored.headers {
+        if let (Ok(name), Ok(value)) = (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+            response.headers_mut().append(name, value);
+        }
+    }
+    response
+        .headers_mut()
+        .insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    response
+}
+
+/// Replay the stored response of a request retried with the same `Idempotency-Key`.
+///
+/// The first request of a user with a key on one of `IDEMPOTENT_ROUTES` runs as usual, and its
+/// successful response is kept for `rss.idempotency_key_ttl_secs`; retries get that response
+/// back (with `Idempotent-Replayed: true`) without running the handler again. A retry arriving
+/// while the first request is still running is refused with 409, and reusing the key with a
+/// different body with 422. Failed responses and responses over `MAX_STORED_RESPONSE_BYTES`
+/// are not kept, so the retry runs again. Installed with `route_layer` inside `authorize`; fails
+/// open when Redis is unreachable.
+pub async fn idempotency(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let prefix = state.config.server.api_prefix.trim_end_matches('/');
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|matched| matched.as_str())
+        .unwrap_or_default();
+    let path = path.strip_prefix(prefix).unwrap_or(path).to_string();
+    let method = request.method().as_str().to_string();
+    let routed = IDEMPOTENT_ROUTES
+        .iter()
+        .any(|(m, p)| *m == method && *p == path);
+    let user_id = request
+        .extensions()
+        .get::<Caller>()
+        .and_then(|caller| caller.user.as_ref())
+        .map(|user| user.id);
+    let key = request
+        .headers()
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .map(|key| key.to_str().map(str::to_string));
+    let (true, Some(user_id), Some(key)) = (routed, user_id, key) else {
+        return next.run(request).await;
+    };
+    let key = match key {
+        Ok(key) if valid_key(&key) => key,
+        _ => {
+            return ApiError::CustomError {
+                message: format!(
+                    "{IDEMPOTENCY_KEY_HEADER} must be 1 to {MAX_IDEMPOTENCY_KEY_LEN} printable ASCII characters"
+                ),
+                code: INVALID_IDEMPOTENCY_KEY,
+            }
+            .into_response();
+        }
+    };
+
+    let (parts, body) = request.into_parts();
+    let body = match to_bytes(body, MAX_IDEMPOTENT_REQUEST_BYTES).await {
+        Ok(body) => body,
+        Err(_) => {
+            return ApiError::CustomError {
+                message: format!(
+                    "Requests with an {IDEMPOTENCY_KEY_HEADER} are limited to {MAX_IDEMPOTENT_REQUEST_BYTES} bytes"
+                ),
+                code: IDEMPOTENT_REQUEST_TOO_LARGE,
+            }
+            .into_response();
+        }
+    };
+    let hash = request_hash(&body);
+    let request = Request::from_parts(parts, Body::from(body));
+
+    let config = server_rss_config();
+    let store = IdempotencyStore::new(&state);
+    let redis_key = store.key(user_id, &format!("{method} {path}"), &key);
+    match store
+        .claim(&redis_key, &hash, config.idempotency_in_flight_ttl_secs)
+        .await
+    {
+        Ok(IdempotencyClaim::Claimed) => {}
+        Ok(IdempotencyClaim::Mismatch) => {
+            tracing::info!(user_id, path, "idempotency key reused with another body");
+            return ApiError::CustomError {
+                message: "This Idempotency-Key was used with a different request body".to_string(),
+                code: IDEMPOTENCY_KEY_REUSED,
+            }
+            .into_response();
+        }
+        Ok(IdempotencyClaim::InFlight) => {
+            tracing::info!(user_id, path, "idempotency key in flight");
+            return ApiError::CustomError {
+                message: "A request with this Idempotency-Key is still being processed".to_string(),
+                code: CONFLICT,
+            }
+            .into_response();
+        }
+        Ok(IdempotencyClaim::Completed(stored)) => {
+            tracing::info!(user_id, path, "idempotency key replayed");
+            return replay(stored);
+        }
+        Err(e) => {
+            warn!(user_id, error = ?e, "idempotency store unavailable, running the request");
+            return next.run(request).await;
+        }
+    }
+
+    let response = next.run(request).await;
+    let storable = response
+        .body()
+        .size_hint()
+        .upper()
+        .is_some_and(|len| len <= MAX_STORED_RESPONSE_BYTES as u64);
+    if !response.status().is_success() || !storable {
+        if let Err(e) = store.release(&redis_key).await {
+            warn!(user_id, error = ?e, "idempotency: failed to release key");
+        }
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, MAX_STORED_RESPONSE_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!(user_id, error = %e, "idempotency: response body unreadable");
+            if let Err(e) = store.release(&redis_key).await {
+                warn!(user_id, error = ?e, "idempotency: failed to release key");
+            }
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    // only text bodies are kept; a retry of anything else runs again
+    let saved = match std::str::from_utf8(&bytes) {
+        Ok(body) => {
+            let stored = StoredResponse {
+                status: parts.status.as_u16(),
+                headers: parts
+                    .headers
+                    .iter()
+                    .filter(|(name, _)| *name != CONTENT_LENGTH)
+                    .filter_map(|(name, value)| {
+                        Some((name.to_string(), value.to_str().ok()?.to_string()))
+                    })
+                    .collect(),
+                body: body.to_string(),
+            };
+            store
+                .complete(&redis_key, &hash, stored, config.idempotency_key_ttl_secs)
+                .await
+        }
+        Err(_) => store.release(&redis_key).await,
+    };
+    if let Err(e) = saved {
+        warn!(user_id, error = ?e, "idempotency: failed to store the response");
+    }
+    Response::from_parts(parts, Body::from(bytes))
+}
@@ -1,12 +1,96 @@
 use axum::{
     body::{Body, to_bytes},
     extract::Request,
-    http::StatusCode,
+    http::{HeaderName, HeaderValue, StatusCode, header::CONTENT_TYPE},
     middleware::Next,
     response::{IntoResponse, Response},
 };
 use http_body_util::BodyExt;
 use tracing::*;
+use uuid::Uuid;
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest incoming `x-request-id` honored; longer ones are replaced
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Largest error body `request_id` is added to
+const MAX_ERROR_BODY_BYTES: usize = 64 * 1024;
+
+/// Id of the current request, available as a request extension
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+fn incoming_request_id(request: &Request) -> Option<String> {
+    let id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)?
+        .to_str()
+        .ok()?
+        .trim();
+    let valid = !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id.bytes().all(|b| b.is_ascii_graphic());
+    valid.then(|| id.to_string())
+}
+
+/// Honor the incoming `x-request-id` or generate one, run the request in a span carrying it,
+/// and return it in the `x-request-id` response header and in the body of JSON errors
+pub async fn request_id(mut request: Request, next: Next) -> Response {
+    let id = incoming_request_id(&request).unwrap_or_else(|| Uuid::new_v4().simple().to_string());
+    request.extensions_mut().insert(RequestId(id.clone()));
+    let span = info_span!(
+        "request",
+        request_id = %id,
+        method = %request.method(),
+        uri = %request.uri(),
+    );
+
+    let response = next.run(request).instrument(span).await;
+    let mut response = if response.status().is_client_error() || response.status().is_server_error()
+    {
+        with_request_id_in_body(response, &id).await
+    } else {
+        response
+    };
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// Add `request_id` to a JSON object body; other bodies are returned unchanged
+async fn with_request_id_in_body(response: Response, id: &str) -> Response {
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    if !is_json {
+        return response;
+    }
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, MAX_ERROR_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!(error = %e, "failed to read error body");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to read error body",
+            )
+                .into_response();
+        }
+    };
+    let body = match serde_json::from_slice::<serde_json::Value>(&bytes) {
+        Ok(serde_json::Value::Object(mut object)) => {
+            object.insert("request_id".to_string(), id.into());
+            parts.headers.remove(axum::http::header::CONTENT_LENGTH);
+            Body::from(serde_json::Value::Object(object).to_string())
+        }
+        _ => Body::from(bytes),
+    };
+    Response::from_parts(parts, body)
+}
 
 pub async fn log_response(req: Request<Body>, next: Next) -> impl IntoResponse {
     let resp = next.run(req).await.into_response();
@@ -0,0 +1,38 @@
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::state::metrics::metrics;
+
+/// Route label of requests that matched no route, so unknown paths cannot grow the label set
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Record count and latency of every request, labelled by its route template
+/// (`/rss/{id}`, not `/rss/42`)
+pub async fn track_metrics(request: Request, next: Next) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+    let method = request.method().to_string();
+    let started = Instant::now();
+
+    let response = next.run(request).await;
+
+    let status = format!("{}xx", response.status().as_u16() / 100);
+    let metrics = metrics();
+    metrics
+        .http_requests
+        .with_label_values(&[&method, &route, &status])
+        .inc();
+    metrics
+        .http_request_duration
+        .with_label_values(&[&method, &route])
+        .observe(started.elapsed().as_secs_f64());
+    response
+}
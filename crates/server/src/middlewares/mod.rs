@@ -1,2 +1,9 @@
 pub mod auth;
+pub mod authz;
+pub mod etag;
+pub mod idempotency;
 pub mod log;
+pub mod metrics;
+pub mod query;
+pub mod rate_limit;
+pub mod read_after_write;
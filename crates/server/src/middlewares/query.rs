@@ -0,0 +1,30 @@
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use common::error::api_error::ApiError;
+use serde::de::DeserializeOwned;
+
+use crate::consts::INVALID_QUERY_PARAM;
+
+/// `axum::extract::Query` whose rejection is an `ApiError` (400).
+///
+/// The message keeps axum's deserialization detail, which names the offending parameter,
+/// e.g. `Failed to deserialize query string: user_interest_ids: invalid entry `abc`: ...`.
+pub struct Query<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for Query<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::extract::Query::<T>::from_request_parts(parts, state).await {
+            Ok(axum::extract::Query(value)) => Ok(Query(value)),
+            Err(rejection) => Err(ApiError::CustomError {
+                message: rejection.body_text(),
+                code: INVALID_QUERY_PARAM,
+            }),
+        }
+    }
+}
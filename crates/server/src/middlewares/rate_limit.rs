@@ -0,0 +1,75 @@
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{HeaderValue, header::RETRY_AFTER},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use common::error::api_error::ApiError;
+
+use super::authz::Caller;
+use crate::{
+    config::server_rss_config,
+    consts::RATE_LIMITED,
+    state::{
+        app_state::AppState,
+        rate_limit::{RateDecision, RateLimiter},
+        usage::UsageEvent,
+    },
+};
+
+/// Bucket shared by every route that queues a verification
+pub const VERIFY_BUCKET: &str = "verify";
+
+/// Routes (method, path without the API prefix) that take a token from the caller's bucket
+pub const RATE_LIMITED_ROUTES: &[(&str, &str, &str)] = &[
+    ("POST", "/verify", VERIFY_BUCKET),
+    ("POST", "/stream-verify", VERIFY_BUCKET),
+];
+
+/// Reject with 429 and `Retry-After` once the caller has used up the bucket of the route.
+///
+/// Installed with `route_layer` inside `authorize`, so the `Caller` is already resolved;
+/// requests without a user (public or service routes) are never limited.
+pub async fn rate_limit(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let prefix = state.config.server.api_prefix.trim_end_matches('/');
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|matched| matched.as_str())
+        .unwrap_or_default();
+    let path = path.strip_prefix(prefix).unwrap_or(path);
+    let bucket = RATE_LIMITED_ROUTES
+        .iter()
+        .find(|(m, p, _)| *m == request.method().as_str() && *p == path)
+        .map(|(_, _, bucket)| *bucket);
+    let user_id = request
+        .extensions()
+        .get::<Caller>()
+        .and_then(|caller| caller.user.as_ref())
+        .map(|user| user.id);
+    let per_minute = server_rss_config().verify_rate_limit_per_minute;
+
+    if let (Some(bucket), Some(user_id)) = (bucket, user_id)
+        && per_minute > 0
+        && let RateDecision::Limited { retry_after } = RateLimiter::new(&state)
+            .acquire_or_allow(bucket, user_id, per_minute)
+            .await
+    {
+        state.usage.record(user_id, UsageEvent::RateLimited, 1);
+        let retry_after_secs = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+        tracing::info!(user_id, bucket, retry_after_secs, "rate limited");
+        let mut response = ApiError::CustomError {
+            message: format!(
+                "Too many requests: at most {per_minute} per minute, retry in {retry_after_secs}s"
+            ),
+            code: RATE_LIMITED,
+        }
+        .into_response();
+        response
+            .headers_mut()
+            .insert(RETRY_AFTER, HeaderValue::from(retry_after_secs));
+        return response;
+    }
+
+    next.run(request).await
+}
@@ -0,0 +1,37 @@
+use axum::{
+    extract::{Request, State},
+    http::Method,
+    middleware::Next,
+    response::Response,
+};
+
+use super::authz::Caller;
+use crate::state::app_state::AppState;
+
+/// After a user's request that may write (anything but `GET`, `HEAD` and `OPTIONS`), serve their
+/// listings from the primary for a while: see `AppState::read_conn_for`.
+///
+/// Installed with `route_layer` inside `authorize`, so the `Caller` is already resolved. The mark
+/// is set once the handler is done, whatever its status, since a failed request may have
+/// written part of its changes.
+pub async fn read_after_write(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let writer = (![Method::GET, Method::HEAD, Method::OPTIONS].contains(request.method()))
+        .then(|| {
+            request
+                .extensions()
+                .get::<Caller>()
+                .and_then(|caller| caller.user.as_ref())
+                .map(|user| user.id)
+        })
+        .flatten();
+
+    let response = next.run(request).await;
+    if let Some(user_id) = writer {
+        state.mark_write(user_id).await;
+    }
+    response
+}
@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use super::page::Pagination;
+
+/// A destructive operation recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    /// `DELETE /rss/{id}`
+    RssSourceDelete,
+    /// `PUT /rss/{id}`
+    RssSourceUpdate,
+    /// `POST /batch-delete`
+    PapersBatchDelete,
+    /// `DELETE /subscriptions/{subscription_id}`
+    SubscriptionDelete,
+    /// `POST /interests` with no interests
+    InterestsClear,
+    /// `POST /subscriptions` with no sources
+    SubscriptionsClear,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::RssSourceDelete => "rss_source_delete",
+            AuditAction::RssSourceUpdate => "rss_source_update",
+            AuditAction::PapersBatchDelete => "papers_batch_delete",
+            AuditAction::SubscriptionDelete => "subscription_delete",
+            AuditAction::InterestsClear => "interests_clear",
+            AuditAction::SubscriptionsClear => "subscriptions_clear",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [
+            AuditAction::RssSourceDelete,
+            AuditAction::RssSourceUpdate,
+            AuditAction::PapersBatchDelete,
+            AuditAction::SubscriptionDelete,
+            AuditAction::InterestsClear,
+            AuditAction::SubscriptionsClear,
+        ]
+        .into_iter()
+        .find(|action| action.as_str() == value)
+    }
+
+    /// What `target_ids` of the action refer to
+    pub fn target_type(&self) -> &'static str {
+        match self {
+            AuditAction::RssSourceDelete
+            | AuditAction::RssSourceUpdate
+            | AuditAction::SubscriptionsClear => "rss_source",
+            AuditAction::PapersBatchDelete => "paper",
+            AuditAction::SubscriptionDelete => "subscription",
+            AuditAction::InterestsClear => "interest",
+        }
+    }
+}
+
+/// A row of the audit log
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub actor_id: i64,
+    pub actor_open_id: Option<String>,
+    pub action: AuditAction,
+    pub target_type: String,
+    pub target_ids: Vec<i64>,
+    /// `request_id` of the request plus details of the operation
+    #[schema(value_type = Object)]
+    pub metadata: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuditLogResponse {
+    pub pagination: Pagination,
+    pub entries: Vec<AuditEntry>,
+}
@@ -0,0 +1,266 @@
+use std::error::Error;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use quick_xml::{Reader, events::Event};
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::redirect::Policy;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Format of a feed, from its root element
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedKind {
+    /// `<rss>` (RSS 0.9x / 2.0)
+    Rss,
+    /// `<rdf:RDF>` (RSS 1.0)
+    Rdf,
+    /// `<feed>` (Atom)
+    Atom,
+}
+
+/// Outcome of looking for the root element of a possibly truncated document
+enum Sniff {
+    /// The root element, or a definite reason the document is not a feed
+    Done(Result<FeedKind, String>),
+    /// The input ended before the root element; the reason applies if nothing follows
+    Short(String),
+}
+
+fn sniff(body: &[u8]) -> Sniff {
+    let mut reader = Reader::from_reader(body);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                return Sniff::Done(match e.local_name().as_ref() {
+                    b"rss" => Ok(FeedKind::Rss),
+                    b"RDF" => Ok(FeedKind::Rdf),
+                    b"feed" => Ok(FeedKind::Atom),
+                    other => Err(format!(
+                        "not an RSS or Atom feed (root element <{}>)",
+                        String::from_utf8_lossy(other)
+                    )),
+                });
+            }
+            Ok(Event::Eof) => return Sniff::Short("empty document".to_string()),
+            Ok(Event::Text(text)) if !text.iter().all(u8::is_ascii_whitespace) => {
+                return Sniff::Done(Err(
+                    "not an RSS or Atom feed (text before the root element)".to_string(),
+                ));
+            }
+            // declaration, doctype, comments, processing instructions, whitespace
+            Ok(_) => {}
+            // possibly a construct cut by the end of the input
+            Err(e) => return Sniff::Short(format!("not an RSS or Atom feed: {e}")),
+        }
+    }
+}
+
+/// Tell whether `body` is an RSS or Atom document by its root element
+pub fn sniff_feed(body: &[u8]) -> Result<FeedKind, String> {
+    match sniff(body) {
+        Sniff::Done(result) => result,
+        Sniff::Short(reason) => Err(reason),
+    }
+}
+
+/// Limits of the fetch that validates a feed URL
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeedFetchLimits {
+    pub timeout: Duration,
+    /// Bytes read at most while looking for the root element
+    pub max_bytes: usize,
+    pub max_redirects: usize,
+    /// Also fetch from loopback, private, link-local and other non-public addresses
+    pub allow_private_addresses: bool,
+}
+
+/// Whether `ip` is a publicly routable unicast address. Loopback, private, shared (CGNAT),
+/// link-local (incl. cloud metadata at 169.254.169.254), documentation, benchmarking,
+/// reserved and multicast ranges are not; IPv4-mapped and NAT64 IPv6 addresses are judged
+/// by their IPv4 address.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, c, _] = ip.octets();
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                || a == 0
+                || (a == 100 && (64..128).contains(&b))
+                || (a == 192 && b == 0 && c == 0)
+                || (a == 198 && (18..20).contains(&b))
+                || a >= 240)
+        }
+        IpAddr::V6(ip) => {
+            if let Some(v4) = ip.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(v4));
+            }
+            let segments = ip.segments();
+            if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+                let [.., a, b, c, d] = ip.octets();
+                return is_public_ip(IpAddr::V4(Ipv4Addr::new(a, b, c, d)));
+            }
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_multicast()
+                // unique local fc00::/7, link-local fe80::/10, documentation 2001:db8::/32
+                || (segments[0] & 0xfe00) == 0xfc00
+                || (segments[0] & 0xffc0) == 0xfe80
+                || (segments[0] == 0x2001 && segments[1] == 0xdb8))
+        }
+    }
+}
+
+/// Refuse URLs that are not http(s) or name a non-public address literally
+fn check_target(url: &reqwest::Url, allow_private_addresses: bool) -> Result<(), String> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("unsupported URL scheme {}", url.scheme()));
+    }
+    let host = url.host_str().ok_or("the URL has no host")?;
+    // IPv6 literals keep their brackets in `host_str`
+    let Ok(ip) = host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+    else {
+        // a name, checked once resolved
+        return Ok(());
+    };
+    if !allow_private_addresses && !is_public_ip(ip) {
+        return Err(format!("address {ip} is not allowed"));
+    }
+    Ok(())
+}
+
+/// Resolves host names to their public addresses only, so a name pointing at an internal
+/// address is refused when connecting (after redirects too)
+struct PublicAddressResolver;
+
+impl Resolve for PublicAddressResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(resolve_public(name))
+    }
+}
+
+async fn resolve_public(name: Name) -> Result<Addrs, Box<dyn Error + Send + Sync>> {
+    let host = name.as_str();
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, 0))
+        .await?
+        .filter(|addr| is_public_ip(addr.ip()))
+        .collect();
+    if addrs.is_empty() {
+        return Err(format!("{host} resolves to no public address").into());
+    }
+    Ok(Box::new(addrs.into_iter()))
+}
+
+/// `e` and its sources, e.g. `error sending request: dns error: ...`
+fn error_chain(e: &reqwest::Error) -> String {
+    let mut message = e.to_string();
+    let mut source = e.source();
+    while let Some(cause) = source {
+        message.push_str(&format!(": {cause}"));
+        source = cause.source();
+    }
+    message
+}
+
+/// Fetch `url` and check it serves an RSS or Atom feed.
+///
+/// Only public addresses are contacted (unless `allow_private_addresses`), at most
+/// `max_redirects` redirects are followed and no more than `max_bytes` of the body are read.
+pub async fn validate_feed_url(url: &str, limits: &FeedFetchLimits) -> Result<FeedKind, String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("invalid URL: {e}"))?;
+    check_target(&parsed, limits.allow_private_addresses)?;
+
+    let (max_redirects, allow_private_addresses) =
+        (limits.max_redirects, limits.allow_private_addresses);
+    let mut builder = reqwest::Client::builder()
+        .timeout(limits.timeout)
+        .no_proxy()
+        .redirect(Policy::custom(move |attempt| {
+            if attempt.previous().len() > max_redirects {
+                return attempt.error(format!("more than {max_redirects} redirects"));
+            }
+            match check_target(attempt.url(), allow_private_addresses) {
+                Ok(()) => attempt.follow(),
+                Err(reason) => attempt.error(format!("redirected to a refused URL: {reason}")),
+            }
+        }));
+    if !limits.allow_private_addresses {
+        builder = builder.dns_resolver(Arc::new(PublicAddressResolver));
+    }
+    let client = builder
+        .build()
+        .map_err(|e| format!("failed to build the HTTP client: {e}"))?;
+
+    let timed_out = || format!("no response within {}s", limits.timeout.as_secs_f32());
+    let mut response = client.get(parsed).send().await.map_err(|e| {
+        if e.is_timeout() {
+            timed_out()
+        } else {
+            format!("failed to fetch the feed: {}", error_chain(&e))
+        }
+    })?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("the feed URL returned HTTP {status}"));
+    }
+
+    let mut body = Vec::new();
+    let mut reason = "empty document".to_string();
+    loop {
+        let chunk = response.chunk().await.map_err(|e| {
+            if e.is_timeout() {
+                timed_out()
+            } else {
+                format!("failed to read the feed: {}", error_chain(&e))
+            }
+        })?;
+        let Some(chunk) = chunk else {
+            return Err(reason);
+        };
+        let room = limits.max_bytes - body.len();
+        body.extend_from_slice(&chunk[..chunk.len().min(room)]);
+        match sniff(&body) {
+            Sniff::Done(result) => return result,
+            Sniff::Short(_) if body.len() >= limits.max_bytes => {
+                return Err(format!(
+                    "no RSS or Atom root element in the first {} bytes",
+                    limits.max_bytes
+                ));
+            }
+            Sniff::Short(short) => reason = short,
+        }
+    }
+}
+
+/// Key identifying the same feed behind different spellings of its URL: scheme and host are
+/// lowercased, default ports, fragments and trailing slashes dropped. The scheme is kept, so
+/// `http` and `https` URLs stay distinct. Unparsable URLs are compared as trimmed text.
+pub fn normalize_feed_url(url: &str) -> String {
+    let url = url.trim();
+    let Ok(parsed) = reqwest::Url::parse(url) else {
+        return url.trim_end_matches('/').to_string();
+    };
+    let mut key = format!("{}://", parsed.scheme());
+    if let Some(host) = parsed.host_str() {
+        key.push_str(host);
+    }
+    if let Some(port) = parsed.port() {
+        key.push_str(&format!(":{port}"));
+    }
+    key.push_str(parsed.path().trim_end_matches('/'));
+    if let Some(query) = parsed.query() {
+        key.push('?');
+        key.push_str(query);
+    }
+    key
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Normalized filters the server actually applied to a listing query.
+///
+/// Absent fields were either not provided or ignored (e.g. empty strings).
+#[derive(Serialize, Deserialize, ToSchema, Debug, Default, Clone, PartialEq)]
+pub struct AppliedFilters {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keyword: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_interest_ids: Option<Vec<i64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rss_source_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_pagination: Option<bool>,
+}
+
+/// Trim a free-text filter, treating blank values as absent
+pub fn normalize_text(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
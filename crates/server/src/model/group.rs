@@ -0,0 +1,65 @@
+use std::cmp::Reverse;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupBy {
+    Day,
+}
+
+/// Items of one page falling on the same local day
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, PartialEq)]
+pub struct DaySection<T> {
+    /// Local date (`YYYY-MM-DD`), `null` for items without a date
+    pub date: Option<NaiveDate>,
+    /// Number of items of this section on the current page
+    pub count: usize,
+    pub papers: Vec<T>,
+}
+
+/// Split a page into day sections in `tz`, one per local day, newest day first and items
+/// without a date last. Items of the same day keep their page order.
+///
+/// `next` is the date of the first item of the following page (if any); the returned flag
+/// is `true` when that item falls on the same day as the last section, i.e. the section
+/// continues on the next page.
+pub fn group_by_day<T>(
+    items: Vec<T>,
+    tz: Tz,
+    date_of: impl Fn(&T) -> Option<DateTime<Utc>>,
+    next: Option<Option<DateTime<Utc>>>,
+) -> (Vec<DaySection<T>>, bool) {
+    let local_day = |date: Option<DateTime<Utc>>| date.map(|d| d.with_timezone(&tz).date_naive());
+
+    let mut items: Vec<(Option<NaiveDate>, T)> = items
+        .into_iter()
+        .map(|item| (local_day(date_of(&item)), item))
+        .collect();
+    // stable: the page order is kept within a day
+    items.sort_by_key(|(day, _)| (day.is_none(), Reverse(*day)));
+
+    let mut sections: Vec<DaySection<T>> = Vec::new();
+    for (day, item) in items {
+        match sections.last_mut() {
+            Some(section) if section.date == day => {
+                section.count += 1;
+                section.papers.push(item);
+            }
+            _ => sections.push(DaySection {
+                date: day,
+                count: 1,
+                papers: vec![item],
+            }),
+        }
+    }
+
+    let section_continues = match (sections.last(), next) {
+        (Some(section), Some(next)) => section.date == local_day(next),
+        _ => false,
+    };
+    (sections, section_continues)
+}
@@ -0,0 +1,103 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::de::Error as DeError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use utoipa::openapi::RefOr;
+use utoipa::openapi::schema::{ObjectBuilder, Schema, Type};
+
+/// Comma-separated list query parameter, e.g. `?user_interest_ids=1,2,3`.
+///
+/// Entries are trimmed and blank entries skipped (`" 1, ,2 "` is `[1, 2]`); any entry that
+/// does not parse as `T` fails the whole parameter. Use `de_opt_comma_separated` for
+/// optional parameters so an empty value means "not provided".
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CommaSeparated<T>(pub Vec<T>);
+
+impl<T> CommaSeparated<T> {
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T> FromStr for CommaSeparated<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                entry
+                    .parse::<T>()
+                    .map_err(|e| format!("invalid entry `{entry}`: {e}"))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(CommaSeparated)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for CommaSeparated<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        raw.parse().map_err(D::Error::custom)
+    }
+}
+
+impl<T: Display> Serialize for CommaSeparated<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let joined = self
+            .0
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        serializer.serialize_str(&joined)
+    }
+}
+
+impl<T> utoipa::PartialSchema for CommaSeparated<T> {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .format(Some(utoipa::openapi::SchemaFormat::Custom(
+                "comma-separated".to_string(),
+            )))
+            .description(Some(
+                "Comma-separated values, e.g. `1,2,3`. Blank entries are ignored; an empty value means not provided.",
+            ))
+            .into()
+    }
+}
+
+impl<T> utoipa::ToSchema for CommaSeparated<T> {}
+
+/// Optional `CommaSeparated`: empty (or only commas/whitespace) becomes `None`
+pub fn de_opt_comma_separated<'de, D, T>(
+    deserializer: D,
+) -> Result<Option<CommaSeparated<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let Some(raw) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    let list: CommaSeparated<T> = raw.parse().map_err(D::Error::custom)?;
+    Ok((!list.0.is_empty()).then_some(list))
+}
@@ -1,2 +1,15 @@
+pub mod audit;
 pub mod base;
+pub mod feed_url;
+pub mod filter;
+pub mod group;
+pub mod list;
+pub mod note;
 pub mod page;
+pub mod preset;
+pub mod schedule;
+pub mod suggestion;
+pub mod tz;
+pub mod usage;
+pub mod verify_stats;
+pub mod verify_stream;
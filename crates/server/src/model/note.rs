@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use common::{error::api_error::*, prelude::ApiCode};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Longest note accepted, in bytes of UTF-8
+pub const MAX_NOTE_BYTES: usize = 4096;
+
+/// A user's note on a paper
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PaperNote {
+    pub note: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Body of `PUT /papers/{paper_id}/note`
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct PaperNoteInput {
+    pub note: String,
+}
+
+impl PaperNoteInput {
+    /// The trimmed note, or an error when it is blank or longer than `MAX_NOTE_BYTES`
+    pub fn validate(self) -> Result<String, ApiError> {
+        let note = self.note.trim();
+        if note.is_empty() {
+            return Err(ApiError::CustomError {
+                message: "note must not be blank; use DELETE to remove it".to_string(),
+                code: ApiCode::COMMON_FEED_ERROR,
+            });
+        }
+        if note.len() > MAX_NOTE_BYTES {
+            return Err(ApiError::CustomError {
+                message: format!("note exceeds {MAX_NOTE_BYTES} bytes"),
+                code: ApiCode::COMMON_FEED_ERROR,
+            });
+        }
+        Ok(note.to_string())
+    }
+}
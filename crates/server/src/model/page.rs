@@ -29,6 +29,42 @@ pub struct Pagination {
     pub total: u64,
     /// Total number of pages
     pub total_pages: u64,
+    /// Whether a page exists after the current one
+    #[serde(default)]
+    pub has_next: bool,
+    /// Whether a page exists before the current one
+    #[serde(default)]
+    pub has_prev: bool,
+}
+
+impl Pagination {
+    pub fn new(page: i32, page_size: i32, total: u64) -> Self {
+        let total_pages = if page_size > 0 {
+            total.div_ceil(page_size as u64)
+        } else {
+            0
+        };
+        Pagination {
+            page,
+            page_size,
+            total,
+            total_pages,
+            has_next: page > 0 && (page as u64) < total_pages,
+            has_prev: page > 1,
+        }
+    }
+
+    /// Pagination info for an unpaginated response that holds all `total` items
+    pub fn all(total: u64) -> Self {
+        Pagination {
+            page: 1,
+            page_size: total as i32,
+            total,
+            total_pages: 1,
+            has_next: false,
+            has_prev: false,
+        }
+    }
 }
 
 impl Page {
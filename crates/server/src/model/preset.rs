@@ -0,0 +1,129 @@
+use chrono::{DateTime, Utc};
+use common::{error::api_error::*, prelude::ApiCode};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct InterestPreset {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    /// Interests in display order
+    pub interests: Vec<String>,
+    /// Channels the preset is meant for, e.g. `["arxiv"]`
+    pub channel_hints: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Body of the admin create / update endpoints
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct InterestPresetInput {
+    pub name: String,
+    pub description: Option<String>,
+    pub interests: Vec<String>,
+    #[serde(default)]
+    pub channel_hints: Vec<String>,
+}
+
+fn invalid(message: String) -> ApiError {
+    ApiError::CustomError {
+        message,
+        code: ApiCode::COMMON_FEED_ERROR,
+    }
+}
+
+/// Trim, drop blanks and duplicates (case-insensitive), keeping the first occurrence's order
+pub fn normalize_interests(interests: &[String]) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    interests
+        .iter()
+        .map(|interest| interest.trim())
+        .filter(|interest| !interest.is_empty() && seen.insert(interest.to_lowercase()))
+        .map(str::to_string)
+        .collect()
+}
+
+impl InterestPresetInput {
+    /// Normalized copy, or an error when it could never be applied
+    pub fn validate(self, max_prompt_number: usize) -> Result<Self, ApiError> {
+        let name = self.name.trim().to_string();
+        if name.is_empty() {
+            return Err(invalid("preset name must not be empty".to_string()));
+        }
+        let interests = normalize_interests(&self.interests);
+        if interests.is_empty() {
+            return Err(invalid(
+                "preset must contain at least one interest".to_string(),
+            ));
+        }
+        if interests.len() > max_prompt_number {
+            return Err(invalid(format!(
+                "Exceeded maximum interests limit: {max_prompt_number} (provided: {})",
+                interests.len()
+            )));
+        }
+        Ok(InterestPresetInput {
+            name,
+            description: self
+                .description
+                .map(|d| d.trim().to_string())
+                .filter(|d| !d.is_empty()),
+            interests,
+            channel_hints: normalize_interests(&self.channel_hints),
+        })
+    }
+}
+
+/// Result of merging a preset into a user's interests
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PresetMerge {
+    /// Every preset interest is already there
+    Unchanged,
+    /// `interests` is the new full list: the existing ones, then `added`
+    Merged {
+        interests: Vec<String>,
+        added: Vec<String>,
+    },
+    /// The merge exceeds the limit; `must_remove` of `removable` (existing interests that are
+    /// not part of the preset) have to go first
+    Conflict {
+        must_remove: usize,
+        removable: Vec<String>,
+    },
+}
+
+/// Merge (never replace) `preset` into `existing`, comparing case-insensitively
+pub fn merge_preset(
+    existing: &[String],
+    preset: &[String],
+    max_prompt_number: usize,
+) -> PresetMerge {
+    let existing = normalize_interests(existing);
+    let known: std::collections::HashSet<String> =
+        existing.iter().map(|i| i.to_lowercase()).collect();
+    let added: Vec<String> = normalize_interests(preset)
+        .into_iter()
+        .filter(|interest| !known.contains(&interest.to_lowercase()))
+        .collect();
+    if added.is_empty() {
+        return PresetMerge::Unchanged;
+    }
+
+    let total = existing.len() + added.len();
+    if total > max_prompt_number {
+        let in_preset: std::collections::HashSet<String> =
+            preset.iter().map(|i| i.trim().to_lowercase()).collect();
+        return PresetMerge::Conflict {
+            must_remove: total - max_prompt_number,
+            removable: existing
+                .into_iter()
+                .filter(|interest| !in_preset.contains(&interest.to_lowercase()))
+                .collect(),
+        };
+    }
+
+    let mut interests = existing;
+    interests.extend(added.iter().cloned());
+    PresetMerge::Merged { interests, added }
+}
@@ -0,0 +1,136 @@
+use chrono::{DateTime, Duration, LocalResult, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+use common::{error::api_error::*, prelude::ApiCode};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A user's daily verification run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct VerifySchedule {
+    pub user_id: i64,
+    pub enabled: bool,
+    /// Local hour (0-23) of `timezone` the run is queued at
+    pub hour_of_day: u8,
+    /// IANA timezone name, e.g. `Europe/Paris`
+    pub timezone: String,
+    pub channel: Option<String>,
+    /// Stop once this many papers matched; the server default when unset
+    pub max_match_limit: Option<i32>,
+    /// When the scheduler last queued the user
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Body of `PUT /verify-schedule`
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct VerifyScheduleInput {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub hour_of_day: u8,
+    /// Defaults to the user's `zoneinfo`, then UTC
+    pub timezone: Option<String>,
+    pub channel: Option<String>,
+    pub max_match_limit: Option<i32>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn invalid(message: String) -> ApiError {
+    ApiError::CustomError {
+        message,
+        code: ApiCode::COMMON_FEED_ERROR,
+    }
+}
+
+impl VerifyScheduleInput {
+    /// Normalized copy, or an error when the scheduler could never run it
+    pub fn validate(self) -> Result<Self, ApiError> {
+        if self.hour_of_day > 23 {
+            return Err(invalid(format!(
+                "hour_of_day must be between 0 and 23, got {}",
+                self.hour_of_day
+            )));
+        }
+        if let Some(limit) = self.max_match_limit.filter(|limit| *limit <= 0) {
+            return Err(invalid(format!(
+                "max_match_limit must be positive, got {limit}"
+            )));
+        }
+        let channel = self
+            .channel
+            .map(|channel| channel.trim().to_string())
+            .filter(|channel| !channel.is_empty());
+        Ok(VerifyScheduleInput { channel, ..self })
+    }
+}
+
+/// `hour` o'clock on `date` in `tz`.
+///
+/// An hour skipped by a DST jump runs at the first instant after the gap, an hour that
+/// happens twice runs at its first occurrence.
+fn slot_on(date: NaiveDate, hour: u8, tz: Tz) -> DateTime<Utc> {
+    let naive = date
+        .and_hms_opt(hour.min(23) as u32, 0, 0)
+        .expect("hour is clamped to 0-23");
+    match tz.from_local_datetime(&naive) {
+        LocalResult::Single(at) | LocalResult::Ambiguous(at, _) => at.with_timezone(&Utc),
+        LocalResult::None => tz
+            .from_local_datetime(&(naive + Duration::hours(1)))
+            .earliest()
+            .map(|at| at.with_timezone(&Utc))
+            .unwrap_or_else(|| Utc.from_utc_datetime(&naive)),
+    }
+}
+
+/// The most recent slot at or before `now`: today's when `hour` has passed in `tz`, else yesterday's
+pub fn latest_slot(hour: u8, tz: Tz, now: DateTime<Utc>) -> DateTime<Utc> {
+    let today = now.with_timezone(&tz).date_naive();
+    let slot = slot_on(today, hour, tz);
+    if slot <= now {
+        return slot;
+    }
+    slot_on(today.pred_opt().unwrap_or(today), hour, tz)
+}
+
+/// The first slot strictly after `now`
+pub fn next_slot(hour: u8, tz: Tz, now: DateTime<Utc>) -> DateTime<Utc> {
+    let today = now.with_timezone(&tz).date_naive();
+    let slot = slot_on(today, hour, tz);
+    if slot > now {
+        return slot;
+    }
+    slot_on(today.succ_opt().unwrap_or(today), hour, tz)
+}
+
+impl VerifySchedule {
+    /// The stored timezone, UTC if it no longer parses
+    pub fn tz(&self) -> Tz {
+        self.timezone.parse().unwrap_or(Tz::UTC)
+    }
+
+    /// The slot to run now, if any.
+    ///
+    /// A slot is due once it is after both the last run and the last change of the schedule,
+    /// so saving a schedule at 9:00 for 8:00 waits for tomorrow instead of firing at once.
+    pub fn due_slot(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if !self.enabled {
+            return None;
+        }
+        let slot = latest_slot(self.hour_of_day, self.tz(), now);
+        let since = self
+            .last_run_at
+            .map_or(self.updated_at, |last| last.max(self.updated_at));
+        (slot > since).then_some(slot)
+    }
+
+    /// When the scheduler will queue the user next: the pending slot if one is due, `None` when disabled
+    pub fn next_run_at(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if !self.enabled {
+            return None;
+        }
+        self.due_slot(now)
+            .or_else(|| Some(next_slot(self.hour_of_day, self.tz(), now)))
+    }
+}
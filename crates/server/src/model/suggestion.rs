@@ -0,0 +1,125 @@
+use std::collections::{HashMap, HashSet};
+
+use common::error::api_error::ApiError;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::consts::INVALID_QUERY_PARAM;
+
+pub const DEFAULT_SUGGESTION_LIMIT: usize = 10;
+pub const MAX_SUGGESTION_LIMIT: usize = 50;
+pub const DEFAULT_SUGGESTION_DAYS: u32 = 30;
+pub const MAX_SUGGESTION_DAYS: u32 = 365;
+
+/// Title words shorter than this are never suggested
+const MIN_KEYWORD_LEN: usize = 4;
+
+/// Frequent title words that say nothing about a topic
+const STOPWORDS: &[&str] = &[
+    "about", "across", "after", "also", "analysis", "approach", "based", "beyond", "between",
+    "from", "into", "large", "learning", "method", "methods", "model", "models", "more", "network",
+    "networks", "novel", "over", "paper", "study", "their", "through", "toward", "towards",
+    "under", "using", "very", "via", "what", "when", "which", "while", "with", "within", "without",
+];
+
+/// A paper the user read or matched: its title and its raw `categories` value
+#[derive(Debug, Clone, Default)]
+pub struct ReadPaper {
+    pub title: String,
+    /// `categories` as text, whatever its storage (`a, b`, `{a,b}` or `["a","b"]`)
+    pub categories: Option<String>,
+}
+
+/// A term the user might want to add as an interest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct InterestSuggestion {
+    pub term: String,
+    /// Papers among the recently read / matched ones carrying the term
+    pub paper_count: i64,
+}
+
+/// Check `limit` and `days` of `GET /interests/suggestions`, applying the defaults
+pub fn suggestion_window(
+    limit: Option<usize>,
+    days: Option<u32>,
+) -> Result<(usize, u32), ApiError> {
+    let limit = limit.unwrap_or(DEFAULT_SUGGESTION_LIMIT);
+    if !(1..=MAX_SUGGESTION_LIMIT).contains(&limit) {
+        return Err(ApiError::CustomError {
+            message: format!("limit must be between 1 and {MAX_SUGGESTION_LIMIT}"),
+            code: INVALID_QUERY_PARAM,
+        });
+    }
+    let days = days.unwrap_or(DEFAULT_SUGGESTION_DAYS);
+    if !(1..=MAX_SUGGESTION_DAYS).contains(&days) {
+        return Err(ApiError::CustomError {
+            message: format!("days must be between 1 and {MAX_SUGGESTION_DAYS}"),
+            code: INVALID_QUERY_PARAM,
+        });
+    }
+    Ok((limit, days))
+}
+
+fn split_categories(raw: &str) -> impl Iterator<Item = String> + '_ {
+    raw.trim_matches(|c| matches!(c, '[' | ']' | '{' | '}'))
+        .split([',', ';'])
+        .map(|part| part.trim().trim_matches('"').trim().to_string())
+        .filter(|part| !part.is_empty())
+}
+
+fn title_keywords(title: &str) -> impl Iterator<Item = String> + '_ {
+    title
+        .split(|c: char| !c.is_alphanumeric() && c != '-')
+        .map(|word| word.trim_matches('-').to_lowercase())
+        .filter(|word| {
+            word.chars().count() >= MIN_KEYWORD_LEN
+                && !word.chars().all(|c| c.is_numeric())
+                && !STOPWORDS.contains(&word.as_str())
+        })
+}
+
+/// Whether `term` and an existing interest contain one another (case-insensitive)
+fn overlaps(term: &str, existing: &[String]) -> bool {
+    existing.iter().any(|interest| {
+        let interest = interest.trim().to_lowercase();
+        !interest.is_empty() && (interest.contains(term) || term.contains(&interest))
+    })
+}
+
+/// Rank the categories and title keywords of `papers` by the number of papers carrying them.
+///
+/// Terms overlapping an existing interest are left out; ties are broken alphabetically so the
+/// result is stable.
+pub fn suggest_interests(
+    papers: &[ReadPaper],
+    existing: &[String],
+    limit: usize,
+) -> Vec<InterestSuggestion> {
+    let mut counts: HashMap<String, i64> = HashMap::new();
+    for paper in papers {
+        let terms: HashSet<String> = paper
+            .categories
+            .as_deref()
+            .into_iter()
+            .flat_map(split_categories)
+            .map(|category| category.to_lowercase())
+            .chain(title_keywords(&paper.title))
+            .collect();
+        for term in terms {
+            *counts.entry(term).or_default() += 1;
+        }
+    }
+
+    let mut ranked: Vec<InterestSuggestion> = counts
+        .into_iter()
+        .filter(|(term, _)| !overlaps(term, existing))
+        .map(|(term, paper_count)| InterestSuggestion { term, paper_count })
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.paper_count
+            .cmp(&a.paper_count)
+            .then_with(|| a.term.cmp(&b.term))
+    });
+    ranked.truncate(limit);
+    ranked
+}
@@ -0,0 +1,22 @@
+use chrono_tz::Tz;
+use common::error::api_error::ApiError;
+
+use crate::{consts::INVALID_QUERY_PARAM, middlewares::auth::UserInfo};
+
+/// Timezone used for day boundaries: the explicit `tz` parameter, then the user's
+/// `zoneinfo` claim, then UTC.
+///
+/// An invalid explicit value is rejected; an invalid `zoneinfo` falls back to UTC.
+pub fn resolve_timezone(explicit: Option<&str>, user: &UserInfo) -> Result<Tz, ApiError> {
+    if let Some(name) = explicit.map(str::trim).filter(|s| !s.is_empty()) {
+        return name.parse::<Tz>().map_err(|_| ApiError::CustomError {
+            message: format!("invalid tz: {name}"),
+            code: INVALID_QUERY_PARAM,
+        });
+    }
+    Ok(user
+        .zoneinfo
+        .as_deref()
+        .and_then(|name| name.trim().parse::<Tz>().ok())
+        .unwrap_or(Tz::UTC))
+}
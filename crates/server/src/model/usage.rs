@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDate};
+use common::error::api_error::ApiError;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::{consts::INVALID_QUERY_PARAM, query::usage::UsageCount, state::usage::UsageEvent};
+
+/// Longest window accepted by the usage endpoints
+pub const MAX_USAGE_WINDOW_DAYS: u32 = 366;
+
+/// Parse a `window` like `30d` into a number of days (default 30)
+pub fn parse_window(window: Option<&str>) -> Result<u32, ApiError> {
+    let Some(window) = window.map(str::trim).filter(|w| !w.is_empty()) else {
+        return Ok(30);
+    };
+    window
+        .strip_suffix('d')
+        .and_then(|days| days.parse::<u32>().ok())
+        .filter(|days| (1..=MAX_USAGE_WINDOW_DAYS).contains(days))
+        .ok_or_else(|| ApiError::CustomError {
+            message: format!(
+                "invalid window `{window}`: expected 1d..{MAX_USAGE_WINDOW_DAYS}d, e.g. 30d"
+            ),
+            code: INVALID_QUERY_PARAM,
+        })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct DailyCount {
+    pub day: NaiveDate,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct UsageSeries {
+    pub event: UsageEvent,
+    pub total: i64,
+    /// One entry per day of the window, oldest first, zero-filled
+    pub daily: Vec<DailyCount>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct UsageReport {
+    pub user_id: i64,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// One series per event type
+    pub series: Vec<UsageSeries>,
+}
+
+impl UsageReport {
+    /// Daily series over `from..=to` from rolled-up and pending counters (summed when a day
+    /// appears in both)
+    pub fn build(user_id: i64, from: NaiveDate, to: NaiveDate, counts: &[UsageCount]) -> Self {
+        let mut totals: HashMap<(NaiveDate, &str), i64> = HashMap::new();
+        for count in counts.iter().filter(|c| c.user_id == user_id) {
+            *totals.entry((count.day, count.event.as_str())).or_default() += count.count;
+        }
+        let days: Vec<NaiveDate> = from.iter_days().take_while(|day| *day <= to).collect();
+        let series = UsageEvent::ALL
+            .into_iter()
+            .map(|event| {
+                let daily: Vec<DailyCount> = days
+                    .iter()
+                    .map(|day| DailyCount {
+                        day: *day,
+                        count: totals.get(&(*day, event.as_str())).copied().unwrap_or(0),
+                    })
+                    .collect();
+                UsageSeries {
+                    event,
+                    total: daily.iter().map(|d| d.count).sum(),
+                    daily,
+                }
+            })
+            .collect();
+        UsageReport {
+            user_id,
+            from,
+            to,
+            series,
+        }
+    }
+}
+
+/// First and last day of a window of `days` ending on `today`
+pub fn window_bounds(today: NaiveDate, days: u32) -> (NaiveDate, NaiveDate) {
+    (today - Duration::days(days as i64 - 1), today)
+}
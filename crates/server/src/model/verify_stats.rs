@@ -0,0 +1,133 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::NaiveDate;
+use common::error::api_error::ApiError;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::consts::INVALID_QUERY_PARAM;
+
+pub const DEFAULT_VERIFY_STATS_DAYS: u32 = 30;
+
+/// Check `days` of `GET /verify-stats` against the configured maximum, applying the default
+pub fn verify_stats_window(days: Option<u32>, max_days: u32) -> Result<u32, ApiError> {
+    let days = days.unwrap_or(DEFAULT_VERIFY_STATS_DAYS.min(max_days));
+    if !(1..=max_days).contains(&days) {
+        return Err(ApiError::CustomError {
+            message: format!("days must be between 1 and {max_days}"),
+            code: INVALID_QUERY_PARAM,
+        });
+    }
+    Ok(days)
+}
+
+/// Verifications of one UTC day against one interest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifyStatsRow {
+    pub day: NaiveDate,
+    pub interest_id: i64,
+    pub verified: i64,
+    pub matched: i64,
+}
+
+fn match_rate(verified: i64, matched: i64) -> Option<f64> {
+    (verified > 0).then(|| matched as f64 / verified as f64)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DailyVerifyStats {
+    /// UTC day
+    pub day: NaiveDate,
+    pub verified: i64,
+    pub matched: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct InterestVerifyStats {
+    pub interest_id: i64,
+    /// `None` once the interest was removed
+    pub interest: Option<String>,
+    pub verified: i64,
+    pub matched: i64,
+    /// matched / verified
+    pub match_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct VerifyStats {
+    /// First and last UTC day of the window
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub verified: i64,
+    pub matched: i64,
+    /// matched / verified over the window, `None` when nothing was verified
+    pub match_rate: Option<f64>,
+    /// One entry per day of the window, oldest first, zero-filled
+    pub daily: Vec<DailyVerifyStats>,
+    /// Interests with verifications in the window, most matched first
+    pub interests: Vec<InterestVerifyStats>,
+}
+
+impl VerifyStats {
+    /// Daily series over `from..=to` and per-interest totals from `rows`; rows outside the
+    /// window are ignored. `interest_names` resolves the interest ids.
+    pub fn build(
+        from: NaiveDate,
+        to: NaiveDate,
+        rows: &[VerifyStatsRow],
+        interest_names: &HashMap<i64, String>,
+    ) -> Self {
+        let rows: Vec<&VerifyStatsRow> = rows
+            .iter()
+            .filter(|row| (from..=to).contains(&row.day))
+            .collect();
+
+        let mut by_day: HashMap<NaiveDate, (i64, i64)> = HashMap::new();
+        let mut by_interest: BTreeMap<i64, (i64, i64)> = BTreeMap::new();
+        for row in &rows {
+            let day = by_day.entry(row.day).or_default();
+            day.0 += row.verified;
+            day.1 += row.matched;
+            let interest = by_interest.entry(row.interest_id).or_default();
+            interest.0 += row.verified;
+            interest.1 += row.matched;
+        }
+
+        let daily: Vec<DailyVerifyStats> = from
+            .iter_days()
+            .take_while(|day| *day <= to)
+            .map(|day| {
+                let (verified, matched) = by_day.get(&day).copied().unwrap_or_default();
+                DailyVerifyStats {
+                    day,
+                    verified,
+                    matched,
+                }
+            })
+            .collect();
+        let mut interests: Vec<InterestVerifyStats> = by_interest
+            .into_iter()
+            .map(|(interest_id, (verified, matched))| InterestVerifyStats {
+                interest_id,
+                interest: interest_names.get(&interest_id).cloned(),
+                verified,
+                matched,
+                match_rate: match_rate(verified, matched),
+            })
+            .collect();
+        // stable: ids ascending among equal counts
+        interests.sort_by(|a, b| b.matched.cmp(&a.matched));
+
+        let verified = daily.iter().map(|day| day.verified).sum();
+        let matched = daily.iter().map(|day| day.matched).sum();
+        VerifyStats {
+            from,
+            to,
+            verified,
+            matched,
+            match_rate: match_rate(verified, matched),
+            daily,
+            interests,
+        }
+    }
+}
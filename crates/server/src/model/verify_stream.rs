@@ -0,0 +1,103 @@
+use axum::response::sse::Event;
+use common::prelude::ApiCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+/// Payload of every event of `POST /stream-verify`.
+///
+/// The SSE event name and the `type` field of the JSON data are always the same, so clients can
+/// dispatch on either. `verify_info`, `verification_details` and `statistics` are produced by the
+/// verify service and passed through unchanged.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum VerifyStreamEvent {
+    /// The verification task is ready to start
+    Ready {
+        user_id: i64,
+        verify_info: Value,
+        timestamp: i64,
+        status: String,
+    },
+    /// The verification task started processing
+    Processing {
+        user_id: i64,
+        verify_info: Value,
+        timestamp: i64,
+        status: String,
+    },
+    /// Periodic progress, every second
+    Heartbeat {
+        user_id: i64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        verify_info: Option<Value>,
+        timestamp: i64,
+        status: String,
+        is_completed: bool,
+    },
+    /// A paper was verified
+    VerifyPaperSuccess {
+        verification_details: Value,
+        verify_info: Value,
+        /// Statistics filtered by the request's `search_params`, when given
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        statistics: Option<Value>,
+        timestamp: i64,
+        status: String,
+    },
+    /// Every paper was verified; the stream ends
+    VerifyCompleted {
+        verify_info: Value,
+        timestamp: i64,
+        status: String,
+        is_completed: bool,
+    },
+    /// The matched count reached the limit; the stream ends
+    MatchLimitReached {
+        user_id: i64,
+        matched: i64,
+        max_limit: i64,
+        timestamp: i64,
+        status: String,
+    },
+    /// Streaming or verification failed or is disabled; the stream ends
+    Error {
+        /// Always `error`, kept for clients reading `status`
+        status: String,
+        code: i64,
+        message: String,
+        timestamp: i64,
+    },
+    /// The client read too slowly and `skipped` events were dropped; a heartbeat follows
+    EventsDropped { skipped: u64, timestamp: i64 },
+}
+
+impl VerifyStreamEvent {
+    pub fn error(code: ApiCode, message: impl Into<String>) -> Self {
+        VerifyStreamEvent::Error {
+            status: "error".to_string(),
+            code: code.code as i64,
+            message: message.into(),
+            timestamp: chrono::Utc::now().timestamp(),
+        }
+    }
+
+    /// SSE event name, the same as the `type` field
+    pub fn name(&self) -> &'static str {
+        match self {
+            VerifyStreamEvent::Ready { .. } => "ready",
+            VerifyStreamEvent::Processing { .. } => "processing",
+            VerifyStreamEvent::Heartbeat { .. } => "heartbeat",
+            VerifyStreamEvent::VerifyPaperSuccess { .. } => "verify_paper_success",
+            VerifyStreamEvent::VerifyCompleted { .. } => "verify_completed",
+            VerifyStreamEvent::MatchLimitReached { .. } => "match_limit_reached",
+            VerifyStreamEvent::Error { .. } => "error",
+            VerifyStreamEvent::EventsDropped { .. } => "events_dropped",
+        }
+    }
+
+    pub fn to_event(&self) -> Event {
+        let data = serde_json::to_string(self).expect("stream event serializes");
+        Event::default().event(self.name()).data(data)
+    }
+}
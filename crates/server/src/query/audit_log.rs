@@ -0,0 +1,125 @@
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DbBackend, DbErr, QueryResult, Statement, Value,
+};
+
+use crate::model::audit::{AuditAction, AuditEntry};
+
+const AUDIT_COLUMNS: &str = "id, actor_id, actor_open_id, action, target_type, \
+     array_to_string(target_ids, ',') AS target_ids, metadata::TEXT AS metadata, created_at";
+
+fn audit_entry_from_row(row: &QueryResult) -> Result<AuditEntry, DbErr> {
+    let action: String = row.try_get("", "action")?;
+    let target_ids: String = row.try_get("", "target_ids")?;
+    let metadata: String = row.try_get("", "metadata")?;
+    Ok(AuditEntry {
+        id: row.try_get("", "id")?,
+        actor_id: row.try_get("", "actor_id")?,
+        actor_open_id: row.try_get("", "actor_open_id")?,
+        action: AuditAction::parse(&action)
+            .ok_or_else(|| DbErr::Type(format!("unknown audit action {action}")))?,
+        target_type: row.try_get("", "target_type")?,
+        target_ids: target_ids
+            .split(',')
+            .filter_map(|id| id.parse().ok())
+            .collect(),
+        metadata: serde_json::from_str(&metadata).map_err(|e| DbErr::Json(e.to_string()))?,
+        created_at: row.try_get("", "created_at")?,
+    })
+}
+
+/// An operation to record; see `record`
+#[derive(Debug, Clone)]
+pub struct NewAuditEntry<'a> {
+    pub actor_id: i64,
+    pub actor_open_id: Option<&'a str>,
+    pub action: AuditAction,
+    pub target_ids: &'a [i64],
+    pub metadata: serde_json::Value,
+}
+
+/// Filters of `list`; `None` matches everything
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AuditFilter {
+    pub action: Option<AuditAction>,
+    pub actor_id: Option<i64>,
+}
+
+pub async fn record(conn: &DatabaseConnection, entry: NewAuditEntry<'_>) -> Result<(), DbErr> {
+    let target_ids = entry
+        .target_ids
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    conn.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "INSERT INTO audit_log (actor_id, actor_open_id, action, target_type, target_ids, metadata) \
+         VALUES ($1, $2, $3, $4, string_to_array($5, ',')::BIGINT[], $6::JSONB)",
+        [
+            entry.actor_id.into(),
+            entry.actor_open_id.map(str::to_string).into(),
+            entry.action.as_str().into(),
+            entry.action.target_type().into(),
+            target_ids.into(),
+            entry.metadata.to_string().into(),
+        ],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// A page of the entries matching `filter`, latest first, and the total number of matches
+pub async fn list(
+    conn: &DatabaseConnection,
+    filter: AuditFilter,
+    offset: i32,
+    limit: i32,
+) -> Result<(Vec<AuditEntry>, u64), DbErr> {
+    let mut conditions = Vec::new();
+    let mut values: Vec<Value> = Vec::new();
+    if let Some(action) = filter.action {
+        values.push(action.as_str().into());
+        conditions.push(format!("action = ${}", values.len()));
+    }
+    if let Some(actor_id) = filter.actor_id {
+        values.push(actor_id.into());
+        conditions.push(format!("actor_id = ${}", values.len()));
+    }
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+
+    let total = conn
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            format!("SELECT COUNT(*) AS count FROM audit_log {where_clause}"),
+            values.clone(),
+        ))
+        .await?
+        .map(|row| row.try_get::<i64>("", "count"))
+        .transpose()?
+        .unwrap_or(0);
+
+    let sql = format!(
+        "SELECT {AUDIT_COLUMNS} FROM audit_log {where_clause} \
+         ORDER BY created_at DESC, id DESC OFFSET ${} LIMIT ${}",
+        values.len() + 1,
+        values.len() + 2
+    );
+    values.push(offset.into());
+    values.push(limit.into());
+    let rows = conn
+        .query_all(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql,
+            values,
+        ))
+        .await?;
+    let entries = rows
+        .iter()
+        .map(audit_entry_from_row)
+        .collect::<Result<_, _>>()?;
+    Ok((entries, total as u64))
+}
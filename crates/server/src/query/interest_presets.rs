@@ -0,0 +1,105 @@
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, DbErr, QueryResult, Statement};
+
+use crate::model::preset::{InterestPreset, InterestPresetInput};
+
+const COLUMNS: &str = "id, name, description, interests, channel_hints, created_at, updated_at";
+
+fn string_list(value: serde_json::Value) -> Vec<String> {
+    serde_json::from_value(value).unwrap_or_default()
+}
+
+fn from_row(row: &QueryResult) -> Result<InterestPreset, DbErr> {
+    Ok(InterestPreset {
+        id: row.try_get("", "id")?,
+        name: row.try_get("", "name")?,
+        description: row.try_get("", "description")?,
+        interests: string_list(row.try_get("", "interests")?),
+        channel_hints: string_list(row.try_get("", "channel_hints")?),
+        created_at: row.try_get("", "created_at")?,
+        updated_at: row.try_get("", "updated_at")?,
+    })
+}
+
+pub async fn list(conn: &DatabaseConnection) -> Result<Vec<InterestPreset>, DbErr> {
+    conn.query_all(Statement::from_string(
+        DbBackend::Postgres,
+        format!("SELECT {COLUMNS} FROM interest_presets ORDER BY id"),
+    ))
+    .await?
+    .iter()
+    .map(from_row)
+    .collect()
+}
+
+pub async fn get(conn: &DatabaseConnection, id: i64) -> Result<Option<InterestPreset>, DbErr> {
+    conn.query_one(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        format!("SELECT {COLUMNS} FROM interest_presets WHERE id = $1"),
+        [id.into()],
+    ))
+    .await?
+    .as_ref()
+    .map(from_row)
+    .transpose()
+}
+
+pub async fn insert(
+    conn: &DatabaseConnection,
+    input: &InterestPresetInput,
+) -> Result<InterestPreset, DbErr> {
+    let row = conn
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            format!(
+                "INSERT INTO interest_presets (name, description, interests, channel_hints) \
+                 VALUES ($1, $2, $3, $4) RETURNING {COLUMNS}"
+            ),
+            [
+                input.name.clone().into(),
+                input.description.clone().into(),
+                serde_json::json!(input.interests).into(),
+                serde_json::json!(input.channel_hints).into(),
+            ],
+        ))
+        .await?
+        .ok_or(DbErr::RecordNotInserted)?;
+    from_row(&row)
+}
+
+/// `None` when the preset does not exist
+pub async fn update(
+    conn: &DatabaseConnection,
+    id: i64,
+    input: &InterestPresetInput,
+) -> Result<Option<InterestPreset>, DbErr> {
+    conn.query_one(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        format!(
+            "UPDATE interest_presets SET name = $2, description = $3, interests = $4, \
+             channel_hints = $5, updated_at = NOW() WHERE id = $1 RETURNING {COLUMNS}"
+        ),
+        [
+            id.into(),
+            input.name.clone().into(),
+            input.description.clone().into(),
+            serde_json::json!(input.interests).into(),
+            serde_json::json!(input.channel_hints).into(),
+        ],
+    ))
+    .await?
+    .as_ref()
+    .map(from_row)
+    .transpose()
+}
+
+/// Whether a preset was deleted
+pub async fn delete(conn: &DatabaseConnection, id: i64) -> Result<bool, DbErr> {
+    let result = conn
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "DELETE FROM interest_presets WHERE id = $1",
+            [id.into()],
+        ))
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
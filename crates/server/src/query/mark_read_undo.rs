@@ -0,0 +1,319 @@
+use std::collections::HashSet;
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, DbErr, Statement, Value};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Verification ids that were unread right before a "mark all as read"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReadUndoSnapshot {
+    /// The exact ids
+    Ids { ids: Vec<i64> },
+    /// Compact form for large sets: inclusive id ranges over the user's rows in `channel`.
+    ///
+    /// Each range covers a run of consecutive rows (by id) that were all unread, so
+    /// restoring every read row of the user in that range and channel is exact.
+    Ranges {
+        channel: Option<String>,
+        ranges: Vec<(i64, i64)>,
+    },
+}
+
+impl ReadUndoSnapshot {
+    /// Build from the user's `(id, unread)` rows in scope, ordered by id.
+    ///
+    /// Ids are stored as-is up to `max_ids`, then as ranges. Returns `None` when even
+    /// the ranges exceed `max_ids` (the rows are too fragmented to snapshot cheaply).
+    pub fn from_rows(rows: &[(i64, bool)], channel: Option<&str>, max_ids: usize) -> Option<Self> {
+        let unread: Vec<i64> = rows
+            .iter()
+            .filter(|(_, unread)| *unread)
+            .map(|(id, _)| *id)
+            .collect();
+        if unread.len() <= max_ids {
+            return Some(ReadUndoSnapshot::Ids { ids: unread });
+        }
+
+        let mut ranges: Vec<(i64, i64)> = Vec::new();
+        let mut current: Option<(i64, i64)> = None;
+        for &(id, unread) in rows {
+            if !unread {
+                ranges.extend(current.take());
+            } else if let Some(range) = current.as_mut() {
+                range.1 = id;
+            } else {
+                current = Some((id, id));
+            }
+        }
+        ranges.extend(current);
+
+        (ranges.len() <= max_ids).then(|| ReadUndoSnapshot::Ranges {
+            channel: channel.map(str::to_string),
+            ranges,
+        })
+    }
+}
+
+fn join_ids(ids: impl Iterator<Item = i64>) -> String {
+    ids.map(|id| id.to_string()).collect::<Vec<_>>().join(",")
+}
+
+pub(crate) const CHANNEL_FILTER: &str = "v.paper_id IN (SELECT p.id FROM rss_papers p \
+     JOIN rss_sources s ON s.id = p.source_id WHERE s.channel = $2)";
+
+/// Ids of the rows "mark all as read" sets read, ordered, at most `limit`
+pub async fn list_unread_ids(
+    conn: &DatabaseConnection,
+    user_id: i64,
+    channel: Option<&str>,
+    limit: usize,
+) -> Result<Vec<i64>, DbErr> {
+    let mut sql = "SELECT v.id::BIGINT AS id FROM user_paper_verifications v \
+                   WHERE v.user_id = $1 AND v.unread = TRUE"
+        .to_string();
+    let mut values: Vec<Value> = vec![user_id.into()];
+    if let Some(channel) = channel {
+        sql.push_str(&format!(" AND {CHANNEL_FILTER}"));
+        values.push(channel.into());
+    }
+    sql.push_str(&format!(" ORDER BY v.id LIMIT {limit}"));
+
+    let rows = conn
+        .query_all(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql,
+            values,
+        ))
+        .await?;
+    rows.into_iter().map(|row| row.try_get("", "id")).collect()
+}
+
+/// `(id, unread)` of the rows "mark all as read" sets read, plus the read row ending each run
+/// of them (by id, among the user's rows in the channel), as `ReadUndoSnapshot::from_rows`
+/// needs to build ranges
+pub async fn list_read_state(
+    conn: &DatabaseConnection,
+    user_id: i64,
+    channel: Option<&str>,
+) -> Result<Vec<(i64, bool)>, DbErr> {
+    let mut sql = "SELECT id, unread FROM (\
+                   SELECT v.id::BIGINT AS id, v.unread AS unread, \
+                   LAG(v.unread) OVER (ORDER BY v.id) AS previous_unread \
+                   FROM user_paper_verifications v WHERE v.user_id = $1"
+        .to_string();
+    let mut values: Vec<Value> = vec![user_id.into()];
+    if let Some(channel) = channel {
+        sql.push_str(&format!(" AND {CHANNEL_FILTER}"));
+        values.push(channel.into());
+    }
+    sql.push_str(") r WHERE r.unread OR r.previous_unread ORDER BY id");
+
+    let rows = conn
+        .query_all(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql,
+            values,
+        ))
+        .await?;
+    rows.into_iter()
+        .map(|row| Ok((row.try_get("", "id")?, row.try_get("", "unread")?)))
+        .collect()
+}
+
+/// Set the snapshot rows back to unread. Returns the number of rows actually changed.
+pub async fn restore_unread(
+    conn: &DatabaseConnection,
+    user_id: i64,
+    snapshot: &ReadUndoSnapshot,
+) -> Result<u64, DbErr> {
+    let statement = match snapshot {
+        ReadUndoSnapshot::Ids { ids } => {
+            if ids.is_empty() {
+                return Ok(0);
+            }
+            Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                "UPDATE user_paper_verifications SET unread = TRUE \
+                 WHERE user_id = $1 AND unread = FALSE \
+                 AND id = ANY(string_to_array($2, ',')::BIGINT[])",
+                [user_id.into(), join_ids(ids.iter().copied()).into()],
+            )
+        }
+        ReadUndoSnapshot::Ranges { channel, ranges } => {
+            if ranges.is_empty() {
+                return Ok(0);
+            }
+            let mut values: Vec<Value> = vec![user_id.into()];
+            let mut sql = "UPDATE user_paper_verifications v SET unread = TRUE \
+                           WHERE v.user_id = $1 AND v.unread = FALSE"
+                .to_string();
+            if let Some(channel) = channel {
+                sql.push_str(&format!(" AND {CHANNEL_FILTER}"));
+                values.push(channel.clone().into());
+            }
+            let lo = values.len() + 1;
+            sql.push_str(&format!(
+                " AND EXISTS (SELECT 1 FROM unnest(\
+                 string_to_array(${lo}, ',')::BIGINT[], \
+                 string_to_array(${}, ',')::BIGINT[]) AS r(lo, hi) \
+                 WHERE v.id BETWEEN r.lo AND r.hi)",
+                lo + 1
+            ));
+            values.push(join_ids(ranges.iter().map(|r| r.0)).into());
+            values.push(join_ids(ranges.iter().map(|r| r.1)).into());
+            Statement::from_sql_and_values(DbBackend::Postgres, sql, values)
+        }
+    };
+    Ok(conn.execute(statement).await?.rows_affected())
+}
+
+/// Set the user's read papers back to unread: `paper_ids`, or every paper with `all`, both
+/// limited to `channel` when given. Returns the number of rows actually changed.
+pub async fn mark_unread(
+    conn: &DatabaseConnection,
+    user_id: i64,
+    paper_ids: &[i32],
+    channel: Option<&str>,
+    all: bool,
+) -> Result<u64, DbErr> {
+    if !all && paper_ids.is_empty() {
+        return Ok(0);
+    }
+    let mut sql = "UPDATE user_paper_verifications v SET unread = TRUE \
+                   WHERE v.user_id = $1 AND v.unread = FALSE"
+        .to_string();
+    let mut values: Vec<Value> = vec![user_id.into()];
+    if let Some(channel) = channel {
+        sql.push_str(&format!(" AND {CHANNEL_FILTER}"));
+        values.push(channel.into());
+    }
+    if !all {
+        sql.push_str(&format!(
+            " AND v.paper_id = ANY(string_to_array(${}, ',')::INT[])",
+            values.len() + 1
+        ));
+        values.push(join_ids(paper_ids.iter().map(|id| i64::from(*id))).into());
+    }
+    let result = conn
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql,
+            values,
+        ))
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// What marking one paper as read did
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MarkReadOutcome {
+    /// The paper was unread and is now read
+    Marked,
+    /// The paper was already read; nothing changed
+    AlreadyRead,
+    /// The user has no verification of the paper (unknown id, another user's paper, deleted, or
+    /// outside the requested channel)
+    NotFound,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct MarkReadItem {
+    pub paper_id: i32,
+    pub outcome: MarkReadOutcome,
+}
+
+/// Per-id result of `POST /mark-as-read` with `detailed=true`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct MarkReadReport {
+    /// Papers marked as read, the number the plain response returns
+    pub count: u64,
+    /// One entry per distinct requested id, in request order
+    pub results: Vec<MarkReadItem>,
+}
+
+impl MarkReadReport {
+    /// Classify `paper_ids` given the ones the user has in scope and the ones just marked
+    pub fn classify(paper_ids: &[i32], in_scope: &HashSet<i32>, marked: &HashSet<i32>) -> Self {
+        let mut seen = HashSet::new();
+        let results: Vec<MarkReadItem> = paper_ids
+            .iter()
+            .filter(|id| seen.insert(**id))
+            .map(|&paper_id| MarkReadItem {
+                paper_id,
+                outcome: if marked.contains(&paper_id) {
+                    MarkReadOutcome::Marked
+                } else if in_scope.contains(&paper_id) {
+                    MarkReadOutcome::AlreadyRead
+                } else {
+                    MarkReadOutcome::NotFound
+                },
+            })
+            .collect();
+        let count = results
+            .iter()
+            .filter(|item| item.outcome == MarkReadOutcome::Marked)
+            .count() as u64;
+        MarkReadReport { count, results }
+    }
+}
+
+/// Mark the user's `paper_ids` as read, limited to `channel` when given, reporting the outcome
+/// of every id. One select finds the papers in scope, one update marks the unread ones.
+pub async fn mark_read_detailed(
+    conn: &DatabaseConnection,
+    user_id: i64,
+    paper_ids: &[i32],
+    channel: Option<&str>,
+) -> Result<MarkReadReport, DbErr> {
+    if paper_ids.is_empty() {
+        return Ok(MarkReadReport::classify(
+            &[],
+            &HashSet::new(),
+            &HashSet::new(),
+        ));
+    }
+    let mut scope = "v.user_id = $1".to_string();
+    let mut values: Vec<Value> = vec![user_id.into()];
+    if let Some(channel) = channel {
+        scope.push_str(&format!(" AND {CHANNEL_FILTER}"));
+        values.push(channel.into());
+    }
+    scope.push_str(&format!(
+        " AND v.paper_id = ANY(string_to_array(${}, ',')::INT[])",
+        values.len() + 1
+    ));
+    values.push(join_ids(paper_ids.iter().map(|id| i64::from(*id))).into());
+
+    let paper_ids_of = |rows: Vec<sea_orm::QueryResult>| -> Result<HashSet<i32>, DbErr> {
+        rows.iter().map(|row| row.try_get("", "paper_id")).collect()
+    };
+    let in_scope = paper_ids_of(
+        conn.query_all(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            format!("SELECT DISTINCT v.paper_id FROM user_paper_verifications v WHERE {scope}"),
+            values.clone(),
+        ))
+        .await?,
+    )?;
+    if in_scope.is_empty() {
+        return Ok(MarkReadReport::classify(
+            paper_ids,
+            &in_scope,
+            &HashSet::new(),
+        ));
+    }
+    let marked = paper_ids_of(
+        conn.query_all(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            format!(
+                "UPDATE user_paper_verifications v SET unread = FALSE \
+                 WHERE {scope} AND v.unread = TRUE RETURNING v.paper_id"
+            ),
+            values,
+        ))
+        .await?,
+    )?;
+    Ok(MarkReadReport::classify(paper_ids, &in_scope, &marked))
+}
@@ -0,0 +1,15 @@
+//! Queries that are local to the server and not (yet) part of `seaorm_db`
+
+pub mod audit_log;
+pub mod interest_presets;
+pub mod mark_read_undo;
+pub mod paper_detail;
+pub mod paper_notes;
+pub mod paper_stars;
+pub mod rss_sources;
+pub mod rss_subscriptions;
+pub mod subscription_folders;
+pub mod usage;
+pub mod user_interests;
+pub mod verify_schedules;
+pub mod verify_stats;
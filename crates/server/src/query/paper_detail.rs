@@ -0,0 +1,43 @@
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use seaorm_db::entities::feed::{rss_papers, rss_sources, user_paper_verifications};
+
+/// A paper with everything a user may see about it
+#[derive(Debug, Clone)]
+pub struct PaperDetail {
+    pub paper: rss_papers::Model,
+    /// The user's verifications of the paper, oldest first
+    pub verifications: Vec<user_paper_verifications::Model>,
+    pub source: Option<rss_sources::Model>,
+}
+
+/// Load `paper_id` for `user_id`.
+///
+/// `None` unless the paper is visible to the user: it comes from one of `subscribed_source_ids`
+/// or the user has a verification of it (kept after unsubscribing).
+pub async fn get_paper_detail(
+    conn: &DatabaseConnection,
+    user_id: i64,
+    paper_id: i32,
+    subscribed_source_ids: &[i32],
+) -> Result<Option<PaperDetail>, DbErr> {
+    let Some(paper) = rss_papers::Entity::find_by_id(paper_id).one(conn).await? else {
+        return Ok(None);
+    };
+    let verifications = user_paper_verifications::Entity::find()
+        .filter(user_paper_verifications::Column::UserId.eq(user_id))
+        .filter(user_paper_verifications::Column::PaperId.eq(paper_id))
+        .order_by_asc(user_paper_verifications::Column::Id)
+        .all(conn)
+        .await?;
+    if verifications.is_empty() && !subscribed_source_ids.contains(&paper.source_id) {
+        return Ok(None);
+    }
+    let source = rss_sources::Entity::find_by_id(paper.source_id)
+        .one(conn)
+        .await?;
+    Ok(Some(PaperDetail {
+        paper,
+        verifications,
+        source,
+    }))
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, DbErr, Statement};
+
+use crate::model::note::PaperNote;
+
+pub async fn get(
+    conn: &DatabaseConnection,
+    user_id: i64,
+    paper_id: i32,
+) -> Result<Option<PaperNote>, DbErr> {
+    conn.query_one(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT note, updated_at FROM paper_notes WHERE user_id = $1 AND paper_id = $2",
+        [user_id.into(), paper_id.into()],
+    ))
+    .await?
+    .map(|row| {
+        Ok(PaperNote {
+            note: row.try_get("", "note")?,
+            updated_at: row.try_get("", "updated_at")?,
+        })
+    })
+    .transpose()
+}
+
+/// Notes of the user on any of `paper_ids`, by paper id
+pub async fn list_for_papers(
+    conn: &DatabaseConnection,
+    user_id: i64,
+    paper_ids: &[i32],
+) -> Result<HashMap<i32, String>, DbErr> {
+    if paper_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let ids = paper_ids
+        .iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    let rows = conn
+        .query_all(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT paper_id, note FROM paper_notes \
+             WHERE user_id = $1 AND paper_id = ANY(string_to_array($2, ',')::INT[])",
+            [user_id.into(), ids.into()],
+        ))
+        .await?;
+    rows.iter()
+        .map(|row| Ok((row.try_get("", "paper_id")?, row.try_get("", "note")?)))
+        .collect()
+}
+
+/// Create or replace the user's note on `paper_id`
+pub async fn upsert(
+    conn: &DatabaseConnection,
+    user_id: i64,
+    paper_id: i32,
+    note: &str,
+) -> Result<PaperNote, DbErr> {
+    let row = conn
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "INSERT INTO paper_notes (user_id, paper_id, note) VALUES ($1, $2, $3) \
+             ON CONFLICT (user_id, paper_id) DO UPDATE \
+             SET note = EXCLUDED.note, updated_at = NOW() \
+             RETURNING note, updated_at",
+            [user_id.into(), paper_id.into(), note.into()],
+        ))
+        .await?
+        .ok_or_else(|| DbErr::RecordNotInserted)?;
+    Ok(PaperNote {
+        note: row.try_get("", "note")?,
+        updated_at: row.try_get("", "updated_at")?,
+    })
+}
+
+/// Returns whether there was a note to delete
+pub async fn delete(conn: &DatabaseConnection, user_id: i64, paper_id: i32) -> Result<bool, DbErr> {
+    let result = conn
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "DELETE FROM paper_notes WHERE user_id = $1 AND paper_id = $2",
+            [user_id.into(), paper_id.into()],
+        ))
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,95 @@
+use std::collections::HashSet;
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, DbErr, Statement, Value};
+
+use super::mark_read_undo::CHANNEL_FILTER;
+
+fn join_ids(ids: &[i32]) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Star `paper_id` for the user. Returns `false` when it was already starred.
+pub async fn star(conn: &DatabaseConnection, user_id: i64, paper_id: i32) -> Result<bool, DbErr> {
+    let result = conn
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "INSERT INTO paper_stars (user_id, paper_id) VALUES ($1, $2) \
+             ON CONFLICT (user_id, paper_id) DO NOTHING",
+            [user_id.into(), paper_id.into()],
+        ))
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Unstar `paper_ids` for the user. Returns the number of stars removed.
+pub async fn unstar(
+    conn: &DatabaseConnection,
+    user_id: i64,
+    paper_ids: &[i32],
+) -> Result<u64, DbErr> {
+    if paper_ids.is_empty() {
+        return Ok(0);
+    }
+    let result = conn
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "DELETE FROM paper_stars \
+             WHERE user_id = $1 AND paper_id = ANY(string_to_array($2, ',')::INT[])",
+            [user_id.into(), join_ids(paper_ids).into()],
+        ))
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// The ones among `paper_ids` the user starred
+pub async fn starred_among(
+    conn: &DatabaseConnection,
+    user_id: i64,
+    paper_ids: &[i32],
+) -> Result<HashSet<i32>, DbErr> {
+    if paper_ids.is_empty() {
+        return Ok(HashSet::new());
+    }
+    let rows = conn
+        .query_all(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT paper_id FROM paper_stars \
+             WHERE user_id = $1 AND paper_id = ANY(string_to_array($2, ',')::INT[])",
+            [user_id.into(), join_ids(paper_ids).into()],
+        ))
+        .await?;
+    rows.iter().map(|row| row.try_get("", "paper_id")).collect()
+}
+
+/// Starred papers of the user that are still in their feed, limited to `channel` when given
+pub async fn count(
+    conn: &DatabaseConnection,
+    user_id: i64,
+    channel: Option<&str>,
+) -> Result<u64, DbErr> {
+    let mut sql = "SELECT COUNT(DISTINCT s.paper_id) AS count FROM paper_stars s \
+                   JOIN user_paper_verifications v \
+                   ON v.user_id = s.user_id AND v.paper_id = s.paper_id \
+                   WHERE s.user_id = $1"
+        .to_string();
+    let mut values: Vec<Value> = vec![user_id.into()];
+    if let Some(channel) = channel {
+        sql.push_str(&format!(" AND {CHANNEL_FILTER}"));
+        values.push(channel.into());
+    }
+    let row = conn
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql,
+            values,
+        ))
+        .await?;
+    let count: i64 = match row {
+        Some(row) => row.try_get("", "count")?,
+        None => 0,
+    };
+    Ok(count as u64)
+}
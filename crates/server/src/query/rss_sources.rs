@@ -0,0 +1,261 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ConnectionTrait, DatabaseConnection, DbBackend, DbErr, EntityTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set, Statement, TransactionTrait, sea_query::Expr,
+};
+use seaorm_db::entities::feed::rss_sources;
+
+use crate::model::feed_url::normalize_feed_url;
+
+/// Fields of an RSS source to change; `None` keeps the stored value
+#[derive(Debug, Clone, Default)]
+pub struct RssSourcePatch {
+    pub channel: Option<String>,
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub description: Option<String>,
+    pub logo_img: Option<String>,
+    pub background_img: Option<String>,
+}
+
+/// Write the provided fields of source `id` and bump `updated_at`. `None` for unknown ids.
+///
+/// A new URL resets `last_fetched_at`, so the next pull fetches the feed from scratch.
+pub async fn update_by_id(
+    conn: &DatabaseConnection,
+    id: i32,
+    patch: RssSourcePatch,
+) -> Result<Option<rss_sources::Model>, DbErr> {
+    let Some(source) = rss_sources::Entity::find_by_id(id).one(conn).await? else {
+        return Ok(None);
+    };
+    let url_changed = patch.url.as_ref().is_some_and(|url| *url != source.url);
+
+    let mut active: rss_sources::ActiveModel = source.into();
+    if let Some(channel) = patch.channel {
+        active.channel = Set(channel);
+    }
+    if let Some(name) = patch.name {
+        active.name = Set(name);
+    }
+    if let Some(url) = patch.url {
+        active.url = Set(url);
+    }
+    if let Some(description) = patch.description {
+        active.description = Set(Some(description));
+    }
+    if let Some(logo_img) = patch.logo_img {
+        active.logo_img = Set(Some(logo_img));
+    }
+    if let Some(background_img) = patch.background_img {
+        active.background_img = Set(Some(background_img));
+    }
+    if url_changed {
+        active.last_fetched_at = Set(None);
+    }
+    active.updated_at = Set(Utc::now().into());
+    active.update(conn).await.map(Some)
+}
+
+/// `LIKE` pattern of the sources that may normalize like `key`: same scheme and host, which
+/// are compared case-insensitively anyway
+fn url_prefix_pattern(key: &str) -> String {
+    let prefix = key
+        .splitn(4, '/')
+        .take(3)
+        .collect::<Vec<_>>()
+        .join("/")
+        .to_lowercase()
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("{prefix}%")
+}
+
+/// Sources whose URL normalizes like `url` (see `normalize_feed_url`), oldest first
+pub async fn find_by_url(
+    conn: &DatabaseConnection,
+    url: &str,
+) -> Result<Vec<rss_sources::Model>, DbErr> {
+    let key = normalize_feed_url(url);
+    let candidates = rss_sources::Entity::find()
+        .filter(Expr::cust_with_values(
+            "lower(url) LIKE $1",
+            [url_prefix_pattern(&key)],
+        ))
+        .order_by_asc(rss_sources::Column::Id)
+        .all(conn)
+        .await?;
+    Ok(candidates
+        .into_iter()
+        .filter(|source| normalize_feed_url(&source.url) == key)
+        .collect())
+}
+
+/// Id of the oldest source per normalized URL, for the sources matching any of `urls`, in one query
+async fn ids_by_normalized_url<C: ConnectionTrait>(
+    conn: &C,
+    urls: &[&str],
+) -> Result<HashMap<String, i32>, DbErr> {
+    let keys: HashSet<String> = urls.iter().map(|url| normalize_feed_url(url)).collect();
+    let mut patterns: Vec<String> = keys.iter().map(|key| url_prefix_pattern(key)).collect();
+    patterns.sort();
+    patterns.dedup();
+    let candidates = rss_sources::Entity::find()
+        .filter(Expr::cust_with_values(
+            "lower(url) LIKE ANY(string_to_array($1, $2))",
+            [patterns.join("\n"), "\n".to_string()],
+        ))
+        .order_by_asc(rss_sources::Column::Id)
+        .all(conn)
+        .await?;
+    let mut ids = HashMap::new();
+    for source in candidates {
+        let key = normalize_feed_url(&source.url);
+        if keys.contains(&key) {
+            ids.entry(key).or_insert(source.id);
+        }
+    }
+    Ok(ids)
+}
+
+/// A source to add with `insert_many`
+#[derive(Debug, Clone)]
+pub struct NewRssSource {
+    pub channel: String,
+    pub name: String,
+    pub url: String,
+    pub description: Option<String>,
+    pub logo_img: Option<String>,
+    pub background_img: Option<String>,
+    /// Insert even when a source with the same URL exists
+    pub force: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InsertOutcome {
+    Created(i32),
+    /// Skipped, this source already uses the URL
+    Duplicate(i32),
+}
+
+/// Insert `sources` in one transaction, in order.
+///
+/// Unless `force` is set, a source is skipped when its URL normalizes like an existing source or
+/// an earlier one of the batch; existing sources are looked up in a single query. Any database
+/// error rolls back the whole batch.
+pub async fn insert_many(
+    conn: &DatabaseConnection,
+    sources: &[NewRssSource],
+) -> Result<Vec<InsertOutcome>, DbErr> {
+    let txn = conn.begin().await?;
+    let urls: Vec<&str> = sources.iter().map(|source| source.url.as_str()).collect();
+    let mut known = ids_by_normalized_url(&txn, &urls).await?;
+
+    let mut outcomes = Vec::with_capacity(sources.len());
+    for source in sources {
+        let key = normalize_feed_url(&source.url);
+        if !source.force
+            && let Some(id) = known.get(&key)
+        {
+            outcomes.push(InsertOutcome::Duplicate(*id));
+            continue;
+        }
+        let now = Utc::now();
+        let inserted = rss_sources::ActiveModel {
+            channel: Set(source.channel.clone()),
+            name: Set(source.name.clone()),
+            url: Set(source.url.clone()),
+            description: Set(source.description.clone()),
+            logo_img: Set(source.logo_img.clone()),
+            background_img: Set(source.background_img.clone()),
+            last_fetched_at: Set(None),
+            created_at: Set(now.into()),
+            updated_at: Set(now.into()),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+        known.entry(key).or_insert(inserted.id);
+        outcomes.push(InsertOutcome::Created(inserted.id));
+    }
+    txn.commit().await?;
+    Ok(outcomes)
+}
+
+/// Distinct channels of all sources, alphabetically
+pub async fn list_channels(conn: &DatabaseConnection) -> Result<Vec<String>, DbErr> {
+    rss_sources::Entity::find()
+        .select_only()
+        .column(rss_sources::Column::Channel)
+        .distinct()
+        .order_by_asc(rss_sources::Column::Channel)
+        .into_tuple()
+        .all(conn)
+        .await
+}
+
+/// Counts shown on a source's badge
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SourceStats {
+    pub paper_count: i64,
+    /// Distinct users subscribed to the source
+    pub subscriber_count: i64,
+}
+
+/// Stats of every source in `source_ids` (zero for sources without papers or subscribers),
+/// grouped in one query
+pub async fn stats_by_ids(
+    conn: &DatabaseConnection,
+    source_ids: &[i32],
+) -> Result<HashMap<i32, SourceStats>, DbErr> {
+    if source_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let ids = source_ids
+        .iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    let rows = conn
+        .query_all(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "WITH ids AS (SELECT DISTINCT unnest(string_to_array($1, ',')::INT[]) AS id) \
+             SELECT ids.id AS source_id, \
+             COALESCE(p.count, 0) AS paper_count, COALESCE(u.count, 0) AS subscriber_count \
+             FROM ids \
+             LEFT JOIN (SELECT source_id, COUNT(*) AS count FROM rss_papers \
+                 WHERE source_id IN (SELECT id FROM ids) GROUP BY source_id) p \
+                 ON p.source_id = ids.id \
+             LEFT JOIN (SELECT source_id, COUNT(DISTINCT user_id) AS count FROM rss_subscriptions \
+                 WHERE source_id IN (SELECT id FROM ids) GROUP BY source_id) u \
+                 ON u.source_id = ids.id",
+            [ids.into()],
+        ))
+        .await?;
+    rows.iter()
+        .map(|row| {
+            Ok((
+                row.try_get("", "source_id")?,
+                SourceStats {
+                    paper_count: row.try_get("", "paper_count")?,
+                    subscriber_count: row.try_get("", "subscriber_count")?,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Distinct users subscribed to the source
+pub async fn subscriber_ids(conn: &DatabaseConnection, source_id: i32) -> Result<Vec<i64>, DbErr> {
+    let rows = conn
+        .query_all(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT DISTINCT user_id FROM rss_subscriptions WHERE source_id = $1",
+            [source_id.into()],
+        ))
+        .await?;
+    rows.iter().map(|row| row.try_get("", "user_id")).collect()
+}
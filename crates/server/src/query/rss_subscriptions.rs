@@ -0,0 +1,18 @@
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter};
+use seaorm_db::entities::feed::rss_subscriptions;
+
+/// Delete subscription `subscription_id` if it belongs to `user_id`.
+///
+/// Returns the number of rows deleted: `0` when the id is unknown or another user's.
+pub async fn delete_by_id_and_user(
+    conn: &DatabaseConnection,
+    subscription_id: i64,
+    user_id: i64,
+) -> Result<u64, DbErr> {
+    let result = rss_subscriptions::Entity::delete_many()
+        .filter(rss_subscriptions::Column::Id.eq(subscription_id))
+        .filter(rss_subscriptions::Column::UserId.eq(user_id))
+        .exec(conn)
+        .await?;
+    Ok(result.rows_affected)
+}
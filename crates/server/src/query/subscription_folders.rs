@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, DbErr, Statement};
+
+fn join_ids(ids: &[i32]) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Folder of each of the user's sources that has one
+pub async fn list(conn: &DatabaseConnection, user_id: i64) -> Result<HashMap<i32, String>, DbErr> {
+    let rows = conn
+        .query_all(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT source_id, folder FROM subscription_folders WHERE user_id = $1",
+            [user_id.into()],
+        ))
+        .await?;
+    rows.iter()
+        .map(|row| Ok((row.try_get("", "source_id")?, row.try_get("", "folder")?)))
+        .collect()
+}
+
+/// Put `source_id` in `folder`, or take it out of any folder with `None`
+pub async fn set(
+    conn: &DatabaseConnection,
+    user_id: i64,
+    source_id: i32,
+    folder: Option<&str>,
+) -> Result<(), DbErr> {
+    let statement = match folder {
+        Some(folder) => Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "INSERT INTO subscription_folders (user_id, source_id, folder) VALUES ($1, $2, $3) \
+             ON CONFLICT (user_id, source_id) DO UPDATE \
+             SET folder = EXCLUDED.folder, updated_at = NOW()",
+            [user_id.into(), source_id.into(), folder.into()],
+        ),
+        None => Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "DELETE FROM subscription_folders WHERE user_id = $1 AND source_id = $2",
+            [user_id.into(), source_id.into()],
+        ),
+    };
+    conn.execute(statement).await?;
+    Ok(())
+}
+
+/// Forget the folders of every source of the user except `source_ids`
+pub async fn retain(
+    conn: &DatabaseConnection,
+    user_id: i64,
+    source_ids: &[i32],
+) -> Result<u64, DbErr> {
+    let result = conn
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "DELETE FROM subscription_folders WHERE user_id = $1 \
+             AND source_id <> ALL(string_to_array($2, ',')::INT[])",
+            [user_id.into(), join_ids(source_ids).into()],
+        ))
+        .await?;
+    Ok(result.rows_affected())
+}
@@ -0,0 +1,90 @@
+use chrono::NaiveDate;
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, DbErr, Statement, TransactionTrait};
+
+/// One `(user, day, event)` counter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageCount {
+    pub user_id: i64,
+    pub day: NaiveDate,
+    pub event: String,
+    pub count: i64,
+}
+
+fn join<T: ToString>(items: impl Iterator<Item = T>) -> String {
+    items
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Add `rows` to `user_api_usage` unless `batch` was already applied.
+///
+/// The batch marker and the counters are written in one transaction, so a batch is counted
+/// exactly once even when the caller crashes before discarding it and retries. Returns
+/// whether the rows were added by this call.
+pub async fn apply_rollup(
+    conn: &DatabaseConnection,
+    batch: &str,
+    rows: &[UsageCount],
+) -> Result<bool, DbErr> {
+    let txn = conn.begin().await?;
+    let inserted = txn
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "INSERT INTO user_api_usage_rollups (batch) VALUES ($1) ON CONFLICT DO NOTHING",
+            [batch.into()],
+        ))
+        .await?
+        .rows_affected();
+    if inserted == 0 {
+        txn.rollback().await?;
+        return Ok(false);
+    }
+    if !rows.is_empty() {
+        txn.execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "INSERT INTO user_api_usage (user_id, day, event, count) \
+             SELECT u::BIGINT, d::DATE, e, c::BIGINT FROM unnest( \
+             string_to_array($1, ','), string_to_array($2, ','), \
+             string_to_array($3, ','), string_to_array($4, ',')) AS t(u, d, e, c) \
+             ON CONFLICT (user_id, day, event) \
+             DO UPDATE SET count = user_api_usage.count + EXCLUDED.count",
+            [
+                join(rows.iter().map(|r| r.user_id)).into(),
+                join(rows.iter().map(|r| r.day)).into(),
+                join(rows.iter().map(|r| r.event.as_str())).into(),
+                join(rows.iter().map(|r| r.count)).into(),
+            ],
+        ))
+        .await?;
+    }
+    txn.commit().await?;
+    Ok(true)
+}
+
+/// Rolled-up counters of `user_id` for `from..=to`
+pub async fn daily_usage(
+    conn: &DatabaseConnection,
+    user_id: i64,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<UsageCount>, DbErr> {
+    let rows = conn
+        .query_all(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT day, event, count FROM user_api_usage \
+             WHERE user_id = $1 AND day BETWEEN $2 AND $3 ORDER BY day, event",
+            [user_id.into(), from.into(), to.into()],
+        ))
+        .await?;
+    rows.into_iter()
+        .map(|row| {
+            Ok(UsageCount {
+                user_id,
+                day: row.try_get("", "day")?,
+                event: row.try_get("", "event")?,
+                count: row.try_get("", "count")?,
+            })
+        })
+        .collect()
+}
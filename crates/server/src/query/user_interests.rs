@@ -0,0 +1,100 @@
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DbBackend, DbErr, Statement, prelude::DateTimeWithTimeZone,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::model::suggestion::ReadPaper;
+
+/// Most recent papers looked at for interest suggestions
+const SUGGESTION_SAMPLE: i64 = 500;
+
+/// An active interest with what it has matched so far
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct InterestDetail {
+    pub id: i64,
+    pub interest: String,
+    pub created_at: DateTimeWithTimeZone,
+    /// LLM model the interest's embedding was generated with
+    pub embedding_version: Option<String>,
+    /// Distinct papers verified as a `Yes` match for this interest
+    pub matched_paper_count: i64,
+}
+
+/// The user's active interests, oldest first, with one grouped count over the verifications
+pub async fn list_detailed(
+    conn: &DatabaseConnection,
+    user_id: i64,
+) -> Result<Vec<InterestDetail>, DbErr> {
+    let rows = conn
+        .query_all(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"
+            SELECT i.id::BIGINT AS id, i.interest, i.created_at, i.version::TEXT AS embedding_version,
+                   COALESCE(m.matched, 0)::BIGINT AS matched_paper_count
+            FROM user_interests i
+            LEFT JOIN (
+                SELECT interest_id, COUNT(DISTINCT paper_id) AS matched
+                FROM user_paper_verifications
+                WHERE user_id = $1 AND "match"::TEXT = 'Yes'
+                GROUP BY interest_id
+            ) m ON m.interest_id = i.id
+            WHERE i.user_id = $1 AND i.deleted_at IS NULL
+            ORDER BY i.id
+            "#,
+            [user_id.into()],
+        ))
+        .await?;
+    rows.iter()
+        .map(|row| {
+            Ok(InterestDetail {
+                id: row.try_get("", "id")?,
+                interest: row.try_get("", "interest")?,
+                created_at: row.try_get("", "created_at")?,
+                embedding_version: row.try_get("", "embedding_version")?,
+                matched_paper_count: row.try_get("", "matched_paper_count")?,
+            })
+        })
+        .collect()
+}
+
+/// Papers of the last `days` days the user read or that matched one of their interests,
+/// newest first
+pub async fn recent_read_papers(
+    conn: &DatabaseConnection,
+    user_id: i64,
+    days: u32,
+) -> Result<Vec<ReadPaper>, DbErr> {
+    let rows = conn
+        .query_all(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"
+            SELECT p.title, p.categories::TEXT AS categories
+            FROM rss_papers p
+            WHERE p.created_at >= NOW() - make_interval(days => $2)
+              AND EXISTS (
+                SELECT 1 FROM user_paper_verifications v
+                WHERE v.paper_id = p.id AND v.user_id = $1
+                  AND (v.unread = FALSE OR v."match"::TEXT = 'Yes')
+              )
+            ORDER BY p.created_at DESC
+            LIMIT $3
+            "#,
+            [
+                user_id.into(),
+                (days as i32).into(),
+                SUGGESTION_SAMPLE.into(),
+            ],
+        ))
+        .await?;
+    rows.iter()
+        .map(|row| {
+            Ok(ReadPaper {
+                title: row
+                    .try_get::<Option<String>>("", "title")?
+                    .unwrap_or_default(),
+                categories: row.try_get("", "categories")?,
+            })
+        })
+        .collect()
+}
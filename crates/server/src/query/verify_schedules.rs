@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, DbErr, QueryResult, Statement};
+
+use crate::model::schedule::{VerifySchedule, VerifyScheduleInput};
+
+const COLUMNS: &str =
+    "user_id, enabled, hour_of_day, timezone, channel, max_match_limit, last_run_at, updated_at";
+
+fn from_row(row: &QueryResult) -> Result<VerifySchedule, DbErr> {
+    Ok(VerifySchedule {
+        user_id: row.try_get("", "user_id")?,
+        enabled: row.try_get("", "enabled")?,
+        hour_of_day: row.try_get::<i16>("", "hour_of_day")?.clamp(0, 23) as u8,
+        timezone: row.try_get("", "timezone")?,
+        channel: row.try_get("", "channel")?,
+        max_match_limit: row.try_get("", "max_match_limit")?,
+        last_run_at: row.try_get("", "last_run_at")?,
+        updated_at: row.try_get("", "updated_at")?,
+    })
+}
+
+pub async fn get(conn: &DatabaseConnection, user_id: i64) -> Result<Option<VerifySchedule>, DbErr> {
+    conn.query_one(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        format!("SELECT {COLUMNS} FROM verify_schedules WHERE user_id = $1"),
+        [user_id.into()],
+    ))
+    .await?
+    .as_ref()
+    .map(from_row)
+    .transpose()
+}
+
+/// Create or replace the user's schedule; `last_run_at` is kept
+pub async fn upsert(
+    conn: &DatabaseConnection,
+    user_id: i64,
+    input: &VerifyScheduleInput,
+    timezone: &str,
+) -> Result<VerifySchedule, DbErr> {
+    let row = conn
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            format!(
+                "INSERT INTO verify_schedules \
+                 (user_id, enabled, hour_of_day, timezone, channel, max_match_limit) \
+                 VALUES ($1, $2, $3, $4, $5, $6) \
+                 ON CONFLICT (user_id) DO UPDATE SET enabled = EXCLUDED.enabled, \
+                 hour_of_day = EXCLUDED.hour_of_day, timezone = EXCLUDED.timezone, \
+                 channel = EXCLUDED.channel, max_match_limit = EXCLUDED.max_match_limit, \
+                 updated_at = NOW() \
+                 RETURNING {COLUMNS}"
+            ),
+            [
+                user_id.into(),
+                input.enabled.into(),
+                (input.hour_of_day as i16).into(),
+                timezone.into(),
+                input.channel.clone().into(),
+                input.max_match_limit.into(),
+            ],
+        ))
+        .await?
+        .ok_or_else(|| DbErr::RecordNotInserted)?;
+    from_row(&row)
+}
+
+/// Every enabled schedule
+pub async fn list_enabled(conn: &DatabaseConnection) -> Result<Vec<VerifySchedule>, DbErr> {
+    conn.query_all(Statement::from_string(
+        DbBackend::Postgres,
+        format!("SELECT {COLUMNS} FROM verify_schedules WHERE enabled ORDER BY user_id"),
+    ))
+    .await?
+    .iter()
+    .map(from_row)
+    .collect()
+}
+
+/// Record a run of `slot` at `now`, unless the slot was already run (or the schedule changed
+/// since). Only one of several concurrent callers gets `true`.
+pub async fn claim(
+    conn: &DatabaseConnection,
+    user_id: i64,
+    slot: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<bool, DbErr> {
+    let result = conn
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "UPDATE verify_schedules SET last_run_at = $3 \
+             WHERE user_id = $1 AND enabled AND updated_at < $2 \
+             AND (last_run_at IS NULL OR last_run_at < $2)",
+            [user_id.into(), slot.into(), now.into()],
+        ))
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// Undo a `claim` made at `claimed_at`, e.g. because queueing the user failed
+pub async fn release(
+    conn: &DatabaseConnection,
+    user_id: i64,
+    claimed_at: DateTime<Utc>,
+    previous: Option<DateTime<Utc>>,
+) -> Result<(), DbErr> {
+    conn.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "UPDATE verify_schedules SET last_run_at = $3 WHERE user_id = $1 AND last_run_at = $2",
+        [user_id.into(), claimed_at.into(), previous.into()],
+    ))
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use chrono::{NaiveDate, NaiveTime};
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, DbErr, Statement, Value};
+
+use super::mark_read_undo::CHANNEL_FILTER;
+use crate::model::verify_stats::VerifyStatsRow;
+
+/// The user's verifications since the start of `from` (UTC), counted per UTC day and interest
+/// in one grouped query, limited to `channel` when given
+pub async fn aggregate_stats(
+    conn: &DatabaseConnection,
+    user_id: i64,
+    from: NaiveDate,
+    channel: Option<&str>,
+) -> Result<Vec<VerifyStatsRow>, DbErr> {
+    let since = from.and_time(NaiveTime::MIN).and_utc();
+    let mut values: Vec<Value> = vec![user_id.into()];
+    let mut filter = String::new();
+    if let Some(channel) = channel {
+        // CHANNEL_FILTER binds the channel as $2
+        values.push(channel.into());
+        filter = format!(" AND {CHANNEL_FILTER}");
+    }
+    values.push(since.into());
+    let sql = format!(
+        r#"
+        SELECT (v.created_at AT TIME ZONE 'UTC')::DATE AS day,
+               v.interest_id::BIGINT AS interest_id,
+               COUNT(*)::BIGINT AS verified,
+               COUNT(*) FILTER (WHERE v."match"::TEXT = 'Yes')::BIGINT AS matched
+        FROM user_paper_verifications v
+        WHERE v.user_id = $1 AND v.created_at >= ${}{filter}
+        GROUP BY 1, 2
+        "#,
+        values.len()
+    );
+    let rows = conn
+        .query_all(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql,
+            values,
+        ))
+        .await?;
+    rows.iter()
+        .map(|row| {
+            Ok(VerifyStatsRow {
+                day: row.try_get("", "day")?,
+                interest_id: row.try_get("", "interest_id")?,
+                verified: row.try_get("", "verified")?,
+                matched: row.try_get("", "matched")?,
+            })
+        })
+        .collect()
+}
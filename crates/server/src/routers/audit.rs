@@ -0,0 +1,118 @@
+use axum::extract::State;
+use common::{error::api_error::*, prelude::ApiCode};
+use serde::Deserialize;
+use serde_json::json;
+use snafu::ResultExt;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::{
+    middlewares::{auth::UserInfo, log::RequestId, query::Query},
+    model::{
+        audit::{AuditAction, AuditLogResponse},
+        base::ApiResponse,
+        page::{Page, Pagination},
+    },
+    query::audit_log::{self, AuditFilter, NewAuditEntry},
+    state::app_state::AppState,
+};
+
+/// Record a destructive operation of `user` in the audit log.
+///
+/// `details` is merged into the entry's metadata next to the request id. Failures are only
+/// logged: the operation already happened and must not be reported as failed because of it.
+pub async fn record_audit(
+    state: &AppState,
+    user: &UserInfo,
+    request_id: Option<&RequestId>,
+    action: AuditAction,
+    target_ids: &[i64],
+    details: serde_json::Value,
+) {
+    let mut metadata = match details {
+        serde_json::Value::Object(details) => details,
+        _ => serde_json::Map::new(),
+    };
+    metadata.insert(
+        "request_id".to_string(),
+        json!(request_id.map(|RequestId(id)| id)),
+    );
+    let entry = NewAuditEntry {
+        actor_id: user.id,
+        actor_open_id: Some(user.open_id.as_str()),
+        action,
+        target_ids,
+        metadata: metadata.into(),
+    };
+    if let Err(e) = audit_log::record(&state.conn, entry).await {
+        tracing::warn!(
+            user_id = user.id,
+            action = action.as_str(),
+            ?target_ids,
+            error = ?e,
+            "audit: failed to record"
+        );
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct AuditLogFilter {
+    pub action: Option<AuditAction>,
+    pub actor_id: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/audit-log",
+    summary = "List the audit log",
+    description = r#"
+Destructive operations, latest first: who ran them, on what and when.
+
+## Actions
+- `rss_source_delete`: `DELETE /rss/{id}`; targets are `rss_source` ids
+- `rss_source_update`: `PUT /rss/{id}`; targets are `rss_source` ids, `metadata.changes` holds the previous and new value of each changed field
+- `papers_batch_delete`: `POST /batch-delete`; targets are `paper` ids
+- `subscription_delete`: `DELETE /subscriptions/{subscription_id}`; targets are `subscription` ids
+- `interests_clear`: `POST /interests` with no interests; targets are the cleared `interest` ids
+- `subscriptions_clear`: `POST /subscriptions` with no sources; targets are the unsubscribed `rss_source` ids
+
+`metadata` holds the `request_id` of the request (see the `x-request-id` header) and details of the operation.
+"#,
+    params(
+        ("page" = Option<i32>, Query, description = "Page number, starts from 1 (default 1)"),
+        ("page_size" = Option<i32>, Query, description = "Entries per page (default 20)"),
+        ("action" = Option<AuditAction>, Query, description = "Only entries of this action"),
+        ("actor_id" = Option<i64>, Query, description = "Only entries of this user"),
+    ),
+    responses(
+        (status = 200, body = AuditLogResponse, description = "A page of audit entries"),
+        (status = 400, description = "Invalid filter"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 403, description = "Admin capability required"),
+        (status = 500, description = "Database error"),
+    ),
+    tag = "Admin",
+)]
+pub async fn audit_log_list(
+    State(state): State<AppState>,
+    Query(page): Query<Page>,
+    Query(filter): Query<AuditLogFilter>,
+) -> Result<ApiResponse<AuditLogResponse>, ApiError> {
+    let filter = AuditFilter {
+        action: filter.action,
+        actor_id: filter.actor_id,
+    };
+    let (entries, total) = audit_log::list(&state.conn, filter, page.offset(), page.page_size())
+        .await
+        .context(DbErrSnafu {
+            stage: "list-audit-log",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+    Ok(ApiResponse::data(AuditLogResponse {
+        pagination: Pagination::new(page.page(), page.page_size(), total),
+        entries,
+    }))
+}
+
+pub fn audit_routers() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new().routes(routes!(audit_log_list))
+}
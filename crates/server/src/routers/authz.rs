@@ -0,0 +1,135 @@
+use axum::http::Method;
+use serde::Serialize;
+use utoipa::ToSchema;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::{middlewares::authz::Capability, model::base::ApiResponse, state::app_state::AppState};
+
+/// Capability required by every route, keyed by method and path (without the API prefix).
+///
+/// Every route in the OpenAPI document must be listed here: `build_router` refuses to start
+/// otherwise.
+pub const ROUTE_CAPABILITIES: &[(&str, &str, Capability)] = &[
+    ("GET", "/health", Capability::Public),
+    ("GET", "/health/ready", Capability::Public),
+    ("GET", "/metrics", Capability::Public),
+    // rss
+    ("GET", "/rss", Capability::User),
+    ("GET", "/rss/channels", Capability::User),
+    ("GET", "/user_rss", Capability::User),
+    ("GET", "/rss/{id}", Capability::User),
+    ("POST", "/rss", Capability::User),
+    ("POST", "/rss/batch", Capability::Admin),
+    ("PUT", "/rss/{id}", Capability::Admin),
+    ("DELETE", "/rss/{id}", Capability::Admin),
+    // subscriptions
+    ("GET", "/subscriptions", Capability::User),
+    ("POST", "/subscriptions", Capability::User),
+    ("POST", "/subscriptions/one", Capability::User),
+    (
+        "DELETE",
+        "/subscriptions/{subscription_id}",
+        Capability::User,
+    ),
+    (
+        "PATCH",
+        "/subscriptions/{subscription_id}/folder",
+        Capability::User,
+    ),
+    // interests
+    ("GET", "/interests", Capability::User),
+    ("POST", "/interests", Capability::User),
+    ("GET", "/interests/detailed", Capability::User),
+    ("GET", "/interests/suggestions", Capability::User),
+    ("POST", "/interests/one", Capability::User),
+    ("DELETE", "/interests/{interest_id}", Capability::User),
+    ("GET", "/interest-presets", Capability::User),
+    ("POST", "/interest-presets/{id}/apply", Capability::User),
+    // feeds
+    ("POST", "/verify", Capability::User),
+    ("GET", "/all-verified-papers", Capability::User),
+    ("POST", "/mark-as-read", Capability::User),
+    ("POST", "/mark-as-read/undo", Capability::User),
+    ("POST", "/mark-as-unread", Capability::User),
+    ("GET", "/unverified-count-info", Capability::User),
+    ("GET", "/unread-count", Capability::User),
+    ("GET", "/starred-count", Capability::User),
+    ("POST", "/batch-delete", Capability::User),
+    ("POST", "/stream-verify", Capability::User),
+    ("GET", "/all-users-verify-info", Capability::Admin),
+    ("GET", "/unverified-papers", Capability::User),
+    ("GET", "/papers/{paper_id}", Capability::User),
+    ("PUT", "/papers/{paper_id}/note", Capability::User),
+    ("DELETE", "/papers/{paper_id}/note", Capability::User),
+    ("POST", "/papers/{paper_id}/star", Capability::User),
+    ("DELETE", "/papers/{paper_id}/star", Capability::User),
+    ("GET", "/verify/match-rate", Capability::User),
+    ("GET", "/verify-stats", Capability::User),
+    ("GET", "/verify-schedule", Capability::User),
+    ("PUT", "/verify-schedule", Capability::User),
+    // usage
+    ("GET", "/usage", Capability::User),
+    // admin
+    ("GET", "/admin/authz/matrix", Capability::Admin),
+    ("GET", "/admin/kill-switches", Capability::Admin),
+    ("PUT", "/admin/kill-switches", Capability::Admin),
+    ("GET", "/admin/usage/{user_id}", Capability::Admin),
+    ("GET", "/admin/audit-log", Capability::Admin),
+    ("POST", "/admin/interest-presets", Capability::Admin),
+    ("GET", "/admin/interest-presets/{id}", Capability::Admin),
+    ("PUT", "/admin/interest-presets/{id}", Capability::Admin),
+    ("DELETE", "/admin/interest-presets/{id}", Capability::Admin),
+];
+
+pub fn route_capability(method: &Method, path: &str) -> Option<Capability> {
+    ROUTE_CAPABILITIES
+        .iter()
+        .find(|(m, p, _)| *m == method.as_str() && *p == path)
+        .map(|(_, _, capability)| *capability)
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuthzEntry {
+    pub method: String,
+    pub path: String,
+    pub capability: Capability,
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/authz/matrix",
+    summary = "Dump the authorization matrix",
+    description = r#"
+List the capability required by every route, for auditing.
+
+## Capabilities
+- `public`: no authentication
+- `user`: any authenticated user
+- `admin`: users listed in `authz.admin_user_ids`
+- `service`: internal callers sending the `x-service-token` header
+
+Unauthenticated callers of a protected route get 401, authenticated callers without the capability get 403.
+"#,
+    responses(
+        (status = 200, body = Vec<AuthzEntry>, description = "Every route with its required capability"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 403, description = "Admin capability required"),
+    ),
+    tag = "Admin",
+)]
+pub async fn authz_matrix() -> ApiResponse<Vec<AuthzEntry>> {
+    ApiResponse::data(
+        ROUTE_CAPABILITIES
+            .iter()
+            .map(|(method, path, capability)| AuthzEntry {
+                method: method.to_string(),
+                path: path.to_string(),
+                capability: *capability,
+            })
+            .collect(),
+    )
+}
+
+pub fn authz_routers() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new().routes(routes!(authz_matrix))
+}
@@ -1,38 +1,58 @@
 use super::FEED_TAG;
+use crate::config::server_rss_config;
+use crate::consts::{
+    FEED_TEMPORARILY_DISABLED, MARK_READ_UNDO_EXPIRED, UNDO_EXPIRES_IN_HEADER, UNDO_TOKEN_HEADER,
+};
+use crate::model::audit::AuditAction;
+use crate::model::filter::{AppliedFilters, normalize_text};
+use crate::model::group::{DaySection, GroupBy, group_by_day};
+use crate::model::list::{CommaSeparated, de_opt_comma_separated};
 use crate::model::page::{Page, Pagination, de_opt_i32_from_any};
+use crate::model::tz::resolve_timezone;
+use crate::model::verify_stream::VerifyStreamEvent;
+use crate::query::mark_read_undo::{self, MarkReadReport, ReadUndoSnapshot};
+use crate::query::{paper_notes, paper_stars};
+use crate::routers::audit::record_audit;
+use crate::state::kill_switch::{KillSwitch, KillSwitches};
+use crate::state::metrics::metrics;
+use crate::state::read_undo::{ReadUndoStore, UndoClaim};
+use crate::state::usage::UsageEvent;
+use crate::state::verify_job::VerifyJobMarker;
 use crate::{
-    middlewares::auth::{User, UserInfo},
+    middlewares::{
+        auth::{User, UserInfo},
+        log::RequestId,
+        query::Query,
+    },
     model::base::ApiResponse,
-    state::app_state::AppState,
+    state::{app_state::AppState, user_context::CachedUserContext},
 };
-use axum::Json;
-use axum::extract::{Query, State};
+use axum::body::{Body, Bytes};
+use axum::extract::State;
+use axum::http::header::{ACCEPT, CONTENT_TYPE};
+use axum::http::{HeaderMap, HeaderValue};
 use axum::response::sse::{Event, KeepAlive, Sse};
-use chrono::{DateTime, FixedOffset};
+use axum::response::{IntoResponse, Response};
+use axum::{BoxError, Extension, Json};
+use chrono::{DateTime, FixedOffset, Utc};
 use common::{error::api_error::*, prelude::ApiCode};
 use feed::dispatch;
 use feed::services::{ConnectionMonitor, SseMessageHandler, VerifyService, create_verify_stream};
 use feed::workers::verify_user_papers::VerifyAllUserPapersInput;
-use futures::stream::Stream;
+use futures::stream::{Stream, StreamExt};
+use seaorm_db::entities::feed::rss_sources;
 use seaorm_db::query::feed::user_paper_verifications::{
     ListVerifiedParams, MarkReadParams, PaperWithVerification, UserPaperVerificationsQuery,
 };
 use seaorm_db::query::feed::utils::{
     UserUnverifiedPapers, count_user_unread_papers, get_user_unverified_papers_count_info,
 };
-use seaorm_db::{
-    entities::feed::rss_sources,
-    query::feed::{
-        rss_sources::RssSourcesQuery, rss_subscriptions::RssSubscriptionsQuery,
-        user_interests::UserInterestsQuery,
-    },
-};
 use serde::{Deserialize, Serialize};
 use snafu::ResultExt;
 use std::collections::HashMap;
 use std::pin::Pin;
 use std::time::Duration;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, oneshot};
 use utoipa::ToSchema;
 
 #[derive(Debug, Deserialize, ToSchema, Clone, Copy)]
@@ -51,6 +71,16 @@ pub struct VerifyRequest {
     pub channel: String,
 }
 
+/// What `POST /verify` did
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VerifyJobStatus {
+    /// A new verification job was queued
+    Queued,
+    /// A job of the user is already queued or running, nothing was added
+    AlreadyQueued,
+}
+
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct AllVerifiedPapersRequest {
     #[serde(flatten)]
@@ -58,14 +88,19 @@ pub struct AllVerifiedPapersRequest {
     /// Whether to ignore pagination and return all data (optional, defaults to false)
     pub ignore_pagination: Option<bool>,
     pub channel: Option<String>,
-    pub matches: Option<String>,
-    pub user_interest_ids: Option<String>,
+    #[serde(default, deserialize_with = "de_opt_comma_separated")]
+    pub matches: Option<CommaSeparated<String>>,
+    #[serde(default, deserialize_with = "de_opt_comma_separated")]
+    pub user_interest_ids: Option<CommaSeparated<i64>>,
     #[serde(flatten)]
     pub time_range: Option<TimeRangeParam>,
     pub ignore_time_range: Option<bool>,
     pub keyword: Option<String>,
     #[serde(default, deserialize_with = "de_opt_i32_from_any")]
     pub rss_source_id: Option<i32>,
+    pub group_by: Option<GroupBy>,
+    pub tz: Option<String>,
+    pub cache_bypass: Option<bool>,
 }
 
 /// params declaration: avoid type degradation to string caused by combination of `#[serde(flatten)]` and `IntoParams`
@@ -79,9 +114,9 @@ pub struct AllVerifiedPapersParams {
     pub ignore_pagination: Option<bool>,
     pub channel: Option<String>,
     /// Comma-separated match types: yes,no,partial
-    pub matches: Option<String>,
+    pub matches: Option<CommaSeparated<String>>,
     /// Comma-separated interest IDs
-    pub user_interest_ids: Option<String>,
+    pub user_interest_ids: Option<CommaSeparated<i64>>,
     /// Start datetime for filtering papers
     pub start: Option<DateTime<FixedOffset>>,
     /// End datetime for filtering papers
@@ -92,6 +127,12 @@ pub struct AllVerifiedPapersParams {
     pub keyword: Option<String>,
     /// Filter papers by specific RSS source ID
     pub rss_source_id: Option<i32>,
+    /// `day`: return the papers as day sections instead of a flat list
+    pub group_by: Option<GroupBy>,
+    /// IANA timezone for day boundaries (defaults to the user's zoneinfo, then UTC)
+    pub tz: Option<String>,
+    /// Build `interest_map` and `source_map` from the database, skipping the cache (debugging)
+    pub cache_bypass: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, ToSchema, Serialize)]
@@ -100,6 +141,37 @@ pub struct AllVerifiedPapersResponse {
     pub papers: Vec<PaperWithVerification>,
     pub interest_map: HashMap<i64, String>,
     pub source_map: HashMap<i32, rss_sources::Model>,
+    /// The user's notes on the papers of this page, by paper id
+    #[serde(default)]
+    pub note_map: HashMap<i32, String>,
+    /// Ids of the papers of this page the user starred
+    #[serde(default)]
+    pub starred_ids: Vec<i32>,
+    /// Normalized filters used for the query
+    #[serde(default)]
+    pub applied_filters: AppliedFilters,
+    /// With `group_by=day`: the page's papers split by local day (`papers` is then empty)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sections: Option<Vec<DaySection<PaperWithVerification>>>,
+    /// With `group_by=day`: the next page starts within the last section's day
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section_continues: Option<bool>,
+}
+
+impl AllVerifiedPapersRequest {
+    /// Normalize the raw query parameters into the filters actually passed to the query
+    pub fn applied_filters(&self) -> AppliedFilters {
+        AppliedFilters {
+            channel: normalize_text(self.channel.as_deref()),
+            keyword: normalize_text(self.keyword.as_deref()),
+            user_interest_ids: self
+                .user_interest_ids
+                .clone()
+                .map(CommaSeparated::into_inner),
+            rss_source_id: self.rss_source_id,
+            ignore_pagination: self.ignore_pagination.filter(|ignore| *ignore),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -112,6 +184,9 @@ pub struct PapersReadRequest {
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct DeletePapersRequest {
     pub ids: Vec<i32>,
+    /// Also delete starred papers (and drop their stars)
+    #[serde(default)]
+    pub force: bool,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -205,6 +280,37 @@ pub async fn unread_count(
     Ok(ApiResponse::data(count as u64))
 }
 
+#[utoipa::path(
+    get,
+    path = "/starred-count",
+    summary = "Get starred papers count",
+    description = r#"
+Count the papers the user starred (`POST /papers/{paper_id}/star`) that are still in their feed, for a shortlist badge. Takes the same `channel` filter as `GET /unread-count`.
+"#,
+    params(
+        ("channel" = Option<String>, Query, description = "Optional channel filter to count starred papers of that channel only"),
+    ),
+    responses(
+        (status = 200, body = u64, description = "Number of starred papers"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 500, description = "Internal server error"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn starred_count(
+    Query(payload): Query<FeedRequest>,
+    State(state): State<AppState>,
+    User(user): User,
+) -> Result<ApiResponse<u64>, ApiError> {
+    let count = paper_stars::count(&state.conn, user.id, payload.channel.as_deref())
+        .await
+        .context(DbErrSnafu {
+            stage: "count-starred-papers",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+    Ok(ApiResponse::data(count))
+}
+
 #[utoipa::path(
     post,
     path = "/verify",
@@ -227,7 +333,7 @@ This endpoint triggers an asynchronous verification job that matches unverified
 - `channel` (required): The channel to filter papers for verification. Only papers from RSS sources in this channel will be considered.
 
 ## Process Flow
-1. **Immediate Response**: Returns `true` immediately upon successful job queuing
+1. **Immediate Response**: Returns `queued` immediately upon successful job queuing, or `already_queued` when a job of the user is still pending
 2. **Background Processing**: Verification runs asynchronously via worker processes
 3. **AI Matching**: Each unverified paper is evaluated against all user interests using semantic similarity
 4. **Result Classification**: Papers are classified as "Yes" (relevant), "No" (not relevant), or "Partial" (somewhat relevant)
@@ -239,14 +345,16 @@ The verification job uses system-configured limits:
 - `max_rss_paper`: Maximum number of RSS papers to process per user
 
 ## Returns
-Returns `true` (wrapped in `ApiResponse<bool>`) if the verification job was successfully queued.
+Returns a `VerifyJobStatus`:
+- `queued`: a new verification job was queued
+- `already_queued`: a job of the user is already queued or running, so nothing was added
 
 **Response Structure:**
 ```json
 {
   "success": true,
   "message": "Success",
-  "data": true
+  "data": "queued"
 }
 ```
 
@@ -282,6 +390,8 @@ After triggering verification, use these endpoints to track progress:
 ## Error Scenarios
 - **500 Error**: Failed to queue verification job (Redis connection issue, queue full)
 - **401 Error**: Unauthorized - no valid authentication token
+- **503 Error**: Verification is temporarily disabled (`verify_dispatch` kill switch, code `FEED_TEMPORARILY_DISABLED`)
+- **429 Error**: More than `rss.verify_rate_limit_per_minute` verifications queued in the last minute (shared with `POST /stream-verify`); the `Retry-After` header gives the seconds to wait
 - **Invalid channel**: Job may queue but process no papers if channel doesn't exist
 
 ## Use Cases
@@ -292,7 +402,8 @@ After triggering verification, use these endpoints to track progress:
 - Batch process unverified papers
 
 ## Important Notes
-- Multiple calls will create multiple jobs (they are additive, not replaced)
+- At most one job per user is queued: calls made while it is pending return `already_queued`. The marker is kept for `rss.verify_job_dedupe_ttl_secs` at most
+- Only this endpoint takes the marker: scheduled runs (`state/verify_schedule.rs`) and `POST /stream-verify` verify regardless of a pending job
 - Verification can be time-consuming for users with many papers
 - Token usage counts toward API rate limits
 - Only processes papers from subscribed RSS sources
@@ -306,8 +417,10 @@ After triggering verification, use these endpoints to track progress:
 "#,
     request_body = VerifyRequest,
     responses(
-        (status = 200, body = bool, description = "Verification job successfully queued, returns true"),
+        (status = 200, body = VerifyJobStatus, description = "Verification job queued, or already pending for the user"),
         (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 429, description = "Rate limit exceeded, see the `Retry-After` header"),
+        (status = 503, description = "Verification is disabled by the `verify_dispatch` kill switch"),
         (status = 500, description = "Failed to queue verification job"),
     ),
     tag = FEED_TAG,
@@ -316,24 +429,40 @@ pub async fn verify(
     State(state): State<AppState>,
     User(user): User,
     Json(payload): Json<VerifyRequest>,
-) -> Result<ApiResponse<bool>, ApiError> {
+) -> Result<ApiResponse<VerifyJobStatus>, ApiError> {
     tracing::info!("verify papers");
+    KillSwitches::new(&state)
+        .ensure_released(KillSwitch::VerifyDispatch)
+        .await?;
+
+    let marker = VerifyJobMarker::new(&state);
+    if !marker.claim(user.id).await {
+        tracing::info!(user_id = user.id, "verify job already queued");
+        return Ok(ApiResponse::data(VerifyJobStatus::AlreadyQueued));
+    }
 
-    dispatch(
+    let dispatched = dispatch(
         VerifyAllUserPapersInput {
             user_id: user.id,
             channel: payload.channel,
             max_prompt_number: state.config.rss.max_prompt_number,
             max_rss_paper: state.config.rss.max_rss_paper,
         },
-        state.redis.apalis_conn,
+        state.redis.apalis_conn.clone(),
     )
-    .await
-    .map_err(|e| ApiError::CustomError {
-        message: format!("verify_papers: {e}"),
-        code: ApiCode::COMMON_FEED_ERROR,
-    })?;
-    Ok(ApiResponse::data(true))
+    .await;
+    if let Err(e) = dispatched {
+        if let Err(release) = marker.release(user.id).await {
+            tracing::warn!(user_id = user.id, error = ?release, "failed to release verify job marker");
+        }
+        return Err(ApiError::CustomError {
+            message: format!("verify_papers: {e}"),
+            code: ApiCode::COMMON_FEED_ERROR,
+        });
+    }
+    state.usage.record(user.id, UsageEvent::VerifyRun, 1);
+    metrics().verify_jobs_dispatched.inc();
+    Ok(ApiResponse::data(VerifyJobStatus::Queued))
 }
 
 #[utoipa::path(
@@ -361,11 +490,24 @@ This endpoint returns papers that have been verified against the user's interest
 ### Filtering Parameters
 - `channel` (optional): Filter by specific channel name (e.g., "arxiv", "default"). Only returns papers from matching channel.
 - `user_interest_ids` (optional): Filter by specific interest IDs as comma-separated string (e.g., "1,2,3,4"). The filtering is applied at the database level.
-  - Empty string or spaces are ignored (same as not providing the parameter)
+  - Empty string or spaces are ignored (same as not providing the parameter); blank entries such as in `1,,2` are skipped
+  - Any entry that is not an integer is rejected with 400, naming the parameter and the bad entry
   - Only returns papers that match at least one of the specified interests
 - `keyword` (optional): Search keyword to filter papers by title or content. Performs substring matching.
 - `rss_source_id` (optional): Filter papers by specific RSS source ID. Only shows papers from that exact source.
 
+### Grouping Parameters
+- `group_by` (optional): `day` returns the page as day sections in `sections` (and an empty `papers`). Pagination still counts papers, not sections.
+- `tz` (optional): IANA timezone for day boundaries, e.g. `Asia/Shanghai`. Defaults to the user's `zoneinfo`, then UTC. Invalid values are rejected with 400.
+
+Each section is `{ "date": "2024-05-12", "count": 3, "papers": [...] }`. A page has one section per local day of `pub_date`, newest day first, with the papers of a day in the listing order; papers without `pub_date` come last, in a section with `"date": null`. `section_continues: true` means the next page starts within the last section's day, so the client should merge it instead of opening a new header.
+
+### Streaming Export
+With `ignore_pagination=true` and `Accept: application/x-ndjson`, the response is `application/x-ndjson` instead of the JSON envelope: one `PaperWithVerification` object per line, in the listing order, read from the database 500 papers at a time. The filters apply exactly as in JSON mode; `group_by`, the maps and the pagination object are not part of the stream. A database error mid-stream aborts the response, so a truncated body means the export failed.
+
+### Debugging Parameters
+- `cache_bypass` (optional, default: false): `interest_map` and `source_map` come from a per-user cache (30s, invalidated by interest, subscription and source changes); `true` rebuilds them from the database.
+
 ### Deprecated/Not Implemented Parameters
 ⚠️ **Note:** The following parameters are declared but not currently implemented:
 - `matches` (optional): Declared but parsing logic is commented out. Passing values will have no effect.
@@ -380,12 +522,21 @@ Returns an `AllVerifiedPapersResponse` object containing:
 - `page` (i32): Current page number
 - `page_size` (i32): Items per page
 - `total` (u64): Total number of papers matching the filter criteria
-- `total_pages` (u64): Total number of pages
+- `total_pages` (u64): Total number of pages (rounded up)
+- `has_next` (bool): Whether a page exists after the current one
+- `has_prev` (bool): Whether a page exists before the current one
 
 When `ignore_pagination=true`:
 - `page`: Set to 1
 - `page_size`: Set to total count
 - `total_pages`: Set to 1
+- `has_next` / `has_prev`: Set to false
+
+### Applied Filters
+`applied_filters` echoes the normalized filters actually used for the query:
+- `channel`, `keyword`: trimmed; blank values are omitted
+- `user_interest_ids`: parsed ids; omitted when empty
+- `rss_source_id`, `ignore_pagination`: omitted when not applied
 
 ### Papers Array
 Array of `PaperWithVerifications` objects, each containing:
@@ -405,6 +556,13 @@ Array of `PaperWithVerifications` objects, each containing:
 - Keys are source IDs
 - Values include: id, channel, name, url, description, logo_img, background_img, timestamps
 
+### Note Map
+- `HashMap<i32, String>`: The user's notes (see `PUT /papers/{paper_id}/note`) on the papers of this page
+- Keys are paper IDs; papers without a note are absent
+
+### Starred IDs
+- `Vec<i32>`: IDs of the papers of this page the user starred (see `POST /papers/{paper_id}/star`), ascending
+
 ## Example Requests
 
 ### Paginated Request (Default)
@@ -460,7 +618,12 @@ Returns page 2 (items 51-100) of arxiv papers containing "neural" and matching i
       "page": 1,
       "page_size": 20,
       "total": 156,
-      "total_pages": 8
+      "total_pages": 8,
+      "has_next": true,
+      "has_prev": false
+    },
+    "applied_filters": {
+      "channel": "arxiv"
     },
     "papers": [
       {
@@ -521,7 +684,10 @@ Returns page 2 (items 51-100) of arxiv papers containing "neural" and matching i
         AllVerifiedPapersParams
     ),
     responses(
-        (status = 200, body = AllVerifiedPapersResponse, description = "Successfully retrieved verified papers with pagination and metadata"),
+        (status = 200, description = "Successfully retrieved verified papers with pagination and metadata; with `ignore_pagination=true` and `Accept: application/x-ndjson`, one paper per line", content(
+            (AllVerifiedPapersResponse = "application/json"),
+            (PaperWithVerification = "application/x-ndjson"),
+        )),
         (status = 401, description = "Unauthorized - valid authentication required"),
         (status = 500, description = "Database error or failed to retrieve papers"),
     ),
@@ -530,50 +696,60 @@ Returns page 2 (items 51-100) of arxiv papers containing "neural" and matching i
 pub async fn all_verified_papers(
     State(state): State<AppState>,
     User(user): User,
+    headers: HeaderMap,
     Query(payload): Query<AllVerifiedPapersRequest>,
-) -> Result<ApiResponse<AllVerifiedPapersResponse>, ApiError> {
+) -> Result<Response, ApiError> {
     tracing::info!("list all verified papers");
     tracing::info!("user: {:?}, payload: {:?}", user, payload);
 
-    // Parse comma-separated user_interest_ids string to Vec<i64>
-    let parsed_user_interest_ids: Option<Vec<i64>> =
-        payload.user_interest_ids.as_ref().and_then(|ids_str| {
-            if ids_str.trim().is_empty() {
-                None
-            } else {
-                let ids: Result<Vec<i64>, _> = ids_str
-                    .split(',')
-                    .map(|s| s.trim())
-                    .filter(|s| !s.is_empty())
-                    .map(|s| s.parse::<i64>())
-                    .collect();
-                ids.ok()
-            }
-        });
+    let applied_filters = payload.applied_filters();
+    if applied_filters.ignore_pagination == Some(true) && accepts_ndjson(&headers) {
+        tracing::info!(user_id = user.id, "stream verified papers as ndjson");
+        state.usage.record(user.id, UsageEvent::Export, 1);
+        return Ok(ndjson_export(
+            state,
+            user.id,
+            applied_filters,
+            payload.ignore_time_range,
+        ));
+    }
+    let group_by_day = payload.group_by == Some(GroupBy::Day);
+    let tz = if group_by_day {
+        Some(resolve_timezone(payload.tz.as_deref(), &user)?)
+    } else {
+        None
+    };
 
     // Check if pagination should be ignored
     let use_pagination = !payload.ignore_pagination.unwrap_or(false);
 
-    // If pagination is enabled, use pagination; otherwise return all data
+    // If pagination is enabled, use pagination; otherwise return all data.
+    // Day grouping fetches one extra row to tell whether the last day continues on the next page.
     let (offset, limit) = if use_pagination {
+        let page_size = payload.pagination.page_size();
         (
             Some(payload.pagination.offset()),
-            Some(payload.pagination.page_size()),
+            Some(if group_by_day {
+                page_size + 1
+            } else {
+                page_size
+            }),
         )
     } else {
         (None, None)
     };
 
-    let verified_papers = UserPaperVerificationsQuery::list_verified_by_user(
-        &state.conn,
+    let read_conn = state.read_conn_for(user.id).await;
+    let mut verified_papers = UserPaperVerificationsQuery::list_verified_by_user(
+        read_conn,
         user.id,
         ListVerifiedParams {
-            channel: payload.channel.clone(),
-            user_interest_ids: parsed_user_interest_ids,
+            channel: applied_filters.channel.clone(),
+            user_interest_ids: applied_filters.user_interest_ids.clone(),
             offset, // Use calculated offset
             limit,  // Use calculated limit
-            keyword: payload.keyword.clone(),
-            rss_source_id: payload.rss_source_id,
+            keyword: applied_filters.keyword.clone(),
+            rss_source_id: applied_filters.rss_source_id,
             ignore_pagination: payload.ignore_pagination,
             ignore_time_range: payload.ignore_time_range,
         },
@@ -584,63 +760,170 @@ pub async fn all_verified_papers(
         code: ApiCode::COMMON_DATABASE_ERROR,
     })?;
 
-    // Query user interests and subscription sources in parallel
-    let (interest_items_result, subscriptions_result) = tokio::join!(
-        UserInterestsQuery::list_by_user_id(&state.conn, user.id),
-        RssSubscriptionsQuery::list_by_user_id(&state.conn, user.id, None)
-    );
+    let (papers, sections, section_continues) = match tz {
+        Some(tz) => {
+            let next = if use_pagination
+                && verified_papers.items.len() > payload.pagination.page_size() as usize
+            {
+                verified_papers
+                    .items
+                    .pop()
+                    .map(|paper| paper_pub_date(&paper))
+            } else {
+                None
+            };
+            let (sections, continues) =
+                group_by_day(verified_papers.items, tz, paper_pub_date, next);
+            (Vec::new(), Some(sections), Some(continues))
+        }
+        None => (verified_papers.items, None, None),
+    };
 
-    let interest_items = interest_items_result.context(DbErrSnafu {
-        stage: "list-user-interests",
-        code: ApiCode::COMMON_DATABASE_ERROR,
-    })?;
-    let interest_map: HashMap<i64, String> = interest_items
+    let paper_ids: Vec<i32> = papers
+        .iter()
+        .chain(
+            sections
+                .iter()
+                .flatten()
+                .flat_map(|section| &section.papers),
+        )
+        .map(|paper| paper.id)
+        .collect();
+    let note_map = paper_notes::list_for_papers(read_conn, user.id, &paper_ids)
+        .await
+        .context(DbErrSnafu {
+            stage: "list-paper-notes",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+    let mut starred_ids: Vec<i32> = paper_stars::starred_among(read_conn, user.id, &paper_ids)
+        .await
+        .context(DbErrSnafu {
+            stage: "get-starred-papers",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?
         .into_iter()
-        .map(|m| (m.id, m.interest))
         .collect();
+    starred_ids.sort_unstable();
 
-    let subscriptions = subscriptions_result.context(DbErrSnafu {
-        stage: "get-rss-subscriptions",
-        code: ApiCode::COMMON_DATABASE_ERROR,
-    })?;
-    let mut source_ids: Vec<i32> = subscriptions.into_iter().map(|s| s.source_id).collect();
-    source_ids.sort_unstable();
-    source_ids.dedup();
-
-    let sources: Vec<rss_sources::Model> = if source_ids.is_empty() {
-        Vec::new()
-    } else {
-        RssSourcesQuery::get_by_ids(&state.conn, source_ids)
-            .await
-            .context(DbErrSnafu {
-                stage: "get-rss-sources",
-                code: ApiCode::COMMON_DATABASE_ERROR,
-            })?
-    };
-    let source_map: HashMap<i32, rss_sources::Model> =
-        sources.into_iter().map(|m| (m.id, m)).collect();
+    // Interests and subscribed sources are served from the short-lived per-user cache
+    let context =
+        CachedUserContext::new(&state).bypass_cache(payload.cache_bypass.unwrap_or(false));
+    let (interest_map, source_map) =
+        tokio::join!(context.interests(user.id), context.sources(user.id));
+    let interest_map = interest_map?;
+    let source_map = source_map?;
 
     Ok(ApiResponse::data(AllVerifiedPapersResponse {
         pagination: if use_pagination {
-            Pagination {
-                page: payload.pagination.page(),
-                page_size: payload.pagination.page_size(),
-                total: verified_papers.total,
-                total_pages: verified_papers.total / payload.pagination.page_size() as u64,
-            }
+            Pagination::new(
+                payload.pagination.page(),
+                payload.pagination.page_size(),
+                verified_papers.total,
+            )
         } else {
             // When not using pagination, return pagination info for all data
-            Pagination {
-                page: 1,
-                page_size: verified_papers.total as i32,
-                total: verified_papers.total,
-                total_pages: 1,
-            }
+            Pagination::all(verified_papers.total)
         },
-        papers: verified_papers.items,
+        papers,
         interest_map,
         source_map,
-    }))
+        note_map,
+        starred_ids,
+        applied_filters,
+        sections,
+        section_continues,
+    })
+    .into_response())
+}
+
+/// Papers read per query by the NDJSON export
+const NDJSON_BATCH_SIZE: i32 = 500;
+
+const NDJSON: &str = "application/x-ndjson";
+
+/// Whether the `Accept` header lists NDJSON
+fn accepts_ndjson(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|media| {
+            media
+                .split(';')
+                .next()
+                .is_some_and(|media| media.trim().eq_ignore_ascii_case(NDJSON))
+        })
+}
+
+/// Every verified paper matching `filters`, one JSON object per line, read
+/// `NDJSON_BATCH_SIZE` papers at a time so the export is never held in memory at once
+fn ndjson_export(
+    state: AppState,
+    user_id: i64,
+    filters: AppliedFilters,
+    ignore_time_range: Option<bool>,
+) -> Response {
+    let batches = futures::stream::try_unfold(Some(0), move |offset| {
+        let state = state.clone();
+        let filters = filters.clone();
+        async move {
+            let Some(offset) = offset else {
+                return Ok::<_, BoxError>(None);
+            };
+            let batch = UserPaperVerificationsQuery::list_verified_by_user(
+                state.read_conn_for(user_id).await,
+                user_id,
+                ListVerifiedParams {
+                    channel: filters.channel,
+                    user_interest_ids: filters.user_interest_ids,
+                    offset: Some(offset),
+                    limit: Some(NDJSON_BATCH_SIZE),
+                    keyword: filters.keyword,
+                    rss_source_id: filters.rss_source_id,
+                    ignore_pagination: None,
+                    ignore_time_range,
+                },
+            )
+            .await
+            .inspect_err(
+                |e| tracing::error!(user_id, offset, error = %e, "ndjson export failed"),
+            )?;
+
+            let mut chunk = Vec::new();
+            for paper in &batch.items {
+                serde_json::to_writer(&mut chunk, paper)?;
+                chunk.push(b'\n');
+            }
+            let next = (batch.items.len() as i32 == NDJSON_BATCH_SIZE)
+                .then_some(offset + NDJSON_BATCH_SIZE);
+            Ok(Some((Bytes::from(chunk), next)))
+        }
+    });
+    ([(CONTENT_TYPE, NDJSON)], Body::from_stream(batches)).into_response()
+}
+
+/// Day key of a listed paper: its `pub_date`
+fn paper_pub_date(paper: &PaperWithVerification) -> Option<DateTime<Utc>> {
+    paper.pub_date.map(|date| date.with_timezone(&Utc))
+}
+
+/// Body of `POST /mark-as-read`: `MarkReadParams` plus the response shape
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MarkReadRequest {
+    #[serde(flatten)]
+    pub params: MarkReadParams,
+    /// Report the outcome of every id instead of a bare count
+    #[serde(default)]
+    pub detailed: bool,
+}
+
+/// A count by default, a per-id report with `detailed=true`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum MarkReadResponse {
+    Count(u64),
+    Detailed(MarkReadReport),
 }
 
 #[utoipa::path(
@@ -667,6 +950,7 @@ This endpoint allows users to mark verified papers as read, updating their `unre
 - `paper_ids` (required): Array of paper IDs to mark as read. Should be IDs of verified papers for the authenticated user.
 - `channel` (optional): Channel filter. When provided, only papers from this channel will be affected. If not provided, no channel filtering is applied.
 - `read_all` (required, boolean): When `true`, marks ALL user's papers as read (ignores `paper_ids`). When `false`, marks only the specified `paper_ids`.
+- `detailed` (optional, default `false`): Return a per-id report instead of a count (see below)
 
 ## Behavior Modes
 
@@ -708,11 +992,31 @@ This endpoint allows users to mark verified papers as read, updating their `unre
 ## Returns
 Returns a `u64` representing the number of papers successfully marked as read.
 
+With `read_all=true` the response also carries an `x-undo-token` header (and `x-undo-expires-in`, in seconds): pass it to `POST /mark-as-read/undo` to restore exactly the papers that were unread before.
+
 Examples:
 - `0`: No papers were marked (e.g., invalid IDs)
 - `5`: 5 papers were marked as read
 - `156`: All 156 papers were marked (when using `read_all=true`)
 
+### Detailed Report
+With `"detailed": true` the response is a `MarkReadReport` telling what happened to each distinct id, in request order:
+```json
+{
+  "count": 1,
+  "results": [
+    { "paper_id": 42, "outcome": "marked" },
+    { "paper_id": 43, "outcome": "already_read" },
+    { "paper_id": 44, "outcome": "not_found" }
+  ]
+}
+```
+- `marked`: was unread, now read (counted in `count`)
+- `already_read`: nothing to do
+- `not_found`: no verification of the user for this id (unknown, another user's, deleted, or outside `channel`)
+
+With `read_all=true` there are no ids to report: `results` is empty and `count` is the number marked.
+
 ## Important Notes
 - This operation only affects verified papers (not unverified)
 - Non-existent or invalid paper IDs are silently ignored (not counted in return value)
@@ -793,10 +1097,17 @@ Returns count of papers from "arxiv" channel marked as read.
 ## Related Endpoints
 - Use `GET /all-verified-papers` to retrieve papers (filter by unread status)
 - Use `GET /unread-count` to get count of unread papers
+- Use `POST /mark-as-read/undo` to undo a `read_all=true`
+- Use `POST /mark-as-unread` to set papers back to unread
 "#,
-    request_body = MarkReadParams,
+    request_body = MarkReadRequest,
+    params(("Idempotency-Key" = Option<String>, Header, description = "Retries with the same key get the first successful response back instead of running again; 409 while the first is still running, 422 if reused with a different body")),
     responses(
-        (status = 200, body = u64, description = "Successfully marked papers as read, returns count of affected papers"),
+        (status = 200, body = MarkReadResponse, description = "Successfully marked papers as read: the count of affected papers, or a per-id report with `detailed=true`",
+            headers(
+                ("x-undo-token" = String, description = "Token for `POST /mark-as-read/undo`, only with `read_all=true`"),
+                ("x-undo-expires-in" = u64, description = "Seconds the undo token stays valid"),
+            )),
         (status = 401, description = "Unauthorized - valid authentication required"),
         (status = 500, description = "Database error or failed to mark papers as read"),
     ),
@@ -805,10 +1116,38 @@ Returns count of papers from "arxiv" channel marked as read.
 pub async fn papers_make_read(
     State(state): State<AppState>,
     User(user): User,
-    Json(payload): Json<MarkReadParams>,
-) -> Result<ApiResponse<u64>, ApiError> {
+    Json(MarkReadRequest {
+        params: payload,
+        detailed,
+    }): Json<MarkReadRequest>,
+) -> Result<(HeaderMap, ApiResponse<MarkReadResponse>), ApiError> {
     tracing::info!("list all verified papers");
 
+    if detailed && !payload.read_all {
+        let report = mark_read_undo::mark_read_detailed(
+            &state.conn,
+            user.id,
+            &payload.paper_ids,
+            payload.channel.as_deref(),
+        )
+        .await
+        .context(DbErrSnafu {
+            stage: "mark-read-detailed",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+        return Ok((
+            HeaderMap::new(),
+            ApiResponse::data(MarkReadResponse::Detailed(report)),
+        ));
+    }
+
+    // Snapshot what is unread before "mark all as read" so it can be undone
+    let undo_token = if payload.read_all {
+        save_read_undo_snapshot(&state, user.id, payload.channel.as_deref()).await?
+    } else {
+        None
+    };
+
     let result = UserPaperVerificationsQuery::mark_read_by_user(&state.conn, user.id, payload)
         .await
         .context(DbErrSnafu {
@@ -816,7 +1155,195 @@ pub async fn papers_make_read(
             code: ApiCode::COMMON_DATABASE_ERROR,
         })?;
 
-    Ok(ApiResponse::data(result))
+    let mut headers = HeaderMap::new();
+    if let Some(token) = undo_token.and_then(|t| HeaderValue::from_str(&t).ok()) {
+        headers.insert(UNDO_TOKEN_HEADER, token);
+        headers.insert(
+            UNDO_EXPIRES_IN_HEADER,
+            HeaderValue::from(server_rss_config().mark_read_undo_window_secs),
+        );
+    }
+
+    let response = if detailed {
+        MarkReadResponse::Detailed(MarkReadReport {
+            count: result,
+            results: Vec::new(),
+        })
+    } else {
+        MarkReadResponse::Count(result)
+    };
+    Ok((headers, ApiResponse::data(response)))
+}
+
+/// `None` when the rows are too fragmented to snapshot or Redis is unavailable
+async fn save_read_undo_snapshot(
+    state: &AppState,
+    user_id: i64,
+    channel: Option<&str>,
+) -> Result<Option<String>, ApiError> {
+    let max_ids = server_rss_config().mark_read_undo_max_ids;
+    let unread = mark_read_undo::list_unread_ids(&state.conn, user_id, channel, max_ids + 1)
+        .await
+        .context(DbErrSnafu {
+            stage: "snapshot-unread-ids",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+    // ranges need the read rows around the unread ones, only fetched when over the cap
+    let rows = if unread.len() <= max_ids {
+        unread.into_iter().map(|id| (id, true)).collect()
+    } else {
+        mark_read_undo::list_read_state(&state.conn, user_id, channel)
+            .await
+            .context(DbErrSnafu {
+                stage: "snapshot-read-state",
+                code: ApiCode::COMMON_DATABASE_ERROR,
+            })?
+    };
+    let Some(snapshot) = ReadUndoSnapshot::from_rows(&rows, channel, max_ids) else {
+        tracing::warn!(
+            user_id,
+            rows = rows.len(),
+            "mark all as read: too fragmented to undo"
+        );
+        return Ok(None);
+    };
+    match ReadUndoStore::new(state).save(user_id, &snapshot).await {
+        Ok(token) => Ok(Some(token)),
+        Err(e) => {
+            tracing::warn!(user_id, error = ?e, "mark all as read: failed to save undo snapshot");
+            Ok(None)
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MarkReadUndoRequest {
+    /// Value of the `x-undo-token` header returned by `POST /mark-as-read`
+    pub undo_token: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/mark-as-read/undo",
+    summary = "Undo a mark all as read",
+    description = r#"
+Restore the papers that were unread right before a `POST /mark-as-read` with `read_all=true`.
+
+## Request Body
+```json
+{
+  "undo_token": "5f0c9a..."
+}
+```
+
+The token comes from the `x-undo-token` response header of `POST /mark-as-read`; `x-undo-expires-in` tells how many seconds it stays valid (default 300).
+
+## Behavior
+- Exactly the rows that were unread before are set back to unread; papers read earlier stay read
+- Redeeming the same token again is a no-op returning `0`
+- Large sets are stored as id ranges instead of ids; very fragmented sets get no token at all
+
+## Returns
+Returns a `u64` with the number of papers set back to unread.
+
+## Errors
+- **410**: The token is unknown or the undo window has passed
+"#,
+    request_body = MarkReadUndoRequest,
+    responses(
+        (status = 200, body = u64, description = "Number of papers restored to unread"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 410, description = "Undo token unknown or expired"),
+        (status = 500, description = "Database or Redis error"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn papers_make_read_undo(
+    State(state): State<AppState>,
+    User(user): User,
+    Json(payload): Json<MarkReadUndoRequest>,
+) -> Result<ApiResponse<u64>, ApiError> {
+    tracing::info!(user_id = user.id, "undo mark all as read");
+
+    let store = ReadUndoStore::new(&state);
+    let snapshot = match store.claim(user.id, &payload.undo_token).await? {
+        UndoClaim::Snapshot(snapshot) => snapshot,
+        UndoClaim::AlreadyUsed => return Ok(ApiResponse::data(0)),
+        UndoClaim::Expired => {
+            return Err(ApiError::CustomError {
+                message: "Undo token is unknown or has expired".to_string(),
+                code: MARK_READ_UNDO_EXPIRED,
+            });
+        }
+    };
+
+    match mark_read_undo::restore_unread(&state.conn, user.id, &snapshot).await {
+        Ok(restored) => Ok(ApiResponse::data(restored)),
+        Err(e) => {
+            store.release(user.id, &payload.undo_token).await;
+            Err(e).context(DbErrSnafu {
+                stage: "restore-unread",
+                code: ApiCode::COMMON_DATABASE_ERROR,
+            })
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/mark-as-unread",
+    summary = "Mark papers as unread",
+    description = r#"
+Set verified papers back to unread, e.g. after an accidental "mark all as read" whose undo window has passed. Takes the same body as `POST /mark-as-read`.
+
+## Request Body
+```json
+{
+  "paper_ids": [42, 1337],
+  "channel": "arxiv",
+  "read_all": false
+}
+```
+- `paper_ids`: papers to mark as unread (ignored when `read_all=true`)
+- `channel` (optional): limit to papers from sources in this channel
+- `read_all`: mark every read paper of the user (in `channel`, if given) as unread
+
+## Returns
+Returns a `u64` with the number of papers actually changed: papers that are already unread, unknown or not the user's are not counted, so calling it twice returns `0` the second time.
+"#,
+    request_body = MarkReadParams,
+    responses(
+        (status = 200, body = u64, description = "Number of papers set back to unread"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 500, description = "Database error"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn papers_make_unread(
+    State(state): State<AppState>,
+    User(user): User,
+    Json(payload): Json<MarkReadParams>,
+) -> Result<ApiResponse<u64>, ApiError> {
+    tracing::info!(
+        user_id = user.id,
+        read_all = payload.read_all,
+        "mark papers as unread"
+    );
+
+    let changed = mark_read_undo::mark_unread(
+        &state.conn,
+        user.id,
+        &payload.paper_ids,
+        payload.channel.as_deref(),
+        payload.read_all,
+    )
+    .await
+    .context(DbErrSnafu {
+        stage: "mark-unread",
+        code: ApiCode::COMMON_DATABASE_ERROR,
+    })?;
+
+    Ok(ApiResponse::data(changed))
 }
 
 #[utoipa::path(
@@ -838,14 +1365,21 @@ This endpoint allows users to permanently delete verified papers from their feed
 
 ## Parameters
 - `ids`: Array of paper IDs to delete
+- `force` (optional, default `false`): also delete starred papers
+
+## Starred Papers
+Starred papers (`POST /papers/{paper_id}/star`) are protected: they are skipped, and not counted, unless `force` is `true`. A forced delete also removes their stars.
 
 ## Returns
 Returns a `u64` representing the number of papers successfully deleted.
 
 ## Note
 This operation is permanent and cannot be undone. Deleted papers will not appear in the user's feed again.
+
+Only the user's own verifications are deleted: ids that are unknown or belong to another user are ignored and not counted.
 "#,
     request_body = DeletePapersRequest,
+    params(("Idempotency-Key" = Option<String>, Header, description = "Retries with the same key get the first successful response back instead of running again; 409 while the first is still running, 422 if reused with a different body")),
     responses(
         (status = 200, body = u64, description = "Successfully deleted papers, returns count of deleted papers"),
         (status = 401, description = "Unauthorized - valid authentication required"),
@@ -856,17 +1390,62 @@ This operation is permanent and cannot be undone. Deleted papers will not appear
 pub async fn batch_delete(
     State(state): State<AppState>,
     User(user): User,
+    request_id: Option<Extension<RequestId>>,
     Json(payload): Json<DeletePapersRequest>,
 ) -> Result<ApiResponse<u64>, ApiError> {
     tracing::info!("delete verified papers by ids");
 
-    let affected =
-        UserPaperVerificationsQuery::delete_by_user_and_ids(&state.conn, user.id, payload.ids)
+    let starred = paper_stars::starred_among(&state.conn, user.id, &payload.ids)
+        .await
+        .context(DbErrSnafu {
+            stage: "get-starred-papers",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+    let ids: Vec<i32> = if payload.force {
+        payload.ids
+    } else {
+        if !starred.is_empty() {
+            tracing::info!(
+                user_id = user.id,
+                skipped = starred.len(),
+                "batch delete skips starred papers"
+            );
+        }
+        payload
+            .ids
+            .into_iter()
+            .filter(|id| !starred.contains(id))
+            .collect()
+    };
+    if ids.is_empty() {
+        return Ok(ApiResponse::data(0));
+    }
+
+    let target_ids: Vec<i64> = ids.iter().map(|&id| id as i64).collect();
+    let affected = UserPaperVerificationsQuery::delete_by_user_and_ids(&state.conn, user.id, ids)
+        .await
+        .context(DbErrSnafu {
+            stage: "delete-verified-papers",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+    if payload.force && !starred.is_empty() {
+        let starred: Vec<i32> = starred.into_iter().collect();
+        paper_stars::unstar(&state.conn, user.id, &starred)
             .await
             .context(DbErrSnafu {
-                stage: "delete-verified-papers",
+                stage: "unstar-deleted-papers",
                 code: ApiCode::COMMON_DATABASE_ERROR,
             })?;
+    }
+    record_audit(
+        &state,
+        &user,
+        request_id.as_deref(),
+        AuditAction::PapersBatchDelete,
+        &target_ids,
+        serde_json::json!({ "force": payload.force, "deleted": affected }),
+    )
+    .await;
 
     Ok(ApiResponse::data(affected))
 }
@@ -930,20 +1509,31 @@ This endpoint creates a persistent SSE connection that streams verification prog
    - Contains: user_id, matched, max_limit, timestamp, status
    - The connection closes after this event
 
+7. **error**: Sent instead of every other event when streaming or verification is disabled by a kill switch (`sse_streams` or `verify_dispatch`), or when the user could not be added to the verification list
+   - Contains: type (`error`), status (`error`), code (`FEED_TEMPORARILY_DISABLED` for a kill switch, `COMMON_FEED_ERROR` otherwise), message, timestamp
+   - The connection closes after this event
+
+The payloads are described by the `VerifyStreamEvent` schema, whose `type` is the event name.
+
+## Rate Limit
+Each connection counts against `rss.verify_rate_limit_per_minute`, shared with `POST /verify`. Past it the request is refused with 429 and a `Retry-After` header, before any event is sent.
+
 ## Connection Management
 - Automatically adds user to verification list before starting (triggers background worker)
 - Subscribes to Redis pub/sub for real-time updates
 - Automatically unsubscribes and cleans up when connection is closed
 - Sends keep-alive messages every 10 seconds
 - Heartbeat events sent every 1 second with current verification status
+- Up to `rss.verify_stream_channel_capacity` events are buffered for a client that reads slower than they are published
 
 ## Note
 This is a long-lived connection. The client should be prepared to handle connection drops and reconnect if needed. The connection may be terminated early if the maximum match limit is reached.
 "#,
     request_body = StreamVerifyRequest,
     responses(
-        (status = 200, description = "SSE connection established successfully, will stream verification updates"),
+        (status = 200, body = VerifyStreamEvent, content_type = "text/event-stream", description = "SSE connection established successfully, will stream verification updates"),
         (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 429, description = "Rate limit exceeded, see the `Retry-After` header"),
         (status = 500, description = "Failed to establish SSE connection or update metadata"),
     ),
     tag = FEED_TAG,
@@ -955,6 +1545,14 @@ pub async fn stream_verify(
 ) -> Sse<Pin<Box<dyn Stream<Item = Result<Event, ApiError>> + Send>>> {
     tracing::info!("SSE connection established for user: {}", user.id);
     let user_id = user.id;
+
+    let kill_switches = KillSwitches::new(&state);
+    for switch in [KillSwitch::SseStreams, KillSwitch::VerifyDispatch] {
+        if kill_switches.is_engaged(switch).await {
+            return Sse::new(Box::pin(disabled_stream(switch))
+                as Pin<Box<dyn Stream<Item = Result<Event, ApiError>> + Send>>);
+        }
+    }
     let verify_papers_sub_channel = state.config.rss.verify_papers_channel.clone();
 
     // Create connection monitor, automatically triggers Drop when SSE stream ends
@@ -965,7 +1563,8 @@ pub async fn stream_verify(
     );
 
     // Create broadcast channel for Redis PubSub message forwarding
-    let (tx, rx) = broadcast::channel::<String>(1000);
+    let (tx, rx) =
+        broadcast::channel::<String>(server_rss_config().verify_stream_channel_capacity.max(1));
 
     // Create message handler to forward Redis messages to SSE stream
     let handler = Box::new(SseMessageHandler::new(
@@ -999,6 +1598,8 @@ pub async fn stream_verify(
         .unwrap_or(state.config.rss.max_match_limit_per_user as i32);
     let append_delay_ms = state.config.rss.update_task_merge_delay_ms.unwrap_or(500);
 
+    // An `error` event is sent, and the stream closed, when the user cannot be queued
+    let (append_failed_tx, append_failed_rx) = oneshot::channel::<VerifyStreamEvent>();
     tokio::spawn(async move {
         tokio::time::sleep(Duration::from_millis(append_delay_ms)).await;
         if let Err(e) = verify_service_for_append
@@ -1011,6 +1612,10 @@ pub async fn stream_verify(
             .await
         {
             tracing::error!("Failed to append user to verify list: {}", e);
+            let _ = append_failed_tx.send(VerifyStreamEvent::error(
+                ApiCode::COMMON_FEED_ERROR,
+                format!("failed to queue verification: {e}"),
+            ));
         }
     });
 
@@ -1027,11 +1632,40 @@ pub async fn stream_verify(
         conn_clone_for_sse,
         payload.ignore_ready_event.unwrap_or(false),
     );
+    let append_failed = futures::stream::once(append_failed_rx)
+        .filter_map(|event| futures::future::ready(event.ok()))
+        .map(|event| (Ok(event.to_event()), true));
+    let stream = futures::stream::select(stream.map(|item| (item, false)), append_failed).scan(
+        false,
+        |closed, (item, last)| {
+            let item = (!*closed).then_some(item);
+            *closed |= last;
+            futures::future::ready(item)
+        },
+    );
+
+    // connected time, counted when the stream is dropped
+    state.usage.record(user_id, UsageEvent::VerifyRun, 1);
+    let usage_timer = state.usage.timer(user_id, UsageEvent::SseSeconds);
+    let connection = metrics().sse_connection();
+    let stream = stream.map(move |item| {
+        let _ = (&usage_timer, &connection);
+        item
+    });
 
     Sse::new(Box::pin(stream) as Pin<Box<dyn Stream<Item = Result<Event, ApiError>> + Send>>)
         .keep_alive(KeepAlive::new().interval(Duration::from_secs(10)))
 }
 
+/// A stream with a single `error` event, for a `stream-verify` refused by a kill switch
+fn disabled_stream(switch: KillSwitch) -> impl Stream<Item = Result<Event, ApiError>> + Send {
+    let event = VerifyStreamEvent::error(
+        FEED_TEMPORARILY_DISABLED,
+        format!("{} is temporarily disabled", switch.as_str()),
+    );
+    futures::stream::once(async move { Ok(event.to_event()) })
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct UserVerifyInfoItem {
     pub user_id: i64,
@@ -1077,11 +1711,12 @@ Returns an array of `UserVerifyInfoItem` objects, each containing:
 - Resource usage tracking
 
 ## Note
-Only the authenticated user's entry will include the `user_info` field for privacy reasons.
+Requires the admin capability (`authz.admin_user_ids`): the entries expose every user's verification stats. Only the caller's own entry includes the `user_info` field.
 "#,
     responses(
         (status = 200, body = Vec<UserVerifyInfoItem>, description = "Successfully retrieved verification info for all users in the queue"),
         (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 403, description = "Admin capability required"),
         (status = 500, description = "Failed to retrieve verification information"),
     ),
     tag = FEED_TAG,
@@ -1,21 +1,63 @@
-use axum::Json;
-use axum::extract::State;
+use axum::extract::{Path, State};
+use axum::{Extension, Json};
 use common::{error::api_error::*, prelude::ApiCode};
 use conf::config::app_config;
 use feed::redis::update_task_manager::{
     TaskType, UpdateTaskData, UpdateTaskInput, UpdateTaskManager,
 };
-use seaorm_db::query::feed::user_interests::UserInterestsQuery;
-use serde::Deserialize;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use seaorm_db::{
+    entities::feed::user_interests::Model as UserInterest,
+    query::feed::user_interests::UserInterestsQuery,
+};
+use serde::{Deserialize, Serialize};
 use snafu::ResultExt;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::{
-    middlewares::auth::User, model::base::ApiResponse, routers::feed::FEED_TAG,
-    state::app_state::AppState,
+    consts::{CONFLICT, RESOURCE_NOT_FOUND},
+    middlewares::{
+        auth::User,
+        etag::{Conditional, IfNoneMatch},
+        log::RequestId,
+        query::Query,
+    },
+    model::{
+        audit::AuditAction,
+        base::ApiResponse,
+        preset::{PresetMerge, merge_preset},
+        suggestion::{InterestSuggestion, suggest_interests, suggestion_window},
+    },
+    query::user_interests::{self, InterestDetail},
+    routers::{audit::record_audit, feed::FEED_TAG},
+    state::{
+        app_state::AppState, pending_interests::PendingInterests, user_context::CachedUserContext,
+    },
 };
 
+#[derive(Debug, Default, Deserialize)]
+pub struct InterestsQuery {
+    #[serde(default)]
+    pub with_ids: bool,
+}
+
+/// An active interest with its id, as used by `DELETE /interests/{interest_id}`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct InterestItem {
+    pub id: i64,
+    pub interest: String,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+/// Plain strings by default, objects with `with_ids=true`
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum InterestList {
+    Simple(Vec<String>),
+    WithIds(Vec<InterestItem>),
+}
+
 #[utoipa::path(
     get,
     path = "/interests",
@@ -39,18 +81,38 @@ Example response:
 ]
 ```
 
+With `with_ids=true`, returns objects carrying the id needed by `DELETE /interests/{interest_id}`:
+```json
+[
+  { "id": 12, "interest": "machine learning", "created_at": "2026-10-16T08:00:00Z" }
+]
+```
+
 ## Use Cases
 - Display user's current interests
 - Edit interest list UI
 - Show what topics the user is tracking
 - Verification configuration
 
+## Caching
+The response carries an `ETag`. Send it back in `If-None-Match` to get `304 Not Modified` (no body) while the interests are unchanged.
+
 ## Related Endpoints
 - Use `POST /interests` to update the interest list
+- Use `POST /interests/one` and `DELETE /interests/{interest_id}` to add or remove a single interest
+- Use `GET /interests/detailed` for ids, embedding version and match counts
+- Use `GET /interests/suggestions` for terms drawn from the papers the user reads
 - Interests are used in paper verification via `/verify`
 "#,
+    params(
+        ("with_ids" = Option<bool>, Query, description = "Return `{ id, interest, created_at }` objects instead of plain strings"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag of a previous response"),
+    ),
     responses(
-        (status = 200, description = "Successfully retrieved user's interests as an array of strings", body = Vec<String>),
+        (status = 200, description = "Successfully retrieved user's interests, as strings or (with `with_ids=true`) objects", body = InterestList,
+            headers(("ETag" = String, description = "Hash of the response"))),
+        (status = 304, description = "Not modified since the ETag in `If-None-Match`",
+            headers(("ETag" = String, description = "Hash of the response"))),
         (status = 401, description = "Unauthorized - valid authentication required"),
         (status = 500, description = "Database error"),
     ),
@@ -59,18 +121,140 @@ Example response:
 pub async fn interests(
     State(state): State<AppState>,
     User(user): User,
-) -> Result<ApiResponse<Vec<String>>, ApiError> {
-    tracing::info!(user_id = user.id, "list interests");
+    Query(params): Query<InterestsQuery>,
+    if_none_match: IfNoneMatch,
+) -> Result<Conditional<ApiResponse<InterestList>>, ApiError> {
+    tracing::info!(
+        user_id = user.id,
+        with_ids = params.with_ids,
+        "list interests"
+    );
 
-    let items = UserInterestsQuery::list_by_user_id(&state.conn, user.id)
+    if_none_match
+        .respond(|| async {
+            let items = UserInterestsQuery::list_by_user_id(&state.conn, user.id)
+                .await
+                .context(DbErrSnafu {
+                    stage: "list-user-interests",
+                    code: ApiCode::COMMON_DATABASE_ERROR,
+                })?;
+
+            let interests = if params.with_ids {
+                InterestList::WithIds(
+                    items
+                        .into_iter()
+                        .map(|m| InterestItem {
+                            id: m.id,
+                            interest: m.interest,
+                            created_at: m.created_at,
+                        })
+                        .collect(),
+                )
+            } else {
+                InterestList::Simple(items.into_iter().map(|m| m.interest).collect())
+            };
+            Ok(ApiResponse::data(interests))
+        })
+        .await
+}
+
+#[utoipa::path(
+    get,
+    path = "/interests/detailed",
+    summary = "Get user's interests with ids and match counts",
+    description = r#"
+Return the active interests as objects, for building filters such as `user_interest_ids` of `GET /all-verified-papers`.
+
+## Returns
+One item per interest, oldest first:
+- `id`: the value to pass in `user_interest_ids` or to `DELETE /interests/{interest_id}`
+- `interest`: the interest text
+- `created_at`
+- `embedding_version`: LLM model the interest's embedding was generated with
+- `matched_paper_count`: distinct papers verified as a `Yes` match for the interest (`No` and `Partial` are not counted)
+
+`GET /interests` keeps returning plain strings.
+"#,
+    responses(
+        (status = 200, body = Vec<InterestDetail>, description = "The user's interests with their match counts"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 500, description = "Database error"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn interests_detailed(
+    State(state): State<AppState>,
+    User(user): User,
+) -> Result<ApiResponse<Vec<InterestDetail>>, ApiError> {
+    tracing::info!(user_id = user.id, "list detailed interests");
+    let items = user_interests::list_detailed(&state.conn, user.id)
         .await
         .context(DbErrSnafu {
-            stage: "list-user-interests",
+            stage: "list-detailed-interests",
             code: ApiCode::COMMON_DATABASE_ERROR,
         })?;
+    Ok(ApiResponse::data(items))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SuggestionsQuery {
+    pub limit: Option<usize>,
+    pub days: Option<u32>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/interests/suggestions",
+    summary = "Suggest interests from the user's reading",
+    description = r#"
+Suggest terms the user might want to add as interests, from what they read recently.
+
+## How
+- Papers of the last `days` days that the user read (`unread = false`) or that matched one of their interests (`Yes`), newest first, at most 500
+- Their `categories` and frequent title keywords are counted, once per paper
+- Terms overlapping an existing interest (one containing the other, case-insensitive) are left out
+- The `limit` terms with the most papers are returned; ties are sorted alphabetically
+
+Pure aggregation: no LLM call is made.
+
+## Query Parameters
+- `limit` (optional, default 10, at most 50)
+- `days` (optional, default 30, at most 365)
+"#,
+    params(
+        ("limit" = Option<usize>, Query, description = "Number of suggestions, 1..=50 (default 10)"),
+        ("days" = Option<u32>, Query, description = "Lookback window in days, 1..=365 (default 30)"),
+    ),
+    responses(
+        (status = 200, body = Vec<InterestSuggestion>, description = "Suggested terms, most supported first"),
+        (status = 400, description = "`limit` or `days` out of range"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 500, description = "Database error"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn interest_suggestions(
+    State(state): State<AppState>,
+    User(user): User,
+    Query(params): Query<SuggestionsQuery>,
+) -> Result<ApiResponse<Vec<InterestSuggestion>>, ApiError> {
+    let (limit, days) = suggestion_window(params.limit, params.days)?;
+    tracing::info!(user_id = user.id, limit, days, "suggest interests");
 
-    let interests = items.into_iter().map(|m| m.interest).collect();
-    Ok(ApiResponse::data(interests))
+    let papers = user_interests::recent_read_papers(&state.conn, user.id, days)
+        .await
+        .context(DbErrSnafu {
+            stage: "list-recent-read-papers",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+    let existing: Vec<String> = list_interests(&state, user.id)
+        .await?
+        .into_iter()
+        .map(|m| m.interest)
+        .collect();
+    Ok(ApiResponse::data(suggest_interests(
+        &papers, &existing, limit,
+    )))
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -263,6 +447,7 @@ System handles multiple rapid requests efficiently, only applies final state.
 
 ## Related Endpoints
 - **`GET /interests`**: Retrieve current active interests
+- **`POST /interests/one`**, **`DELETE /interests/{interest_id}`**: Add or remove a single interest
 - **`POST /verify`**: Trigger paper verification with updated interests
 - **`POST /stream-verify`**: Stream verification progress with live updates
 - **`GET /all-verified-papers`**: View papers matched to your interests
@@ -279,6 +464,7 @@ System handles multiple rapid requests efficiently, only applies final state.
 pub async fn set_interests(
     State(state): State<AppState>,
     User(user): User,
+    http_request_id: Option<Extension<RequestId>>,
     Json(payload): Json<SetInterestsRequest>,
 ) -> Result<ApiResponse<String>, ApiError> {
     tracing::info!(
@@ -287,14 +473,249 @@ pub async fn set_interests(
         "set interests (async)"
     );
 
+    // the interests an empty list clears, for the audit log
+    let cleared = if payload.interests.is_empty() {
+        let mut ids: Vec<i64> = CachedUserContext::new(&state)
+            .bypass_cache(true)
+            .interests(user.id)
+            .await
+            .map(|interests| interests.into_keys().collect())
+            .unwrap_or_default();
+        ids.sort_unstable();
+        Some(ids)
+    } else {
+        None
+    };
+
+    let ((), request_id) =
+        change_interests(&state, user.id, |_| Ok(((), Some(payload.interests)))).await?;
+    let request_id = request_id.expect("a replacement is always queued");
+    if let Some(cleared) = cleared {
+        record_audit(
+            &state,
+            &user,
+            http_request_id.as_deref(),
+            AuditAction::InterestsClear,
+            &cleared,
+            serde_json::json!({ "update_request_id": request_id }),
+        )
+        .await;
+    }
+
+    // Return request_id immediately (do not wait for database operation)
+    Ok(ApiResponse::data(request_id))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AddInterestRequest {
+    pub interest: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum InterestChangeStatus {
+    Added,
+    Removed,
+    /// The interest was already there; nothing was queued
+    Unchanged,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct InterestChange {
+    pub status: InterestChangeStatus,
+    /// Id of the queued update, absent when `unchanged`
+    pub request_id: Option<String>,
+    /// The user's interests once the update is applied
+    pub interests: Vec<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/interests/one",
+    summary = "Add one interest",
+    description = r#"
+Add a single interest without resubmitting the whole list.
+
+## Behavior
+- The interest is trimmed and compared case-insensitively with the current ones; when it is already there nothing is queued and `status` is `unchanged`
+- Otherwise the current interests plus the new one are queued through the same update path as `POST /interests`, so a previously soft-deleted identical interest is restored rather than duplicated, and the embedding is generated as usual
+- The count stays capped by `rss.max_prompt_number`: adding to a full list is refused with 409
+
+## Note
+Like `POST /interests`, the update is applied ~500ms later. Changes of one user are queued one at a time, each starting from the list queued before it, so quick successive adds and removals are all kept.
+"#,
+    request_body = AddInterestRequest,
+    responses(
+        (status = 200, body = InterestChange, description = "Interest added, or already present"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 409, description = "The user already has `rss.max_prompt_number` interests, or another change is still being queued"),
+        (status = 500, description = "Blank interest, database error or failed to queue the update"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn add_interest(
+    State(state): State<AppState>,
+    User(user): User,
+    Json(payload): Json<AddInterestRequest>,
+) -> Result<ApiResponse<InterestChange>, ApiError> {
+    let interest = payload.interest.trim().to_string();
+    if interest.is_empty() {
+        return Err(ApiError::CustomError {
+            message: "interest must not be empty".to_string(),
+            code: ApiCode::COMMON_FEED_ERROR,
+        });
+    }
+    tracing::info!(user_id = user.id, "add interest");
+
+    let max_count = state.config.rss.max_prompt_number;
+    let ((status, interests), request_id) = change_interests(&state, user.id, |existing| {
+        match merge_preset(&existing, std::slice::from_ref(&interest), max_count) {
+            PresetMerge::Unchanged => Ok(((InterestChangeStatus::Unchanged, existing), None)),
+            PresetMerge::Merged { interests, .. } => Ok((
+                (InterestChangeStatus::Added, interests.clone()),
+                Some(interests),
+            )),
+            PresetMerge::Conflict { .. } => Err(ApiError::CustomError {
+                message: format!(
+                    "Exceeded maximum interests limit: {max_count}, remove an interest first"
+                ),
+                code: CONFLICT,
+            }),
+        }
+    })
+    .await?;
+    Ok(ApiResponse::data(InterestChange {
+        status,
+        request_id,
+        interests,
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/interests/{interest_id}",
+    summary = "Remove one interest",
+    description = r#"
+Soft-delete a single interest by id (see `GET /interests?with_ids=true`), leaving the others untouched.
+
+The remaining interests are queued through the same update path as `POST /interests`, so the removed one can be restored later by adding it again. Like `POST /interests/one`, the removal starts from the list queued before it, so it keeps an interest added just before.
+"#,
+    params(("interest_id" = i64, Path, description = "Interest id")),
+    responses(
+        (status = 200, body = InterestChange, description = "Removal queued"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 404, description = "No active interest with this id for the user, or its removal is already queued"),
+        (status = 409, description = "Another change of the user's interests is still being queued"),
+        (status = 500, description = "Database error or failed to queue the update"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn remove_interest(
+    State(state): State<AppState>,
+    User(user): User,
+    Path(interest_id): Path<i64>,
+) -> Result<ApiResponse<InterestChange>, ApiError> {
+    let not_found = || ApiError::CustomError {
+        message: format!("interest {interest_id} not found"),
+        code: RESOURCE_NOT_FOUND,
+    };
+    let removed = list_interests(&state, user.id)
+        .await?
+        .into_iter()
+        .find(|m| m.id == interest_id)
+        .ok_or_else(not_found)?
+        .interest
+        .trim()
+        .to_lowercase();
+    tracing::info!(user_id = user.id, interest_id, "remove interest");
+
+    let (interests, request_id) = change_interests(&state, user.id, |existing| {
+        let interests: Vec<String> = existing
+            .iter()
+            .filter(|interest| interest.trim().to_lowercase() != removed)
+            .cloned()
+            .collect();
+        if interests.len() == existing.len() {
+            return Err(not_found());
+        }
+        Ok((interests.clone(), Some(interests)))
+    })
+    .await?;
+    Ok(ApiResponse::data(InterestChange {
+        status: InterestChangeStatus::Removed,
+        request_id,
+        interests,
+    }))
+}
+
+async fn list_interests(state: &AppState, user_id: i64) -> Result<Vec<UserInterest>, ApiError> {
+    UserInterestsQuery::list_by_user_id(&state.conn, user_id)
+        .await
+        .context(DbErrSnafu {
+            stage: "list-user-interests",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })
+}
+
+/// Apply `change` to the user's latest interests and queue the list it returns (`None`:
+/// nothing to queue). Returns the outcome of `change` and the update's request id.
+///
+/// Shared by every endpoint changing interests. Changes of one user run one at a time and
+/// start from the list queued last while it may be unapplied (see `PendingInterests`), else
+/// from the stored interests, so no change is lost to the latest-wins update of another.
+pub(crate) async fn change_interests<R>(
+    state: &AppState,
+    user_id: i64,
+    change: impl FnOnce(Vec<String>) -> Result<(R, Option<Vec<String>>), ApiError>,
+) -> Result<(R, Option<String>), ApiError> {
+    let pending = PendingInterests::new(state);
+    let token = pending.lock(user_id).await?;
+    let result = apply_change(state, &pending, user_id, change).await;
+    pending.unlock(user_id, &token).await;
+    result
+}
+
+async fn apply_change<R>(
+    state: &AppState,
+    pending: &PendingInterests<'_>,
+    user_id: i64,
+    change: impl FnOnce(Vec<String>) -> Result<(R, Option<Vec<String>>), ApiError>,
+) -> Result<(R, Option<String>), ApiError> {
+    let current = match pending.get(user_id).await? {
+        Some(interests) => interests,
+        None => list_interests(state, user_id)
+            .await?
+            .into_iter()
+            .map(|m| m.interest)
+            .collect(),
+    };
+    let (outcome, interests) = change(current)?;
+    let Some(interests) = interests else {
+        return Ok((outcome, None));
+    };
+    let request_id = submit_interests_update(state, user_id, interests.clone()).await?;
+    if let Err(e) = pending.set(user_id, &interests).await {
+        tracing::warn!(user_id, error = ?e, "failed to record the queued interests");
+    }
+    Ok((outcome, Some(request_id)))
+}
+
+/// Validate and queue a full replacement of the user's interests on the `UpdateTaskManager`,
+/// so limits, metadata generation and cache invalidation are the same everywhere. Returns the
+/// update's request id.
+async fn submit_interests_update(
+    state: &AppState,
+    user_id: i64,
+    interests: Vec<String>,
+) -> Result<String, ApiError> {
     // Validate max interests limit
     let max_count = state.config.rss.max_prompt_number;
-    if payload.interests.len() > max_count {
+    if interests.len() > max_count {
         return Err(ApiError::CustomError {
             message: format!(
                 "Exceeded maximum interests limit: {} (provided: {})",
                 max_count,
-                payload.interests.len()
+                interests.len()
             ),
             code: ApiCode::COMMON_FEED_ERROR,
         });
@@ -313,13 +734,14 @@ pub async fn set_interests(
         state.config.rss.update_task_merge_delay_ms.unwrap_or(500),
     );
 
+    CachedUserContext::new(state).begin_update(user_id).await;
     let request_id = manager
         .submit_update(
             UpdateTaskInput {
                 task_type: TaskType::UserInterests,
-                user_id: user.id,
+                user_id,
                 data: UpdateTaskData::UserInterests {
-                    interests: payload.interests,
+                    interests,
                     version: config.llm.model.clone(),
                 },
                 request_id: Uuid::new_v4().to_string(),
@@ -333,11 +755,10 @@ pub async fn set_interests(
         })?;
 
     tracing::info!(
-        user_id = user.id,
+        user_id,
         request_id = %request_id,
         "Successfully queued user interests update"
     );
 
-    // Return request_id immediately (do not wait for database operation)
-    Ok(ApiResponse::data(request_id))
+    Ok(request_id)
 }
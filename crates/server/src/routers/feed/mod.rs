@@ -5,31 +5,62 @@ use crate::state::app_state::AppState;
 pub mod feeds;
 pub mod interests;
 pub mod paper;
+pub mod presets;
 pub mod rss;
+pub mod schedule;
 pub mod subscriptions;
+pub mod verify_stats;
 
 pub(crate) const FEED_TAG: &str = "feed";
 
 pub fn feed_routers() -> OpenApiRouter<AppState> {
     OpenApiRouter::new()
         .routes(routes!(rss::rss))
+        .routes(routes!(rss::rss_channels))
         .routes(routes!(rss::user_rss))
         .routes(routes!(rss::rss_detail))
         .routes(routes!(rss::rss_create))
+        .routes(routes!(rss::rss_batch_create))
+        .routes(routes!(rss::rss_update))
         .routes(routes!(rss::rss_delete))
         .routes(routes!(subscriptions::subscriptions))
         .routes(routes!(subscriptions::batch_subscriptions))
         .routes(routes!(subscriptions::subscriptions_create_one))
         .routes(routes!(subscriptions::subscriptions_delete_one))
+        .routes(routes!(subscriptions::subscription_move_folder))
         .routes(routes!(interests::interests))
         .routes(routes!(interests::set_interests))
+        .routes(routes!(interests::interests_detailed))
+        .routes(routes!(interests::interest_suggestions))
+        .routes(routes!(interests::add_interest))
+        .routes(routes!(interests::remove_interest))
+        .routes(routes!(presets::interest_presets))
+        .routes(routes!(presets::apply_interest_preset))
+        .routes(routes!(presets::create_interest_preset))
+        .routes(routes!(
+            presets::get_interest_preset,
+            presets::update_interest_preset,
+            presets::delete_interest_preset
+        ))
         .routes(routes!(feeds::verify))
         .routes(routes!(feeds::all_verified_papers))
         .routes(routes!(feeds::papers_make_read))
+        .routes(routes!(feeds::papers_make_read_undo))
+        .routes(routes!(feeds::papers_make_unread))
         .routes(routes!(feeds::unverified_count_info))
         .routes(routes!(feeds::unread_count))
+        .routes(routes!(feeds::starred_count))
         .routes(routes!(feeds::batch_delete))
         .routes(routes!(feeds::stream_verify))
         .routes(routes!(feeds::all_users_verify_info))
         .routes(routes!(paper::unverified_papers))
+        .routes(routes!(paper::paper_detail))
+        .routes(routes!(paper::put_paper_note, paper::delete_paper_note))
+        .routes(routes!(paper::star_paper, paper::unstar_paper))
+        .routes(routes!(verify_stats::match_rate))
+        .routes(routes!(verify_stats::verify_stats))
+        .routes(routes!(
+            schedule::get_verify_schedule,
+            schedule::put_verify_schedule
+        ))
 }
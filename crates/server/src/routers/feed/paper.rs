@@ -1,18 +1,31 @@
 use super::FEED_TAG;
 use crate::{
-    middlewares::auth::User,
-    model::{base::ApiResponse, page::Pagination},
-    state::app_state::AppState,
+    consts::RESOURCE_NOT_FOUND,
+    middlewares::{auth::User, query::Query},
+    model::note::{PaperNote, PaperNoteInput},
+    model::{
+        base::ApiResponse,
+        filter::{AppliedFilters, normalize_text},
+        page::Pagination,
+    },
+    query::{
+        paper_detail::{PaperDetail, get_paper_detail},
+        paper_notes, paper_stars,
+    },
+    state::{app_state::AppState, user_context::CachedUserContext},
 };
-use axum::extract::{Query, State};
+use axum::Json;
+use axum::extract::{Path, State};
 use common::{error::api_error::*, prelude::ApiCode};
 use seaorm_db::entities::feed::user_paper_verifications::VerificationMatch;
+use seaorm_db::entities::feed::{rss_papers, rss_sources, user_paper_verifications};
 use seaorm_db::query::feed::{
     rss_papers::RssPaperDataWithDetail,
     user_paper_verifications::{ListUnverifiedParams, UserPaperVerificationsQuery},
 };
 use serde::{Deserialize, Serialize};
 use snafu::ResultExt;
+use std::collections::HashMap;
 use utoipa::ToSchema;
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -36,6 +49,20 @@ fn default_verification_match() -> Option<VerificationMatch> {
 pub struct UnverifiedPapersResponse {
     pub pagination: Pagination,
     pub papers: Vec<RssPaperDataWithDetail>,
+    /// Normalized filters used for the query
+    pub applied_filters: AppliedFilters,
+}
+
+impl PapersRequest {
+    /// Normalize the raw query parameters into the filters actually passed to the query.
+    /// `rss_source_id` is not supported by the unverified listing and is never echoed.
+    pub fn applied_filters(&self) -> AppliedFilters {
+        AppliedFilters {
+            channel: normalize_text(self.channel.as_deref()),
+            keyword: normalize_text(self.keyword.as_deref()),
+            ..Default::default()
+        }
+    }
 }
 
 #[utoipa::path(
@@ -79,19 +106,27 @@ Returns an `UnverifiedPapersResponse` object containing:
   "page": 1,
   "page_size": 20,
   "total": 156,
-  "total_pages": 8
+  "total_pages": 8,
+  "has_next": true,
+  "has_prev": false
 }
 ```
+`total_pages` is rounded up; `has_next`/`has_prev` are computed server-side.
 When no pagination params are provided, pagination info reflects the complete dataset:
 ```json
 {
   "page": 1,
   "page_size": 156,  // Total count
   "total": 156,
-  "total_pages": 1
+  "total_pages": 1,
+  "has_next": false,
+  "has_prev": false
 }
 ```
 
+### Applied Filters
+`applied_filters` echoes the normalized `channel` and `keyword` actually used for the query (trimmed, blank values omitted). `rss_source_id` is accepted but not applied by this endpoint, so it never appears here.
+
 ### Papers Array
 Array of `RssPaperDataWithDetail` objects, each containing:
 - **Paper Core Fields**: id, title, link, description, author, pub_date
@@ -168,8 +203,11 @@ Returns first 100 arxiv papers containing "machine learning".
       "page": 1,
       "page_size": 20,
       "total": 156,
-      "total_pages": 8
+      "total_pages": 8,
+      "has_next": true,
+      "has_prev": false
     },
+    "applied_filters": {},
     "papers": [
       {
         "id": 12345,
@@ -246,14 +284,16 @@ pub async fn unverified_papers(
         (None, None)
     };
 
+    let applied_filters = payload.applied_filters();
+
     let unverified_result = UserPaperVerificationsQuery::list_unverified_papers(
-        &state.conn,
+        state.read_conn_for(user.id).await,
         user.id,
         ListUnverifiedParams {
             offset,
             limit,
-            channel: payload.channel.clone(),
-            keyword: payload.keyword.clone(),
+            channel: applied_filters.channel.clone(),
+            keyword: applied_filters.keyword.clone(),
         },
     )
     .await
@@ -266,30 +306,237 @@ pub async fn unverified_papers(
 
     // Set response based on whether pagination is used
     let pagination = if use_pagination {
-        let page = payload.page.unwrap_or(1);
-        let page_size = payload.page_size.unwrap_or(20);
-        Pagination {
-            page,
-            page_size,
+        Pagination::new(
+            payload.page.unwrap_or(1),
+            payload.page_size.unwrap_or(20),
             total,
-            total_pages: if page_size > 0 {
-                total / page_size as u64
-            } else {
-                0
-            },
-        }
+        )
     } else {
         // When not using pagination, return pagination info for all data
-        Pagination {
-            page: 1,
-            page_size: total as i32,
-            total,
-            total_pages: 1,
-        }
+        Pagination::all(total)
     };
 
     Ok(ApiResponse::data(UnverifiedPapersResponse {
         pagination,
         papers: rss_papers,
+        applied_filters,
+    }))
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PaperDetailResponse {
+    pub paper: rss_papers::Model,
+    /// The user's verifications of the paper, oldest first (empty when not verified yet)
+    pub verifications: Vec<user_paper_verifications::Model>,
+    /// The user's interests, by id, to resolve the verifications' interest ids
+    pub interest_map: HashMap<i64, String>,
+    /// The paper's RSS source
+    pub source: Option<rss_sources::Model>,
+    /// The user's note on the paper
+    pub note: Option<PaperNote>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/papers/{paper_id}",
+    summary = "Get a paper with its verifications",
+    description = r#"
+Fetch a single paper for a detail view, instead of paging through `/all-verified-papers`.
+
+A paper is visible when it comes from a source the user subscribes to, or when the user has a verification of it (verifications are kept after unsubscribing). Any other paper, existing or not, returns 404.
+
+## Returns
+- `paper`: the paper itself
+- `verifications`: the user's verifications of it against each interest, oldest first
+- `interest_map`: the user's interests by id, as in `/all-verified-papers`
+- `source`: the paper's RSS source
+- `note`: the user's note on the paper (see `PUT /papers/{paper_id}/note`), or `null`
+"#,
+    params(("paper_id" = i32, Path, description = "Paper id")),
+    responses(
+        (status = 200, body = PaperDetailResponse, description = "The paper with the user's verifications"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 404, description = "Paper not found or not visible to the user"),
+        (status = 500, description = "Database error"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn paper_detail(
+    State(state): State<AppState>,
+    User(user): User,
+    Path(paper_id): Path<i32>,
+) -> Result<ApiResponse<PaperDetailResponse>, ApiError> {
+    tracing::info!(user_id = user.id, paper_id, "get paper detail");
+
+    let interest_map = CachedUserContext::new(&state).interests(user.id).await?;
+    let detail = visible_paper(&state, user.id, paper_id).await?;
+    let note = paper_notes::get(&state.conn, user.id, paper_id)
+        .await
+        .context(DbErrSnafu {
+            stage: "get-paper-note",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+
+    Ok(ApiResponse::data(PaperDetailResponse {
+        paper: detail.paper,
+        verifications: detail.verifications,
+        interest_map,
+        source: detail.source,
+        note,
     }))
 }
+
+/// `paper_id` if it is visible to the user, as defined by `GET /papers/{paper_id}`; 404 otherwise
+async fn visible_paper(
+    state: &AppState,
+    user_id: i64,
+    paper_id: i32,
+) -> Result<PaperDetail, ApiError> {
+    let source_ids = CachedUserContext::new(state).subscriptions(user_id).await?;
+    get_paper_detail(&state.conn, user_id, paper_id, &source_ids)
+        .await
+        .context(DbErrSnafu {
+            stage: "get-paper-detail",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?
+        .ok_or_else(|| ApiError::CustomError {
+            message: format!("paper {paper_id} not found"),
+            code: RESOURCE_NOT_FOUND,
+        })
+}
+
+#[utoipa::path(
+    put,
+    path = "/papers/{paper_id}/note",
+    summary = "Set the user's note on a paper",
+    description = r#"
+Attach a short note to a paper ("re-read for related work section"), replacing any previous one.
+
+- The note is trimmed; it must not be blank and is limited to 4096 bytes
+- The paper must be visible to the user, as for `GET /papers/{paper_id}`
+- The note shows up in `GET /papers/{paper_id}` and in the `note_map` of `/all-verified-papers`
+"#,
+    params(("paper_id" = i32, Path, description = "Paper id")),
+    request_body = PaperNoteInput,
+    responses(
+        (status = 200, body = PaperNote, description = "The saved note"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 404, description = "Paper not found or not visible to the user"),
+        (status = 500, description = "Blank or too long note, or database error"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn put_paper_note(
+    State(state): State<AppState>,
+    User(user): User,
+    Path(paper_id): Path<i32>,
+    Json(payload): Json<PaperNoteInput>,
+) -> Result<ApiResponse<PaperNote>, ApiError> {
+    let note = payload.validate()?;
+    visible_paper(&state, user.id, paper_id).await?;
+    tracing::info!(user_id = user.id, paper_id, "set paper note");
+
+    let note = paper_notes::upsert(&state.conn, user.id, paper_id, &note)
+        .await
+        .context(DbErrSnafu {
+            stage: "set-paper-note",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+    Ok(ApiResponse::data(note))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/papers/{paper_id}/note",
+    summary = "Delete the user's note on a paper",
+    description = r#"
+Remove the user's note on a paper. Returns `false` when there was no note.
+"#,
+    params(("paper_id" = i32, Path, description = "Paper id")),
+    responses(
+        (status = 200, body = bool, description = "Whether a note was deleted"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 500, description = "Database error"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn delete_paper_note(
+    State(state): State<AppState>,
+    User(user): User,
+    Path(paper_id): Path<i32>,
+) -> Result<ApiResponse<bool>, ApiError> {
+    tracing::info!(user_id = user.id, paper_id, "delete paper note");
+    let deleted = paper_notes::delete(&state.conn, user.id, paper_id)
+        .await
+        .context(DbErrSnafu {
+            stage: "delete-paper-note",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+    Ok(ApiResponse::data(deleted))
+}
+
+#[utoipa::path(
+    post,
+    path = "/papers/{paper_id}/star",
+    summary = "Star a paper",
+    description = r#"
+Add a paper to the user's shortlist, independently of its read state.
+
+- Idempotent: starring a starred paper returns `false` and is not an error
+- The paper must be visible to the user, as for `GET /papers/{paper_id}`
+- Starred papers are skipped by `POST /batch-delete` unless it is called with `force: true`
+"#,
+    params(("paper_id" = i32, Path, description = "Paper id")),
+    responses(
+        (status = 200, body = bool, description = "Whether the paper was newly starred"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 404, description = "Paper not found or not visible to the user"),
+        (status = 500, description = "Database error"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn star_paper(
+    State(state): State<AppState>,
+    User(user): User,
+    Path(paper_id): Path<i32>,
+) -> Result<ApiResponse<bool>, ApiError> {
+    visible_paper(&state, user.id, paper_id).await?;
+    tracing::info!(user_id = user.id, paper_id, "star paper");
+    let starred = paper_stars::star(&state.conn, user.id, paper_id)
+        .await
+        .context(DbErrSnafu {
+            stage: "star-paper",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+    Ok(ApiResponse::data(starred))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/papers/{paper_id}/star",
+    summary = "Unstar a paper",
+    description = r#"
+Remove a paper from the user's shortlist. Idempotent: returns `false` when the paper was not starred.
+"#,
+    params(("paper_id" = i32, Path, description = "Paper id")),
+    responses(
+        (status = 200, body = bool, description = "Whether a star was removed"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 500, description = "Database error"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn unstar_paper(
+    State(state): State<AppState>,
+    User(user): User,
+    Path(paper_id): Path<i32>,
+) -> Result<ApiResponse<bool>, ApiError> {
+    tracing::info!(user_id = user.id, paper_id, "unstar paper");
+    let removed = paper_stars::unstar(&state.conn, user.id, &[paper_id])
+        .await
+        .context(DbErrSnafu {
+            stage: "unstar-paper",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+    Ok(ApiResponse::data(removed > 0))
+}
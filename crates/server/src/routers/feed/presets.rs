@@ -0,0 +1,341 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use common::{error::api_error::*, prelude::ApiCode};
+use sea_orm::{DbErr, SqlErr};
+use serde::Serialize;
+use snafu::ResultExt;
+use utoipa::ToSchema;
+
+use super::{FEED_TAG, interests::change_interests};
+use crate::{
+    consts::{CONFLICT, RESOURCE_NOT_FOUND},
+    middlewares::auth::User,
+    model::{
+        base::ApiResponse,
+        preset::{InterestPreset, InterestPresetInput, PresetMerge, merge_preset},
+    },
+    query::interest_presets,
+    state::app_state::AppState,
+};
+
+/// Map a unique-name violation to 409, any other database error as usual
+fn check_unique<T>(result: Result<T, DbErr>, stage: &'static str) -> Result<T, ApiError> {
+    match result {
+        Err(e) if matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) => {
+            Err(ApiError::CustomError {
+                message: "an interest preset with this name already exists".to_string(),
+                code: CONFLICT,
+            })
+        }
+        result => result.context(DbErrSnafu {
+            stage,
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        }),
+    }
+}
+
+fn preset_not_found(id: i64) -> ApiError {
+    ApiError::CustomError {
+        message: format!("interest preset {id} not found"),
+        code: RESOURCE_NOT_FOUND,
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/interest-presets",
+    summary = "List interest presets",
+    description = r#"
+List the curated interest presets (e.g. "LLM researcher starter pack") a user can apply in one click with `POST /interest-presets/{id}/apply`.
+"#,
+    responses(
+        (status = 200, body = Vec<InterestPreset>, description = "All presets, oldest first"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 500, description = "Database error"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn interest_presets(
+    State(state): State<AppState>,
+) -> Result<ApiResponse<Vec<InterestPreset>>, ApiError> {
+    let presets = interest_presets::list(&state.conn)
+        .await
+        .context(DbErrSnafu {
+            stage: "list-interest-presets",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+    Ok(ApiResponse::data(presets))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApplyPresetStatus {
+    /// The missing interests were queued for addition
+    Applied,
+    /// The user already has every interest of the preset
+    Unchanged,
+    /// Applying would exceed `rss.max_prompt_number`; nothing was changed
+    Conflict,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApplyPresetResponse {
+    pub status: ApplyPresetStatus,
+    /// Request id of the queued interests update (`applied` only)
+    pub request_id: Option<String>,
+    /// Interests the preset adds
+    pub added: Vec<String>,
+    /// The user's interests after applying (before, when nothing changed)
+    pub interests: Vec<String>,
+    /// `conflict`: how many existing interests have to be removed first
+    pub must_remove: Option<usize>,
+    /// `conflict`: existing interests that are not part of the preset
+    pub removable: Option<Vec<String>>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/interest-presets/{id}/apply",
+    summary = "Apply an interest preset",
+    description = r#"
+Merge a preset's interests into the user's interests. Existing interests are kept; only the missing ones are added (compared case-insensitively), through the same update path as `POST /interests`.
+
+## Responses
+- `applied`: the merged list was queued; `request_id` tracks the update
+- `unchanged`: every preset interest is already there (applying twice is a no-op)
+- `conflict` (HTTP 409): the merge would exceed `rss.max_prompt_number`; `must_remove` of the `removable` interests have to be removed first
+"#,
+    params(("id" = i64, Path, description = "Preset id")),
+    responses(
+        (status = 200, body = ApplyPresetResponse, description = "Preset applied, or already applied"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 404, description = "Preset not found"),
+        (status = 409, body = ApplyPresetResponse, description = "Applying would exceed the interest limit"),
+        (status = 500, description = "Database error or failed to queue the update"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn apply_interest_preset(
+    State(state): State<AppState>,
+    User(user): User,
+    Path(id): Path<i64>,
+) -> Result<(StatusCode, ApiResponse<ApplyPresetResponse>), ApiError> {
+    let preset = interest_presets::get(&state.conn, id)
+        .await
+        .context(DbErrSnafu {
+            stage: "get-interest-preset",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?
+        .ok_or_else(|| preset_not_found(id))?;
+    tracing::info!(user_id = user.id, preset_id = id, "apply interest preset");
+
+    let max_count = state.config.rss.max_prompt_number;
+    let (response, request_id) = change_interests(&state, user.id, |existing| {
+        Ok(
+            match merge_preset(&existing, &preset.interests, max_count) {
+                PresetMerge::Unchanged => (
+                    ApplyPresetResponse {
+                        status: ApplyPresetStatus::Unchanged,
+                        request_id: None,
+                        added: Vec::new(),
+                        interests: existing,
+                        must_remove: None,
+                        removable: None,
+                    },
+                    None,
+                ),
+                PresetMerge::Merged { interests, added } => (
+                    ApplyPresetResponse {
+                        status: ApplyPresetStatus::Applied,
+                        request_id: None,
+                        added,
+                        interests: interests.clone(),
+                        must_remove: None,
+                        removable: None,
+                    },
+                    Some(interests),
+                ),
+                PresetMerge::Conflict {
+                    must_remove,
+                    removable,
+                } => (
+                    ApplyPresetResponse {
+                        status: ApplyPresetStatus::Conflict,
+                        request_id: None,
+                        added: Vec::new(),
+                        interests: existing,
+                        must_remove: Some(must_remove),
+                        removable: Some(removable),
+                    },
+                    None,
+                ),
+            },
+        )
+    })
+    .await?;
+    if let Some(must_remove) = response.must_remove {
+        return Ok((
+            StatusCode::CONFLICT,
+            ApiResponse {
+                message: format!(
+                    "Applying this preset exceeds the maximum interests limit: remove {must_remove} interest(s) first"
+                ),
+                data: response,
+                success: false,
+            },
+        ));
+    }
+    let response = ApplyPresetResponse {
+        request_id,
+        ..response
+    };
+    Ok((StatusCode::OK, ApiResponse::data(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/interest-presets/{id}",
+    summary = "Get an interest preset",
+    params(("id" = i64, Path, description = "Preset id")),
+    responses(
+        (status = 200, body = InterestPreset, description = "The preset"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 403, description = "Admin capability required"),
+        (status = 404, description = "Preset not found"),
+    ),
+    tag = "Admin",
+)]
+pub async fn get_interest_preset(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<ApiResponse<InterestPreset>, ApiError> {
+    let preset = interest_presets::get(&state.conn, id)
+        .await
+        .context(DbErrSnafu {
+            stage: "get-interest-preset",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?
+        .ok_or_else(|| preset_not_found(id))?;
+    Ok(ApiResponse::data(preset))
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/interest-presets",
+    summary = "Create an interest preset",
+    description = r#"
+Create a curated preset. Interests are trimmed and deduplicated (case-insensitively, keeping order); a preset must have between 1 and `rss.max_prompt_number` interests and a unique name.
+
+```json
+{
+  "name": "LLM researcher starter pack",
+  "description": "Core topics for LLM research",
+  "interests": ["large language models", "instruction tuning", "RLHF"],
+  "channel_hints": ["arxiv"]
+}
+```
+"#,
+    request_body = InterestPresetInput,
+    responses(
+        (status = 200, body = InterestPreset, description = "The created preset"),
+        (status = 400, description = "Invalid preset"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 403, description = "Admin capability required"),
+        (status = 409, description = "A preset with this name already exists"),
+    ),
+    tag = "Admin",
+)]
+pub async fn create_interest_preset(
+    State(state): State<AppState>,
+    User(user): User,
+    Json(payload): Json<InterestPresetInput>,
+) -> Result<ApiResponse<InterestPreset>, ApiError> {
+    let input = payload.validate(state.config.rss.max_prompt_number)?;
+    let preset = check_unique(
+        interest_presets::insert(&state.conn, &input).await,
+        "create-interest-preset",
+    )?;
+    tracing::info!(
+        admin_id = user.id,
+        preset_id = preset.id,
+        "interest preset created"
+    );
+    Ok(ApiResponse::data(preset))
+}
+
+#[utoipa::path(
+    put,
+    path = "/admin/interest-presets/{id}",
+    summary = "Replace an interest preset",
+    description = r#"
+Replace every field of a preset; same validation as creation. Users who already applied it keep their interests.
+"#,
+    params(("id" = i64, Path, description = "Preset id")),
+    request_body = InterestPresetInput,
+    responses(
+        (status = 200, body = InterestPreset, description = "The updated preset"),
+        (status = 400, description = "Invalid preset"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 403, description = "Admin capability required"),
+        (status = 404, description = "Preset not found"),
+        (status = 409, description = "A preset with this name already exists"),
+    ),
+    tag = "Admin",
+)]
+pub async fn update_interest_preset(
+    State(state): State<AppState>,
+    User(user): User,
+    Path(id): Path<i64>,
+    Json(payload): Json<InterestPresetInput>,
+) -> Result<ApiResponse<InterestPreset>, ApiError> {
+    let input = payload.validate(state.config.rss.max_prompt_number)?;
+    let preset = check_unique(
+        interest_presets::update(&state.conn, id, &input).await,
+        "update-interest-preset",
+    )?
+    .ok_or_else(|| preset_not_found(id))?;
+    tracing::info!(
+        admin_id = user.id,
+        preset_id = id,
+        "interest preset updated"
+    );
+    Ok(ApiResponse::data(preset))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/admin/interest-presets/{id}",
+    summary = "Delete an interest preset",
+    params(("id" = i64, Path, description = "Preset id")),
+    responses(
+        (status = 200, body = bool, description = "Preset deleted, returns true"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 403, description = "Admin capability required"),
+        (status = 404, description = "Preset not found"),
+    ),
+    tag = "Admin",
+)]
+pub async fn delete_interest_preset(
+    State(state): State<AppState>,
+    User(user): User,
+    Path(id): Path<i64>,
+) -> Result<ApiResponse<bool>, ApiError> {
+    let deleted = interest_presets::delete(&state.conn, id)
+        .await
+        .context(DbErrSnafu {
+            stage: "delete-interest-preset",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+    if !deleted {
+        return Err(preset_not_found(id));
+    }
+    tracing::info!(
+        admin_id = user.id,
+        preset_id = id,
+        "interest preset deleted"
+    );
+    Ok(ApiResponse::data(true))
+}
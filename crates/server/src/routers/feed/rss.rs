@@ -1,8 +1,11 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
-use axum::Json;
 use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::{Extension, Json};
 use common::{error::api_error::*, prelude::ApiCode};
+use futures::StreamExt;
+use sea_orm::{EntityTrait, prelude::DateTimeWithTimeZone};
 use seaorm_db::{
     entities::feed::rss_sources,
     query::feed::{
@@ -14,7 +17,33 @@ use serde::{Deserialize, Serialize};
 use snafu::ResultExt;
 use utoipa::ToSchema;
 
-use crate::{middlewares::auth::User, model::base::ApiResponse, state::app_state::AppState};
+use crate::{
+    config::server_rss_config,
+    consts::{CONFLICT, INVALID_FEED_URL, RESOURCE_NOT_FOUND, RSS_BATCH_TOO_LARGE},
+    middlewares::{
+        auth::User,
+        authz::{Caller, Capability},
+        etag::{Conditional, IfNoneMatch},
+        log::RequestId,
+        query::Query,
+    },
+    model::{
+        audit::AuditAction,
+        base::ApiResponse,
+        feed_url::{FeedFetchLimits, normalize_feed_url, validate_feed_url},
+        filter::normalize_text,
+        page::{Page, Pagination},
+    },
+    query::{
+        rss_sources::{
+            InsertOutcome, NewRssSource, RssSourcePatch, SourceStats, find_by_url, insert_many,
+            list_channels, stats_by_ids, subscriber_ids, update_by_id,
+        },
+        subscription_folders,
+    },
+    routers::audit::record_audit,
+    state::{app_state::AppState, user_context::CachedUserContext, version::VersionCounter},
+};
 
 use super::FEED_TAG;
 
@@ -37,6 +66,40 @@ pub struct RssTreeVec {
     #[schema(no_recursion)]
     pub children: Vec<RssTreeVec>,
     pub data: Option<rss_sources::Model>,
+    /// With `with_stats=true`: papers of the source, summed over a branch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paper_count: Option<i64>,
+    /// With `with_stats=true`: subscribers of the source, summed over a branch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subscriber_count: Option<i64>,
+    /// With `with_stats=true`: last fetch of the source, the latest of a branch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_fetched_at: Option<DateTimeWithTimeZone>,
+    /// With `include_subscription_state=true`, on leaves: the user subscribes to the source
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subscribed: Option<bool>,
+    /// With `include_subscription_state=true`, on subscribed leaves: the subscription's id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subscription_id: Option<i64>,
+    /// With `include_subscription_state=true`, on branches: subscribed sources below the node
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subscribed_count: Option<i64>,
+}
+
+impl RssTreeVec {
+    fn node(name: String, children: Vec<RssTreeVec>, data: Option<rss_sources::Model>) -> Self {
+        RssTreeVec {
+            name,
+            children,
+            data,
+            paper_count: None,
+            subscriber_count: None,
+            last_fetched_at: None,
+            subscribed: None,
+            subscription_id: None,
+            subscribed_count: None,
+        }
+    }
 }
 
 pub fn convert_to_tree(rss_sources: Vec<rss_sources::Model>) -> RssTree {
@@ -81,28 +144,63 @@ pub fn convert_btreemap_to_vec(tree: RssTree) -> RssTreeVec {
 
     for (key, node) in tree.children {
         let child_tree = match node {
-            RssNode::Leaf(data) => RssTreeVec {
-                name: key,
-                data: Some((*data).clone()),
-                children: vec![],
-            },
+            RssNode::Leaf(data) => RssTreeVec::node(key, vec![], Some(*data)),
             RssNode::Branch(branch_tree) => {
                 let converted_tree = convert_btreemap_to_vec(*branch_tree);
-                RssTreeVec {
-                    name: key,
-                    children: converted_tree.children,
-                    data: None,
-                }
+                RssTreeVec::node(key, converted_tree.children, None)
             }
         };
         children_vec.push(child_tree);
     }
 
-    RssTreeVec {
-        name: tree.name,
-        children: children_vec,
-        data: None,
+    RssTreeVec::node(tree.name, children_vec, None)
+}
+
+/// Fill the `with_stats` fields: leaves from `stats` and their source, branches with the sum
+/// of their children's counts and the latest `last_fetched_at`
+pub fn roll_up_stats(node: &mut RssTreeVec, stats: &HashMap<i32, SourceStats>) {
+    if let Some(source) = &node.data {
+        let source_stats = stats.get(&source.id).copied().unwrap_or_default();
+        node.paper_count = Some(source_stats.paper_count);
+        node.subscriber_count = Some(source_stats.subscriber_count);
+        node.last_fetched_at = source.last_fetched_at;
+        return;
+    }
+    let (mut paper_count, mut subscriber_count, mut last_fetched_at) = (0, 0, None);
+    for child in &mut node.children {
+        roll_up_stats(child, stats);
+        paper_count += child.paper_count.unwrap_or(0);
+        subscriber_count += child.subscriber_count.unwrap_or(0);
+        last_fetched_at = last_fetched_at.max(child.last_fetched_at);
     }
+    node.paper_count = Some(paper_count);
+    node.subscriber_count = Some(subscriber_count);
+    node.last_fetched_at = last_fetched_at;
+}
+
+/// Mark the leaves of sources in `subscriptions` (source id to subscription id) and count
+/// them on every branch; returns the node's subscribed count
+pub fn mark_subscriptions(node: &mut RssTreeVec, subscriptions: &HashMap<i32, i64>) -> i64 {
+    if let Some(source) = &node.data {
+        let subscription_id = subscriptions.get(&source.id).copied();
+        node.subscribed = Some(subscription_id.is_some());
+        node.subscription_id = subscription_id;
+        return i64::from(subscription_id.is_some());
+    }
+    let count = node
+        .children
+        .iter_mut()
+        .map(|child| mark_subscriptions(child, subscriptions))
+        .sum();
+    node.subscribed_count = Some(count);
+    count
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RssTreeRequest {
+    pub channel: Option<String>,
+    pub with_stats: Option<bool>,
+    pub include_subscription_state: Option<bool>,
 }
 
 #[utoipa::path(
@@ -113,7 +211,7 @@ pub fn convert_btreemap_to_vec(tree: RssTree) -> RssTreeVec {
 Retrieve all available RSS sources organized in a hierarchical tree structure.
 
 ## Overview
-This endpoint returns all RSS sources from the system, organized into a tree structure based on their channel and name hierarchy.
+This endpoint returns all RSS sources from the system, organized into a tree structure based on their channel and name hierarchy. Pass `channel` to only get the sources of one channel; `GET /rss/channels` lists the channels for a channel switcher.
 
 ## Tree Structure
 The RSS sources are organized hierarchically:
@@ -130,11 +228,29 @@ Returns a `RssTreeVec` object containing:
   - logo_img, background_img
   - created_at, updated_at, last_fetched_at
 
+## Stats
+With `with_stats=true` every node also has:
+- `paper_count`: papers of the source; on a branch, the sum of its children
+- `subscriber_count`: users subscribed to the source; on a branch, the sum of its children (a user subscribed to two sources counts twice)
+- `last_fetched_at`: last fetch of the source; on a branch, the latest of its children (omitted when never fetched)
+
+## Subscription State
+With `include_subscription_state=true` the tree is annotated for the caller:
+- leaves: `subscribed`, and `subscription_id` when subscribed
+- branches: `subscribed_count`, the subscribed sources below the node
+
+Leave it off for the same response for every user.
+
 ## Use Cases
 - Display RSS sources in a hierarchical UI
 - Browse available sources by category
 - Show the complete RSS source catalog
 "#,
+    params(
+        ("channel" = Option<String>, Query, description = "Only the sources of this channel (all channels when omitted)"),
+        ("with_stats" = Option<bool>, Query, description = "Add paper / subscriber counts and last fetch time to every node"),
+        ("include_subscription_state" = Option<bool>, Query, description = "Mark the sources the user subscribes to"),
+    ),
     responses(
         (status = 200, body = RssTreeVec, description = "Successfully retrieved RSS sources tree structure"),
         (status = 401, description = "Unauthorized - valid authentication required"),
@@ -144,26 +260,139 @@ Returns a `RssTreeVec` object containing:
 )]
 pub async fn rss(
     State(state): State<AppState>,
-    User(_user): User,
+    User(user): User,
+    Query(payload): Query<RssTreeRequest>,
 ) -> Result<ApiResponse<RssTreeVec>, ApiError> {
-    tracing::info!("list rss sources");
+    let channel = normalize_text(payload.channel.as_deref());
+    tracing::info!(?channel, "list rss sources");
+    let with_stats = payload.with_stats.unwrap_or(false);
+    let with_subscriptions = payload.include_subscription_state.unwrap_or(false);
+    let read_conn = state.read_conn_for(user.id).await;
 
-    let rss_sources = RssSourcesQuery::list_all(&state.conn, Some("arxiv"))
+    let rss_sources = RssSourcesQuery::list_all(read_conn, channel.as_deref())
         .await
         .context(DbErrSnafu {
             stage: "list-rss-sources",
             code: ApiCode::COMMON_DATABASE_ERROR,
         })?;
 
+    let stats = if with_stats {
+        let source_ids: Vec<i32> = rss_sources.iter().map(|source| source.id).collect();
+        Some(
+            stats_by_ids(read_conn, &source_ids)
+                .await
+                .context(DbErrSnafu {
+                    stage: "get-rss-source-stats",
+                    code: ApiCode::COMMON_DATABASE_ERROR,
+                })?,
+        )
+    } else {
+        None
+    };
+
+    let subscriptions = if with_subscriptions {
+        let subscriptions = RssSubscriptionsQuery::list_by_user_id(read_conn, user.id, None)
+            .await
+            .context(DbErrSnafu {
+                stage: "get-rss-subscriptions",
+                code: ApiCode::COMMON_DATABASE_ERROR,
+            })?;
+        Some(
+            subscriptions
+                .into_iter()
+                .map(|subscription| (subscription.source_id, subscription.id))
+                .collect::<HashMap<_, _>>(),
+        )
+    } else {
+        None
+    };
+
     let tree = convert_to_tree(rss_sources);
-    let tree_vec = convert_btreemap_to_vec(tree);
+    let mut tree_vec = convert_btreemap_to_vec(tree);
+    if let Some(stats) = stats {
+        roll_up_stats(&mut tree_vec, &stats);
+    }
+    if let Some(subscriptions) = subscriptions {
+        mark_subscriptions(&mut tree_vec, &subscriptions);
+    }
     Ok(ApiResponse::data(tree_vec))
     // Ok(ApiResponse::data(rss_sources))
 }
 
+#[utoipa::path(
+    get,
+    path = "/rss/channels",
+    summary = "List RSS source channels",
+    description = r#"
+List the distinct channels of all RSS sources, alphabetically, e.g. to build a channel switcher over `GET /rss?channel=...`.
+"#,
+    responses(
+        (status = 200, body = Vec<String>, description = "Every channel with at least one source"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 500, description = "Database error"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn rss_channels(
+    State(state): State<AppState>,
+    User(user): User,
+) -> Result<ApiResponse<Vec<String>>, ApiError> {
+    let channels = list_channels(state.read_conn_for(user.id).await)
+        .await
+        .context(DbErrSnafu {
+            stage: "list-rss-channels",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+    Ok(ApiResponse::data(channels))
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct UserRssResponse {
     pub source_map: Vec<rss_sources::Model>,
+    /// With `group_by_folder=true`: the same sources grouped by the user's folders
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub folders: Option<Vec<SourceFolder>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SourceFolder {
+    /// Folder name, `null` for the sources in no folder
+    pub folder: Option<String>,
+    pub sources: Vec<rss_sources::Model>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct UserRssRequest {
+    pub group_by_folder: Option<bool>,
+}
+
+/// Group `sources` by folder: named folders alphabetically, then the unfiled sources
+fn group_by_folder(
+    sources: &[rss_sources::Model],
+    folders: &HashMap<i32, String>,
+) -> Vec<SourceFolder> {
+    let mut named: BTreeMap<&str, Vec<rss_sources::Model>> = BTreeMap::new();
+    let mut unfiled = Vec::new();
+    for source in sources {
+        match folders.get(&source.id) {
+            Some(folder) => named.entry(folder).or_default().push(source.clone()),
+            None => unfiled.push(source.clone()),
+        }
+    }
+    let mut groups: Vec<SourceFolder> = named
+        .into_iter()
+        .map(|(folder, sources)| SourceFolder {
+            folder: Some(folder.to_string()),
+            sources,
+        })
+        .collect();
+    if !unfiled.is_empty() {
+        groups.push(SourceFolder {
+            folder: None,
+            sources: unfiled,
+        });
+    }
+    groups
 }
 
 #[utoipa::path(
@@ -190,9 +419,22 @@ Returns a `UserRssResponse` object containing:
 
 ## Note
 The returned sources are automatically deduplicated, so each unique source appears only once even if the user has multiple subscriptions to it.
+
+## Folders
+With `group_by_folder=true` the response also has `folders`: the same sources grouped by the user's subscription folders (see `PATCH /subscriptions/{id}/folder`), named folders in alphabetical order, then a `"folder": null` group with the sources in no folder.
+
+## Caching
+The response carries an `ETag`, a hash of the response. Send it back in `If-None-Match` to get `304 Not Modified` (no body) while the response is unchanged.
 "#,
+    params(
+        ("group_by_folder" = Option<bool>, Query, description = "Also return the sources grouped by the user's folders"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag of a previous response"),
+    ),
     responses(
-        (status = 200, body = UserRssResponse, description = "Successfully retrieved user's subscribed RSS sources"),
+        (status = 200, body = UserRssResponse, description = "Successfully retrieved user's subscribed RSS sources",
+            headers(("ETag" = String, description = "Hash of the response"))),
+        (status = 304, description = "Not modified since the ETag in `If-None-Match`",
+            headers(("ETag" = String, description = "Hash of the response"))),
         (status = 401, description = "Unauthorized - valid authentication required"),
         (status = 500, description = "Database error"),
     ),
@@ -201,32 +443,46 @@ The returned sources are automatically deduplicated, so each unique source appea
 pub async fn user_rss(
     State(state): State<AppState>,
     User(user): User,
-) -> Result<ApiResponse<UserRssResponse>, ApiError> {
+    Query(payload): Query<UserRssRequest>,
+    if_none_match: IfNoneMatch,
+) -> Result<Conditional<ApiResponse<UserRssResponse>>, ApiError> {
     tracing::info!(user_id = user.id, "list user subscribed rss sources");
+    let grouped = payload.group_by_folder.unwrap_or(false);
 
-    let subscriptions = RssSubscriptionsQuery::list_by_user_id(&state.conn, user.id, None)
-        .await
-        .context(DbErrSnafu {
-            stage: "get-rss-subscriptions",
-            code: ApiCode::COMMON_DATABASE_ERROR,
-        })?;
+    let context = CachedUserContext::new(&state);
+    if_none_match
+        .respond(|| async {
+            let source_ids = context.subscriptions(user.id).await?;
 
-    let mut source_ids: Vec<i32> = subscriptions.into_iter().map(|s| s.source_id).collect();
-    source_ids.sort_unstable();
-    source_ids.dedup();
+            let source_map: Vec<rss_sources::Model> = if source_ids.is_empty() {
+                Vec::new()
+            } else {
+                RssSourcesQuery::get_by_ids(&state.conn, source_ids)
+                    .await
+                    .context(DbErrSnafu {
+                        stage: "get-rss-sources",
+                        code: ApiCode::COMMON_DATABASE_ERROR,
+                    })?
+            };
 
-    let source_map: Vec<rss_sources::Model> = if source_ids.is_empty() {
-        Vec::new()
-    } else {
-        RssSourcesQuery::get_by_ids(&state.conn, source_ids)
-            .await
-            .context(DbErrSnafu {
-                stage: "get-rss-sources",
-                code: ApiCode::COMMON_DATABASE_ERROR,
-            })?
-    };
+            let folders = if grouped {
+                let folders = subscription_folders::list(&state.conn, user.id)
+                    .await
+                    .context(DbErrSnafu {
+                        stage: "get-subscription-folders",
+                        code: ApiCode::COMMON_DATABASE_ERROR,
+                    })?;
+                Some(group_by_folder(&source_map, &folders))
+            } else {
+                None
+            };
 
-    Ok(ApiResponse::data(UserRssResponse { source_map }))
+            Ok(ApiResponse::data(UserRssResponse {
+                source_map,
+                folders,
+            }))
+        })
+        .await
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -237,6 +493,12 @@ pub struct CreateRssSource {
     pub description: Option<String>,
     pub logo_img: Option<String>,
     pub background_img: Option<String>,
+    /// Insert without fetching the URL first (admins only, for bulk imports)
+    #[serde(default)]
+    pub skip_validation: bool,
+    /// Insert even when a source with the same URL exists
+    #[serde(default)]
+    pub force: bool,
 }
 
 #[utoipa::path(
@@ -327,6 +589,16 @@ This endpoint allows adding a new RSS feed source to the system. The source will
 - `description` (optional): Descriptive text about the source
 - `logo_img` (optional): Logo image URL
 - `background_img` (optional): Background image URL
+- `skip_validation` (optional, admins only): Insert without checking the URL
+- `force` (optional): Insert even when a source with the same URL already exists
+
+## Duplicates
+A source whose URL only differs in the case of the scheme or host, a default port, a fragment or a trailing slash is the same feed (`http` and `https` stay distinct). Creating it again returns 409 with `success: false` and the existing source's id as `data`, unless `force` is set.
+
+## Validation
+Before inserting, the URL is fetched (timeout `rss.feed_validation_timeout_secs`, default 10s) and must return a 2xx response whose root element is `<rss>`, `<rdf:RDF>` or `<feed>` (Atom). Otherwise the request is rejected with 400 and the reason, e.g. `the feed URL returned HTTP 404 Not Found` or `not an RSS or Atom feed (root element <html>)`.
+
+Only public addresses are fetched: URLs naming or resolving to loopback, private, link-local (e.g. `169.254.169.254`) or other internal addresses are rejected, redirects included. At most `rss.feed_validation_max_redirects` (3) redirects are followed and `rss.feed_validation_max_bytes` (256 KiB) of the body read while looking for the root element.
 
 ## Returns
 Returns the `id` (i32) of the newly created RSS source.
@@ -340,10 +612,13 @@ Returns the `id` (i32) of the newly created RSS source.
 The `name` field supports hierarchical organization using pipe separators (e.g., "Category|Subcategory|Feed Name").
 "#,
     request_body = CreateRssSource,
+    params(("Idempotency-Key" = Option<String>, Header, description = "Retries with the same key get the first successful response back instead of running again; 409 while the first is still running, 422 if reused with a different body")),
     responses(
         (status = 200, description = "RSS source created successfully, returns the new source ID", body = i32),
         (status = 401, description = "Unauthorized - valid authentication required"),
-        (status = 400, description = "Invalid request data"),
+        (status = 400, description = "Invalid request data, or the URL is not a reachable RSS/Atom feed"),
+        (status = 403, description = "`skip_validation` requires the admin capability"),
+        (status = 409, description = "A source with the same URL exists, returns its ID", body = i32),
         (status = 500, description = "Database error or creation failed"),
     ),
     tag = FEED_TAG,
@@ -351,10 +626,45 @@ The `name` field supports hierarchical organization using pipe separators (e.g.,
 pub async fn rss_create(
     State(state): State<AppState>,
     User(_user): User,
+    Extension(caller): Extension<Caller>,
     Json(payload): Json<CreateRssSource>,
-) -> Result<ApiResponse<i32>, ApiError> {
+) -> Result<(StatusCode, ApiResponse<i32>), ApiError> {
     tracing::info!(name = payload.name, url = payload.url, "create rss source");
 
+    if !payload.force {
+        let existing = find_by_url(&state.conn, &payload.url)
+            .await
+            .context(DbErrSnafu {
+                stage: "find-rss-source-by-url",
+                code: ApiCode::COMMON_DATABASE_ERROR,
+            })?;
+        if let Some(existing) = existing.first() {
+            return Ok((
+                StatusCode::CONFLICT,
+                ApiResponse {
+                    data: existing.id,
+                    success: false,
+                    message: format!(
+                        "RSS source {} already uses this URL; pass force=true to add a duplicate",
+                        existing.id
+                    ),
+                },
+            ));
+        }
+    }
+
+    if payload.skip_validation {
+        caller.check(Capability::Admin)?;
+    } else {
+        let kind = validate_feed_url(&payload.url, &server_rss_config().feed_fetch_limits())
+            .await
+            .map_err(|reason| ApiError::CustomError {
+                message: format!("Invalid feed URL {}: {reason}", payload.url),
+                code: INVALID_FEED_URL,
+            })?;
+        tracing::info!(url = payload.url, ?kind, "feed url validated");
+    }
+
     let id = RssSourcesQuery::insert(
         &state.conn,
         RssSourceData {
@@ -373,8 +683,363 @@ pub async fn rss_create(
         stage: "create-rss-source",
         code: ApiCode::COMMON_DATABASE_ERROR,
     })?;
+    VersionCounter::rss_sources(&state).bump().await;
+
+    Ok((StatusCode::OK, ApiResponse::data(id)))
+}
+
+/// Most sources accepted by one `POST /rss/batch`
+pub const MAX_RSS_BATCH: usize = 200;
+
+/// Concurrent feed URL checks of a batch
+const BATCH_VALIDATION_CONCURRENCY: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchItemStatus {
+    Created,
+    /// A source already uses the URL; `id` is that source
+    Duplicate,
+    /// Not inserted, see `error`
+    Invalid,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BatchItemResult {
+    /// Position of the item in the request
+    pub index: usize,
+    pub status: BatchItemStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Why `source` can not be inserted, before touching the database
+async fn check_batch_item(
+    source: &CreateRssSource,
+    limits: &FeedFetchLimits,
+) -> Result<(), String> {
+    for (field, value) in [
+        ("channel", &source.channel),
+        ("name", &source.name),
+        ("url", &source.url),
+    ] {
+        if value.trim().is_empty() {
+            return Err(format!("{field} must not be empty"));
+        }
+    }
+    if source.skip_validation {
+        let url = reqwest::Url::parse(&source.url).map_err(|e| format!("invalid URL: {e}"))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(format!("unsupported URL scheme {}", url.scheme()));
+        }
+        return Ok(());
+    }
+    validate_feed_url(&source.url, limits)
+        .await
+        .map(|_| ())
+        .map_err(|reason| format!("Invalid feed URL {}: {reason}", source.url))
+}
+
+#[utoipa::path(
+    post,
+    path = "/rss/batch",
+    summary = "Create RSS sources in bulk",
+    description = r#"
+Import up to 200 RSS sources at once, e.g. a set of arxiv category feeds.
+
+## Request Body
+An array of `POST /rss` bodies. `skip_validation` and `force` apply per item.
+
+## Behavior
+- Each item is checked first: `channel`, `name` and `url` must not be blank, and the URL must be a reachable RSS/Atom feed unless `skip_validation` is set. Failing items are reported as `invalid` and skipped.
+- The remaining items are inserted in one transaction. An item whose URL is already used by a source, or by an earlier item of the batch, is reported as `duplicate` with that source's `id` (unless `force` is set).
+- A database error rolls back the whole batch and the request fails with 500: either every `created` item exists or none does.
+
+## Returns
+One result per item, in request order:
+```json
+[
+  { "index": 0, "status": "created", "id": 101 },
+  { "index": 1, "status": "duplicate", "id": 42 },
+  { "index": 2, "status": "invalid", "error": "name must not be empty" }
+]
+```
+"#,
+    request_body = Vec<CreateRssSource>,
+    responses(
+        (status = 200, body = Vec<BatchItemResult>, description = "Per-item results"),
+        (status = 400, description = "More than 200 items (nothing was inserted)"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 403, description = "Admin capability required"),
+        (status = 500, description = "Database error (nothing was inserted)"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn rss_batch_create(
+    State(state): State<AppState>,
+    User(user): User,
+    Json(payload): Json<Vec<CreateRssSource>>,
+) -> Result<ApiResponse<Vec<BatchItemResult>>, ApiError> {
+    tracing::info!(
+        user_id = user.id,
+        count = payload.len(),
+        "create rss sources in bulk"
+    );
+    if payload.len() > MAX_RSS_BATCH {
+        return Err(ApiError::CustomError {
+            message: format!(
+                "at most {MAX_RSS_BATCH} sources per batch, got {}",
+                payload.len()
+            ),
+            code: RSS_BATCH_TOO_LARGE,
+        });
+    }
+
+    let limits = server_rss_config().feed_fetch_limits();
+    let checks: Vec<Result<(), String>> = futures::stream::iter(&payload)
+        .map(|source| check_batch_item(source, &limits))
+        .buffered(BATCH_VALIDATION_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut results = Vec::with_capacity(payload.len());
+    let mut valid = Vec::new();
+    for (index, (source, check)) in payload.into_iter().zip(checks).enumerate() {
+        match check {
+            Ok(()) => valid.push((
+                index,
+                NewRssSource {
+                    channel: source.channel,
+                    name: source.name,
+                    url: source.url,
+                    description: source.description,
+                    logo_img: source.logo_img,
+                    background_img: source.background_img,
+                    force: source.force,
+                },
+            )),
+            Err(error) => results.push(BatchItemResult {
+                index,
+                status: BatchItemStatus::Invalid,
+                id: None,
+                error: Some(error),
+            }),
+        }
+    }
+
+    let sources: Vec<NewRssSource> = valid.iter().map(|(_, source)| source.clone()).collect();
+    let outcomes = insert_many(&state.conn, &sources)
+        .await
+        .context(DbErrSnafu {
+            stage: "create-rss-sources-batch",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+    let mut created = 0;
+    for ((index, _), outcome) in valid.iter().zip(outcomes) {
+        let (status, id) = match outcome {
+            InsertOutcome::Created(id) => {
+                created += 1;
+                (BatchItemStatus::Created, id)
+            }
+            InsertOutcome::Duplicate(id) => (BatchItemStatus::Duplicate, id),
+        };
+        results.push(BatchItemResult {
+            index: *index,
+            status,
+            id: Some(id),
+            error: None,
+        });
+    }
+    results.sort_by_key(|result| result.index);
 
-    Ok(ApiResponse::data(id))
+    if created > 0 {
+        VersionCounter::rss_sources(&state).bump().await;
+    }
+    tracing::info!(
+        user_id = user.id,
+        created,
+        total = results.len(),
+        "rss source batch imported"
+    );
+    Ok(ApiResponse::data(results))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateRssSource {
+    pub channel: Option<String>,
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub description: Option<String>,
+    pub logo_img: Option<String>,
+    pub background_img: Option<String>,
+    /// Change the URL without fetching it first
+    #[serde(default)]
+    pub skip_validation: bool,
+    /// Change the URL even when another source uses the same URL
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[utoipa::path(
+    put,
+    path = "/rss/{id}",
+    summary = "Update an RSS source (admin)",
+    description = r#"
+Edit an RSS source in place, keeping its id, subscriptions and papers (unlike delete + create).
+
+Sources are shared by every subscriber, so this requires the admin capability.
+
+## Request Body
+Same fields as `POST /rss`, all optional; only the provided fields are written:
+```json
+{
+  "name": "AI Research|Machine Learning",
+  "url": "https://example.com/new-feed.xml"
+}
+```
+
+## Behavior
+- `updated_at` is set to the current time
+- A new `url` is checked like in `POST /rss`: it must be a reachable RSS/Atom feed on a public address unless `skip_validation` is set (400 otherwise), and no other source may use the same URL unless `force` is set (409 otherwise). A URL that only differs in spelling (see `POST /rss`) is not checked.
+- Changing `url` resets `last_fetched_at` to null, so the next pull re-fetches the feed
+- The update is recorded in the audit log (`rss_source_update`) with the previous and new value of each changed field
+
+## Returns
+Returns the updated `rss_sources::Model`.
+"#,
+    params(
+        ("id" = i32, Path, description = "The unique identifier of the RSS source to update"),
+    ),
+    request_body = UpdateRssSource,
+    responses(
+        (status = 200, description = "RSS source updated, returns the updated source", body = rss_sources::Model),
+        (status = 400, description = "The new URL is not a reachable RSS/Atom feed"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 403, description = "Admin capability required"),
+        (status = 404, description = "RSS source not found"),
+        (status = 409, description = "Another source uses the new URL"),
+        (status = 500, description = "Database error or update failed"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn rss_update(
+    State(state): State<AppState>,
+    User(user): User,
+    Path(id): Path<i32>,
+    request_id: Option<Extension<RequestId>>,
+    Json(payload): Json<UpdateRssSource>,
+) -> Result<ApiResponse<rss_sources::Model>, ApiError> {
+    tracing::info!(id, ?payload, "update rss source");
+
+    let not_found = || ApiError::CustomError {
+        message: format!("rss source {id} not found"),
+        code: RESOURCE_NOT_FOUND,
+    };
+    let current = rss_sources::Entity::find_by_id(id)
+        .one(&state.conn)
+        .await
+        .context(DbErrSnafu {
+            stage: "get-rss-source",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?
+        .ok_or_else(not_found)?;
+
+    let new_url = payload
+        .url
+        .as_deref()
+        .filter(|url| normalize_feed_url(url) != normalize_feed_url(&current.url));
+    if let Some(url) = new_url {
+        if !payload.force {
+            let existing = find_by_url(&state.conn, url).await.context(DbErrSnafu {
+                stage: "find-rss-source-by-url",
+                code: ApiCode::COMMON_DATABASE_ERROR,
+            })?;
+            if let Some(existing) = existing.iter().find(|source| source.id != id) {
+                return Err(ApiError::CustomError {
+                    message: format!(
+                        "RSS source {} already uses this URL; pass force=true to use it anyway",
+                        existing.id
+                    ),
+                    code: CONFLICT,
+                });
+            }
+        }
+        if !payload.skip_validation {
+            let kind = validate_feed_url(url, &server_rss_config().feed_fetch_limits())
+                .await
+                .map_err(|reason| ApiError::CustomError {
+                    message: format!("Invalid feed URL {url}: {reason}"),
+                    code: INVALID_FEED_URL,
+                })?;
+            tracing::info!(url, ?kind, "feed url validated");
+        }
+    }
+
+    let source = update_by_id(
+        &state.conn,
+        id,
+        RssSourcePatch {
+            channel: payload.channel,
+            name: payload.name,
+            url: payload.url,
+            description: payload.description,
+            logo_img: payload.logo_img,
+            background_img: payload.background_img,
+        },
+    )
+    .await
+    .context(DbErrSnafu {
+        stage: "update-rss-source",
+        code: ApiCode::COMMON_DATABASE_ERROR,
+    })?
+    .ok_or_else(not_found)?;
+    VersionCounter::rss_sources(&state).bump().await;
+    record_audit(
+        &state,
+        &user,
+        request_id.as_deref(),
+        AuditAction::RssSourceUpdate,
+        &[id as i64],
+        serde_json::json!({ "changes": source_changes(&current, &source) }),
+    )
+    .await;
+
+    Ok(ApiResponse::data(source))
+}
+
+/// `{field: {from, to}}` of the fields that differ between `before` and `after`
+fn source_changes(before: &rss_sources::Model, after: &rss_sources::Model) -> serde_json::Value {
+    let mut changes = serde_json::Map::new();
+    for (field, from, to) in [
+        ("channel", Some(&before.channel), Some(&after.channel)),
+        ("name", Some(&before.name), Some(&after.name)),
+        ("url", Some(&before.url), Some(&after.url)),
+        (
+            "description",
+            before.description.as_ref(),
+            after.description.as_ref(),
+        ),
+        (
+            "logo_img",
+            before.logo_img.as_ref(),
+            after.logo_img.as_ref(),
+        ),
+        (
+            "background_img",
+            before.background_img.as_ref(),
+            after.background_img.as_ref(),
+        ),
+    ] {
+        if from != to {
+            changes.insert(
+                field.to_string(),
+                serde_json::json!({ "from": from, "to": to }),
+            );
+        }
+    }
+    changes.into()
 }
 
 #[utoipa::path(
@@ -385,7 +1050,7 @@ pub async fn rss_create(
 Delete an RSS source from the system.
 
 ## Overview
-This endpoint permanently removes an RSS source from the database.
+This endpoint permanently removes an RSS source from the database. Sources are shared by every subscriber, so it requires the admin capability.
 
 ## Parameters
 - `id`: The unique identifier of the RSS source to delete
@@ -412,6 +1077,7 @@ This operation is permanent and cannot be undone. Ensure the correct ID is speci
     responses(
         (status = 200, description = "RSS source deleted successfully, returns true", body = bool),
         (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 403, description = "Admin capability required"),
         (status = 404, description = "RSS source not found"),
         (status = 500, description = "Database error or deletion failed"),
     ),
@@ -420,16 +1086,36 @@ This operation is permanent and cannot be undone. Ensure the correct ID is speci
 pub async fn rss_delete(
     Path(id): Path<i32>,
     State(state): State<AppState>,
-    User(_user): User,
+    User(user): User,
+    request_id: Option<Extension<RequestId>>,
 ) -> Result<ApiResponse<bool>, ApiError> {
     tracing::info!(id, "delete rss source");
 
+    // read before the delete takes the subscriptions with it
+    let subscribers = subscriber_ids(&state.conn, id).await.context(DbErrSnafu {
+        stage: "get-rss-source-subscribers",
+        code: ApiCode::COMMON_DATABASE_ERROR,
+    })?;
     RssSourcesQuery::delete_by_id(&state.conn, id)
         .await
         .context(DbErrSnafu {
             stage: "delete-rss-source",
             code: ApiCode::COMMON_DATABASE_ERROR,
         })?;
+    VersionCounter::rss_sources(&state).bump().await;
+    let context = CachedUserContext::new(&state);
+    for user_id in subscribers {
+        context.invalidate(user_id).await;
+    }
+    record_audit(
+        &state,
+        &user,
+        request_id.as_deref(),
+        AuditAction::RssSourceDelete,
+        &[id as i64],
+        serde_json::json!({}),
+    )
+    .await;
 
     Ok(ApiResponse::data(true))
 }
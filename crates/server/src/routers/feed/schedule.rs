@@ -0,0 +1,111 @@
+use axum::{Json, extract::State};
+use chrono::{DateTime, Utc};
+use common::{error::api_error::*, prelude::ApiCode};
+use serde::Serialize;
+use snafu::ResultExt;
+use utoipa::ToSchema;
+
+use super::FEED_TAG;
+use crate::{
+    middlewares::auth::User,
+    model::{
+        base::ApiResponse,
+        schedule::{VerifySchedule, VerifyScheduleInput},
+        tz::resolve_timezone,
+    },
+    query::verify_schedules,
+    state::app_state::AppState,
+};
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VerifyScheduleView {
+    #[serde(flatten)]
+    pub schedule: VerifySchedule,
+    /// When the next scheduled run will be queued, `null` when disabled
+    pub next_run_at: Option<DateTime<Utc>>,
+}
+
+impl VerifyScheduleView {
+    fn new(schedule: VerifySchedule) -> Self {
+        let next_run_at = schedule.next_run_at(Utc::now());
+        VerifyScheduleView {
+            schedule,
+            next_run_at,
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/verify-schedule",
+    summary = "Get the user's verification schedule",
+    description = r#"
+Return the daily verification schedule set with `PUT /verify-schedule`, or `null` when the user never set one.
+"#,
+    responses(
+        (status = 200, body = Option<VerifyScheduleView>, description = "The schedule and its next run"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 500, description = "Database error"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn get_verify_schedule(
+    State(state): State<AppState>,
+    User(user): User,
+) -> Result<ApiResponse<Option<VerifyScheduleView>>, ApiError> {
+    let schedule = verify_schedules::get(&state.conn, user.id)
+        .await
+        .context(DbErrSnafu {
+            stage: "get-verify-schedule",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+    Ok(ApiResponse::data(schedule.map(VerifyScheduleView::new)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/verify-schedule",
+    summary = "Set the user's verification schedule",
+    description = r#"
+Opt in to (or out of) an automatic verification run every day, instead of pressing the verify button.
+
+## Behavior
+- Every day at `hour_of_day` (0-23) in `timezone`, the user is added to the verify list, like `POST /stream-verify` does
+- `timezone` defaults to the user's `zoneinfo`, then UTC
+- `max_match_limit` defaults to the server's `max_match_limit_per_user`
+- Users already being verified are skipped until their run is over
+- The first run is the first slot after the schedule is saved: saving at 9:00 for 8:00 runs tomorrow
+- `enabled: false` keeps the settings but stops the runs
+- Nothing is scheduled while the `verify_dispatch` kill switch is engaged
+"#,
+    request_body = VerifyScheduleInput,
+    responses(
+        (status = 200, body = VerifyScheduleView, description = "The saved schedule and its next run"),
+        (status = 400, description = "Unknown timezone"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 500, description = "Invalid hour or match limit, or database error"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn put_verify_schedule(
+    State(state): State<AppState>,
+    User(user): User,
+    Json(payload): Json<VerifyScheduleInput>,
+) -> Result<ApiResponse<VerifyScheduleView>, ApiError> {
+    let input = payload.validate()?;
+    let tz = resolve_timezone(input.timezone.as_deref(), &user)?;
+    let schedule = verify_schedules::upsert(&state.conn, user.id, &input, tz.name())
+        .await
+        .context(DbErrSnafu {
+            stage: "put-verify-schedule",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+    tracing::info!(
+        user_id = user.id,
+        enabled = schedule.enabled,
+        hour_of_day = schedule.hour_of_day,
+        timezone = %schedule.timezone,
+        "verify schedule saved"
+    );
+    Ok(ApiResponse::data(VerifyScheduleView::new(schedule)))
+}
@@ -1,20 +1,31 @@
-use axum::Json;
+use std::collections::HashSet;
+
 use axum::extract::{Path, State};
+use axum::{Extension, Json};
 use common::{error::api_error::*, prelude::ApiCode};
 use feed::redis::update_task_manager::{
     TaskType, UpdateTaskData, UpdateTaskInput, UpdateTaskManager,
 };
 use seaorm_db::{
-    entities::feed::rss_subscriptions, query::feed::rss_subscriptions::RssSubscriptionsQuery,
+    entities::feed::rss_subscriptions,
+    query::feed::{rss_sources::RssSourcesQuery, rss_subscriptions::RssSubscriptionsQuery},
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use snafu::ResultExt;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::{
-    middlewares::auth::User, model::base::ApiResponse, routers::feed::FEED_TAG,
-    state::app_state::AppState,
+    consts::{RESOURCE_NOT_FOUND, UNKNOWN_SOURCE},
+    middlewares::{
+        auth::User,
+        etag::{Conditional, IfNoneMatch},
+        log::RequestId,
+    },
+    model::{audit::AuditAction, base::ApiResponse},
+    query::{rss_subscriptions as user_subscriptions, subscription_folders},
+    routers::{audit::record_audit, feed::FEED_TAG},
+    state::{app_state::AppState, user_context::CachedUserContext},
 };
 
 #[utoipa::path(
@@ -32,6 +43,7 @@ Returns an array of `rss_subscriptions::Model` objects, each containing:
 - `id`: Subscription record ID (unique identifier for the subscription)
 - `user_id`: User ID who owns this subscription
 - `source_id`: RSS source ID being subscribed to
+- `folder`: The user's folder for this source, or `null`
 - `created_at`: Timestamp when the subscription was created
 - `updated_at`: Timestamp of last update
 
@@ -41,14 +53,23 @@ Returns an array of `rss_subscriptions::Model` objects, each containing:
 - Show subscribed feeds in UI
 - Sync subscription status
 
+## Caching
+The response carries an `ETag`. Send it back in `If-None-Match` to get `304 Not Modified` (no body) while the subscriptions are unchanged.
+
 ## Related Endpoints
 - Use `POST /subscriptions` to batch update subscriptions
 - Use `POST /subscriptions/one` to add a single subscription
 - Use `DELETE /subscriptions/{id}` to remove a subscription
 - Use `GET /user_rss` to get RSS source details for subscribed feeds
 "#,
+    params(
+        ("If-None-Match" = Option<String>, Header, description = "ETag of a previous response"),
+    ),
     responses(
-        (status = 200, body = Vec<rss_subscriptions::Model>, description = "Successfully retrieved user's subscriptions"),
+        (status = 200, body = Vec<SubscriptionWithFolder>, description = "Successfully retrieved user's subscriptions",
+            headers(("ETag" = String, description = "Hash of the response"))),
+        (status = 304, description = "Not modified since the ETag in `If-None-Match`",
+            headers(("ETag" = String, description = "Hash of the response"))),
         (status = 401, description = "Unauthorized - valid authentication required"),
         (status = 500, description = "Database error"),
     ),
@@ -57,27 +78,152 @@ Returns an array of `rss_subscriptions::Model` objects, each containing:
 pub async fn subscriptions(
     State(state): State<AppState>,
     User(user): User,
-) -> Result<ApiResponse<Vec<rss_subscriptions::Model>>, ApiError> {
+    if_none_match: IfNoneMatch,
+) -> Result<Conditional<ApiResponse<Vec<SubscriptionWithFolder>>>, ApiError> {
     tracing::info!("get subscriptions");
 
-    let subscriptions = RssSubscriptionsQuery::list_by_user_id(&state.conn, user.id, None)
+    if_none_match
+        .respond(|| async {
+            let subscriptions = RssSubscriptionsQuery::list_by_user_id(&state.conn, user.id, None)
+                .await
+                .context(DbErrSnafu {
+                    stage: "get-rss-subscriptions",
+                    code: ApiCode::COMMON_DATABASE_ERROR,
+                })?;
+            let mut folders = subscription_folders::list(&state.conn, user.id)
+                .await
+                .context(DbErrSnafu {
+                    stage: "get-subscription-folders",
+                    code: ApiCode::COMMON_DATABASE_ERROR,
+                })?;
+
+            Ok(ApiResponse::data(
+                subscriptions
+                    .into_iter()
+                    .map(|subscription| SubscriptionWithFolder {
+                        folder: folders.remove(&subscription.source_id),
+                        subscription,
+                    })
+                    .collect(),
+            ))
+        })
         .await
-        .context(DbErrSnafu {
-            stage: "get-rss-subscriptions",
-            code: ApiCode::COMMON_DATABASE_ERROR,
-        })?;
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SubscriptionWithFolder {
+    #[serde(flatten)]
+    pub subscription: rss_subscriptions::Model,
+    /// The user's folder for this source
+    pub folder: Option<String>,
+}
 
-    Ok(ApiResponse::data(subscriptions))
+/// Longest folder name accepted
+const MAX_FOLDER_LEN: usize = 255;
+
+/// Trimmed folder name, `None` for blank ones
+fn normalize_folder(folder: Option<String>) -> Result<Option<String>, ApiError> {
+    let folder = folder
+        .map(|folder| folder.trim().to_string())
+        .filter(|folder| !folder.is_empty());
+    if folder
+        .as_ref()
+        .is_some_and(|folder| folder.chars().count() > MAX_FOLDER_LEN)
+    {
+        return Err(ApiError::CustomError {
+            message: format!("folder name exceeds {MAX_FOLDER_LEN} characters"),
+            code: ApiCode::COMMON_FEED_ERROR,
+        });
+    }
+    Ok(folder)
+}
+
+/// A source to subscribe to: a bare id, or an id with its folder
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum SubscriptionSource {
+    /// Keeps the source's current folder
+    Id(i32),
+    /// Moves the source to `folder` (`null` or missing: out of any folder)
+    WithFolder {
+        source_id: i32,
+        folder: Option<String>,
+    },
+}
+
+impl SubscriptionSource {
+    pub fn source_id(&self) -> i32 {
+        match self {
+            SubscriptionSource::Id(source_id) => *source_id,
+            SubscriptionSource::WithFolder { source_id, .. } => *source_id,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct SubscriptionsCreateRequest {
-    pub source_ids: Vec<i32>,
+    pub source_ids: Vec<SubscriptionSource>,
+    /// Drop source ids that don't exist instead of rejecting the request
+    #[serde(default)]
+    pub ignore_unknown: bool,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct SubscriptionCreateOneRequest {
     pub source_id: i32,
+    /// Folder to put the source in
+    pub folder: Option<String>,
+}
+
+/// Outcome of `POST /subscriptions/one`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SubscriptionCreateResult {
+    /// A new subscription `id` was created
+    Created { id: i64 },
+    /// The user was already subscribed, through subscription `id`
+    AlreadySubscribed { id: i64 },
+    /// No RSS source has this id
+    UnknownSource,
+}
+
+impl SubscriptionCreateResult {
+    /// The subscription id, unless the source doesn't exist
+    pub fn id(&self) -> Option<i64> {
+        match self {
+            SubscriptionCreateResult::Created { id }
+            | SubscriptionCreateResult::AlreadySubscribed { id } => Some(*id),
+            SubscriptionCreateResult::UnknownSource => None,
+        }
+    }
+}
+
+/// The ids among `source_ids` with no RSS source, in request order and without repeats
+async fn unknown_source_ids(state: &AppState, source_ids: &[i32]) -> Result<Vec<i32>, ApiError> {
+    if source_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let known: HashSet<i32> = RssSourcesQuery::get_by_ids(&state.conn, source_ids.to_vec())
+        .await
+        .context(DbErrSnafu {
+            stage: "get-rss-sources",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?
+        .into_iter()
+        .map(|source| source.id)
+        .collect();
+    let mut seen = HashSet::new();
+    Ok(source_ids
+        .iter()
+        .copied()
+        .filter(|id| !known.contains(id) && seen.insert(*id))
+        .collect())
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SubscriptionFolderRequest {
+    /// New folder, `null` to take the subscription out of its folder
+    pub folder: Option<String>,
 }
 
 #[utoipa::path(
@@ -100,6 +246,25 @@ This endpoint allows users to batch update their RSS subscriptions. The system u
 ### Parameters
 - `source_ids` (required): Array of RSS source IDs to subscribe to. Empty array `[]` is allowed and will clear all subscriptions.
 
+### Folders
+An entry can also be an object carrying the source's folder:
+```json
+{
+  "source_ids": [1, { "source_id": 2, "folder": "Reading list" }, { "source_id": 3, "folder": null }]
+}
+```
+- `{ "source_id", "folder" }` puts the source in that folder (`null` takes it out of any folder)
+- A bare id keeps the folder the source already has
+- Folders of sources missing from the list are forgotten
+
+Folders are saved immediately, before the delayed subscription update runs.
+
+### Unknown Source IDs
+Every id is checked against the RSS sources before anything is queued:
+- By default the request is rejected with `400` listing the unknown ids, and nothing changes
+- With `"ignore_unknown": true` the unknown ids are dropped and the rest is applied; if every
+  id is unknown the request is still rejected with `400`, so it cannot turn into "clear all"
+
 ## Behavior & Update Logic
 
 ### Asynchronous Processing with 500ms Delay
@@ -211,7 +376,7 @@ User quickly selects/deselects multiple feeds in UI:
 ### Edge Cases
 - **Empty array**: All subscriptions soft-deleted (can be restored)
 - **Duplicate source IDs**: Automatically deduplicated
-- **Invalid source IDs**: May cause operation to fail at database level
+- **Invalid source IDs**: Rejected with `400` (or dropped with `ignore_unknown`, unless all are unknown)
 - **Very large arrays**: Performance may degrade with extremely large subscription lists
 
 ## Error Handling
@@ -223,7 +388,7 @@ User quickly selects/deselects multiple feeds in UI:
 1. **Wait after submission**: Don't immediately query subscriptions (wait >500ms)
 2. **Single final submission**: Send one update with complete final list
 3. **Reasonable subscription count**: Keep number of subscriptions manageable
-4. **Check source validity**: Ensure source IDs exist before subscribing
+4. **Check source validity**: Unknown source IDs reject the whole request unless `ignore_unknown` is set
 5. **Use single endpoint**: For adding one subscription, prefer `POST /subscriptions/one`
 
 ## Performance Characteristics
@@ -241,7 +406,7 @@ User quickly selects/deselects multiple feeds in UI:
     responses(
         (status = 200, description = "Successfully queued subscriptions update, returns request ID for tracking", body = String),
         (status = 401, description = "Unauthorized - valid authentication required"),
-        (status = 400, description = "Invalid request data"),
+        (status = 400, description = "Invalid request data, or unknown source ids (listed in the message)"),
         (status = 500, description = "Failed to queue update request"),
     ),
     tag = FEED_TAG,
@@ -249,16 +414,65 @@ User quickly selects/deselects multiple feeds in UI:
 pub async fn batch_subscriptions(
     State(state): State<AppState>,
     User(user): User,
+    http_request_id: Option<Extension<RequestId>>,
     Json(payload): Json<SubscriptionsCreateRequest>,
 ) -> Result<ApiResponse<String>, ApiError> {
-    let count = payload.source_ids.len();
+    let requested: Vec<i32> = payload
+        .source_ids
+        .iter()
+        .map(SubscriptionSource::source_id)
+        .collect();
+    let unknown = unknown_source_ids(&state, &requested).await?;
+    if !unknown.is_empty() {
+        if !payload.ignore_unknown {
+            let ids: Vec<String> = unknown.iter().map(i32::to_string).collect();
+            return Err(ApiError::CustomError {
+                message: format!("unknown source ids: {}", ids.join(", ")),
+                code: UNKNOWN_SOURCE,
+            });
+        }
+        // only an explicitly empty list clears the subscriptions
+        if requested.iter().all(|id| unknown.contains(id)) {
+            let ids: Vec<String> = unknown.iter().map(i32::to_string).collect();
+            return Err(ApiError::CustomError {
+                message: format!("no known source ids, unknown: {}", ids.join(", ")),
+                code: UNKNOWN_SOURCE,
+            });
+        }
+        tracing::info!(user_id = user.id, ?unknown, "dropping unknown source ids");
+    }
+
+    let mut folders = Vec::new();
+    for source in &payload.source_ids {
+        if let SubscriptionSource::WithFolder { source_id, folder } = source
+            && !unknown.contains(source_id)
+        {
+            folders.push((*source_id, normalize_folder(folder.clone())?));
+        }
+    }
+    let source_ids: Vec<i32> = requested
+        .into_iter()
+        .filter(|id| !unknown.contains(id))
+        .collect();
+    let count = source_ids.len();
     tracing::info!(user_id = user.id, count, "set subscriptions (async)");
-    if count == 0 {
+    // the sources an empty list unsubscribes from, for the audit log
+    let cleared = if count == 0 {
         tracing::info!(
             user_id = user.id,
             "empty source_ids: clear all subscriptions"
         );
-    }
+        let mut ids: Vec<i64> = CachedUserContext::new(&state)
+            .bypass_cache(true)
+            .subscriptions(user.id)
+            .await
+            .map(|source_ids| source_ids.into_iter().map(i64::from).collect())
+            .unwrap_or_default();
+        ids.sort_unstable();
+        Some(ids)
+    } else {
+        None
+    };
 
     // Create UpdateTaskManager
     let manager = UpdateTaskManager::new(
@@ -271,13 +485,14 @@ pub async fn batch_subscriptions(
         state.config.rss.update_task_merge_delay_ms.unwrap_or(500),
     );
 
+    CachedUserContext::new(&state).begin_update(user.id).await;
     let request_id = manager
         .submit_update(
             UpdateTaskInput {
                 task_type: TaskType::UserSubscriptions,
                 user_id: user.id,
                 data: UpdateTaskData::UserSubscriptions {
-                    source_ids: payload.source_ids,
+                    source_ids: source_ids.clone(),
                 },
                 request_id: Uuid::new_v4().to_string(),
             },
@@ -289,6 +504,32 @@ pub async fn batch_subscriptions(
             code: ApiCode::COMMON_FEED_ERROR,
         })?;
 
+    subscription_folders::retain(&state.conn, user.id, &source_ids)
+        .await
+        .context(DbErrSnafu {
+            stage: "retain-subscription-folders",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+    for (source_id, folder) in folders {
+        subscription_folders::set(&state.conn, user.id, source_id, folder.as_deref())
+            .await
+            .context(DbErrSnafu {
+                stage: "set-subscription-folder",
+                code: ApiCode::COMMON_DATABASE_ERROR,
+            })?;
+    }
+    if let Some(cleared) = cleared {
+        record_audit(
+            &state,
+            &user,
+            http_request_id.as_deref(),
+            AuditAction::SubscriptionsClear,
+            &cleared,
+            serde_json::json!({ "update_request_id": request_id }),
+        )
+        .await;
+    }
+
     tracing::info!(
         user_id = user.id,
         request_id = %request_id,
@@ -312,34 +553,42 @@ This endpoint adds one RSS source subscription to the user's existing subscripti
 ## Request Body
 ```json
 {
-  "source_id": 42
+  "source_id": 42,
+  "folder": "Reading list"
 }
 ```
 
 ## Parameters
 - `source_id`: The RSS source ID to subscribe to
+- `folder` (optional): Folder to put the source in; when omitted the source keeps its folder
 
 ## Behavior
 - **Append Operation**: Does NOT remove existing subscriptions
 - Only adds the specified source to the user's subscription list
-- Idempotent: If already subscribed, returns `null` (no error)
-- If the source doesn't exist, returns `null` (no error)
+- Idempotent: If already subscribed, returns the existing subscription (no error)
+- If the source doesn't exist, nothing is created (no error)
 
 ## Returns
-Returns an `Option<i64>`:
-- `Some(id)`: Subscription was created successfully, returns the new subscription ID
-- `null`: Subscription already exists OR source doesn't exist (no action taken)
+An object whose `status` tells what happened:
+- `created`: the subscription `id` was created
+- `already_subscribed`: the user already had subscription `id` to this source
+- `unknown_source`: no RSS source has this id; nothing changed
 
 ## Response Examples
 
-**Success - New subscription created:**
+**New subscription created:**
+```json
+{ "status": "created", "id": 123 }
+```
+
+**Already subscribed:**
 ```json
-123
+{ "status": "already_subscribed", "id": 98 }
 ```
 
-**Already exists or invalid source:**
+**Invalid source:**
 ```json
-null
+{ "status": "unknown_source" }
 ```
 
 ## Use Cases
@@ -353,7 +602,7 @@ null
 |---------|-------------------------|------------------------------|
 | Operation | Replace all | Append one |
 | Existing subscriptions | Removed | Preserved |
-| If already subscribed | Creates anyway | Returns null |
+| If already subscribed | Creates anyway | `already_subscribed` |
 | Multiple sources | Yes | No |
 
 ## Related Endpoints
@@ -362,8 +611,9 @@ null
 - Use `GET /subscriptions` to view all current subscriptions
 "#,
     request_body = SubscriptionCreateOneRequest,
+    params(("Idempotency-Key" = Option<String>, Header, description = "Retries with the same key get the first successful response back instead of running again; 409 while the first is still running, 422 if reused with a different body")),
     responses(
-        (status = 200, description = "Returns subscription ID if created, or null if already exists or source invalid", body = Option<i64>),
+        (status = 200, description = "Created, already subscribed, or unknown source", body = SubscriptionCreateResult),
         (status = 401, description = "Unauthorized - valid authentication required"),
         (status = 500, description = "Database error"),
     ),
@@ -373,21 +623,57 @@ pub async fn subscriptions_create_one(
     State(state): State<AppState>,
     User(user): User,
     Json(body): Json<SubscriptionCreateOneRequest>,
-) -> Result<ApiResponse<Option<i64>>, ApiError> {
+) -> Result<ApiResponse<SubscriptionCreateResult>, ApiError> {
     tracing::info!(
         user_id = user.id,
         source_id = body.source_id,
         "create one subscription"
     );
 
-    let id = RssSubscriptionsQuery::insert_one_source(&state.conn, user.id, body.source_id)
-        .await
-        .context(DbErrSnafu {
-            stage: "create-one-rss-subscription",
-            code: ApiCode::COMMON_DATABASE_ERROR,
-        })?;
+    let folder = normalize_folder(body.folder)?;
+
+    if !unknown_source_ids(&state, &[body.source_id])
+        .await?
+        .is_empty()
+    {
+        return Ok(ApiResponse::data(SubscriptionCreateResult::UnknownSource));
+    }
+
+    let result =
+        match RssSubscriptionsQuery::insert_one_source(&state.conn, user.id, body.source_id)
+            .await
+            .context(DbErrSnafu {
+                stage: "create-one-rss-subscription",
+                code: ApiCode::COMMON_DATABASE_ERROR,
+            })? {
+            Some(id) => SubscriptionCreateResult::Created { id },
+            None => {
+                let existing = RssSubscriptionsQuery::list_by_user_id(&state.conn, user.id, None)
+                    .await
+                    .context(DbErrSnafu {
+                        stage: "get-rss-subscriptions",
+                        code: ApiCode::COMMON_DATABASE_ERROR,
+                    })?
+                    .into_iter()
+                    .find(|subscription| subscription.source_id == body.source_id)
+                    .ok_or_else(|| ApiError::CustomError {
+                        message: format!("subscription to source {} not created", body.source_id),
+                        code: ApiCode::COMMON_FEED_ERROR,
+                    })?;
+                SubscriptionCreateResult::AlreadySubscribed { id: existing.id }
+            }
+        };
+    if let Some(folder) = folder {
+        subscription_folders::set(&state.conn, user.id, body.source_id, Some(&folder))
+            .await
+            .context(DbErrSnafu {
+                stage: "set-subscription-folder",
+                code: ApiCode::COMMON_DATABASE_ERROR,
+            })?;
+    }
+    CachedUserContext::new(&state).invalidate(user.id).await;
 
-    Ok(ApiResponse::data(id))
+    Ok(ApiResponse::data(result))
 }
 
 #[utoipa::path(
@@ -414,6 +700,7 @@ To get subscription IDs, first call `GET /subscriptions`.
 Returns `true` if the deletion was successful.
 
 ## Behavior
+- Only the user's own subscriptions can be deleted: another user's subscription id is answered with `404`, exactly like an unknown one
 - Removes the specified subscription record
 - User will no longer receive papers from this source
 - Does not affect other users' subscriptions to the same source
@@ -426,8 +713,8 @@ Returns `true` if the deletion was successful.
 - Clean up subscriptions
 
 ## Error Handling
-- If subscription ID doesn't exist: Operation may succeed silently
-- If subscription belongs to another user: Typical database constraints apply
+- If subscription ID doesn't exist: `404`
+- If subscription belongs to another user: `404`, and nothing is deleted
 
 ## Example Workflow
 1. Call `GET /subscriptions` to get subscription list
@@ -446,7 +733,7 @@ Returns `true` if the deletion was successful.
     responses(
         (status = 200, description = "Subscription deleted successfully, returns true", body = bool),
         (status = 401, description = "Unauthorized - valid authentication required"),
-        (status = 404, description = "Subscription not found"),
+        (status = 404, description = "The user has no such subscription"),
         (status = 500, description = "Database error"),
     ),
     tag = FEED_TAG,
@@ -454,6 +741,7 @@ Returns `true` if the deletion was successful.
 pub async fn subscriptions_delete_one(
     State(state): State<AppState>,
     User(user): User,
+    request_id: Option<Extension<RequestId>>,
     Path(subscription_id): Path<i64>,
 ) -> Result<ApiResponse<bool>, ApiError> {
     tracing::info!(
@@ -462,12 +750,103 @@ pub async fn subscriptions_delete_one(
         "delete one subscription"
     );
 
-    RssSubscriptionsQuery::delete_by_id(&state.conn, subscription_id)
+    let deleted = user_subscriptions::delete_by_id_and_user(&state.conn, subscription_id, user.id)
         .await
         .context(DbErrSnafu {
             stage: "delete-one-rss-subscription",
             code: ApiCode::COMMON_DATABASE_ERROR,
         })?;
+    if deleted == 0 {
+        return Err(ApiError::CustomError {
+            message: format!("subscription {subscription_id} not found"),
+            code: RESOURCE_NOT_FOUND,
+        });
+    }
+    CachedUserContext::new(&state).invalidate(user.id).await;
+    record_audit(
+        &state,
+        &user,
+        request_id.as_deref(),
+        AuditAction::SubscriptionDelete,
+        &[subscription_id],
+        serde_json::json!({}),
+    )
+    .await;
 
     Ok(ApiResponse::data(true))
 }
+
+#[utoipa::path(
+    patch,
+    path = "/subscriptions/{subscription_id}/folder",
+    summary = "Move a subscription to a folder",
+    description = r#"
+Put one of the user's subscriptions in a folder, or take it out of its folder with `null`.
+
+Folders are the user's own grouping of their subscriptions, independent of the global RSS tree; they show up as `folder` in `GET /subscriptions` and as groups in `GET /user_rss?group_by_folder=true`. A folder exists as long as one subscription is in it.
+
+## Request Body
+```json
+{
+  "folder": "Reading list"
+}
+```
+Folder names are trimmed (blank means `null`) and limited to 255 characters.
+"#,
+    params(
+        ("subscription_id" = i64, Path, description = "The subscription record ID (not the source ID)"),
+    ),
+    request_body = SubscriptionFolderRequest,
+    responses(
+        (status = 200, description = "The updated subscription", body = SubscriptionWithFolder),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 404, description = "The user has no such subscription"),
+        (status = 500, description = "Database error"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn subscription_move_folder(
+    State(state): State<AppState>,
+    User(user): User,
+    Path(subscription_id): Path<i64>,
+    Json(body): Json<SubscriptionFolderRequest>,
+) -> Result<ApiResponse<SubscriptionWithFolder>, ApiError> {
+    tracing::info!(
+        user_id = user.id,
+        subscription_id,
+        folder = ?body.folder,
+        "move subscription to folder"
+    );
+    let folder = normalize_folder(body.folder)?;
+
+    let subscription = RssSubscriptionsQuery::list_by_user_id(&state.conn, user.id, None)
+        .await
+        .context(DbErrSnafu {
+            stage: "get-rss-subscriptions",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?
+        .into_iter()
+        .find(|subscription| subscription.id == subscription_id)
+        .ok_or_else(|| ApiError::CustomError {
+            message: format!("subscription {subscription_id} not found"),
+            code: RESOURCE_NOT_FOUND,
+        })?;
+
+    subscription_folders::set(
+        &state.conn,
+        user.id,
+        subscription.source_id,
+        folder.as_deref(),
+    )
+    .await
+    .context(DbErrSnafu {
+        stage: "set-subscription-folder",
+        code: ApiCode::COMMON_DATABASE_ERROR,
+    })?;
+    CachedUserContext::new(&state).invalidate(user.id).await;
+
+    Ok(ApiResponse::data(SubscriptionWithFolder {
+        subscription,
+        folder,
+    }))
+}
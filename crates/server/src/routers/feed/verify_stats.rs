@@ -0,0 +1,206 @@
+use axum::extract::State;
+use chrono::Utc;
+use common::{error::api_error::*, prelude::ApiCode};
+use feed::services::VerifyService;
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+use utoipa::ToSchema;
+
+use super::FEED_TAG;
+use crate::{
+    config::server_rss_config,
+    middlewares::{auth::User, query::Query},
+    model::{
+        base::ApiResponse,
+        usage::window_bounds,
+        verify_stats::{VerifyStats, verify_stats_window},
+    },
+    query::verify_stats::aggregate_stats,
+    state::{app_state::AppState, user_context::CachedUserContext},
+};
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct MatchRateParams {
+    /// Token budget used to recommend a `max_match_limit_per_user`
+    pub token_budget: Option<i64>,
+}
+
+/// Match rate of one verification run
+#[derive(Debug, Serialize, ToSchema, Clone, Copy, PartialEq)]
+pub struct MatchRateSample {
+    pub matched: i64,
+    /// success + fail
+    pub completed: i64,
+    pub token_usage: i64,
+    /// matched / completed, `None` when nothing completed yet
+    pub match_rate: Option<f64>,
+    /// token_usage / matched, `None` when nothing matched yet
+    pub avg_tokens_per_match: Option<f64>,
+}
+
+impl MatchRateSample {
+    pub fn from_counts(matched: i64, success: i64, fail: i64, token_usage: i64) -> Self {
+        let completed = success + fail;
+        MatchRateSample {
+            matched,
+            completed,
+            token_usage,
+            match_rate: (completed > 0).then(|| matched as f64 / completed as f64),
+            avg_tokens_per_match: (matched > 0).then(|| token_usage as f64 / matched as f64),
+        }
+    }
+
+    /// Largest match limit whose expected token spend stays within `token_budget`
+    pub fn recommend_max_match_limit(&self, token_budget: i64) -> Option<i64> {
+        let avg = self.avg_tokens_per_match?;
+        if avg <= 0.0 || token_budget <= 0 {
+            return None;
+        }
+        Some((token_budget as f64 / avg).floor() as i64)
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MatchRateResponse {
+    /// Live numbers of the user's current (or most recent, not yet expired) run
+    pub current_run: Option<MatchRateSample>,
+    /// Configured default for `max_match_limit_per_user`
+    pub default_max_match_limit: i64,
+    /// Present when `token_budget` is given and the run has matched at least one paper
+    pub recommended_max_match_limit: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/verify/match-rate",
+    summary = "Get the user's verification match rate",
+    description = r#"
+Return the match rate of the authenticated user's current verification run to help tune `max_match_limit_per_user`.
+
+## Query Parameters
+- `token_budget` (optional): Target token budget. When provided, the response recommends the largest `max_match_limit_per_user` whose expected spend stays within it.
+
+## Returns
+- `current_run`: `matched`, `completed` (success + fail), `token_usage`, `match_rate` (matched / completed) and `avg_tokens_per_match`. `null` when the user has no run in Redis.
+- `default_max_match_limit`: The configured default limit
+- `recommended_max_match_limit`: `floor(token_budget / avg_tokens_per_match)`, or `null` when it can't be computed
+
+Rates are `null` while nothing has completed (or matched), never `NaN`.
+
+## Note
+Only the current run is reported; its Redis statistics expire after the run finishes.
+"#,
+    params(MatchRateParams),
+    responses(
+        (status = 200, body = MatchRateResponse, description = "Successfully computed match rate"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 500, description = "Failed to read verification statistics"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn match_rate(
+    State(state): State<AppState>,
+    User(user): User,
+    Query(params): Query<MatchRateParams>,
+) -> Result<ApiResponse<MatchRateResponse>, ApiError> {
+    tracing::info!(user_id = user.id, "get verify match rate");
+
+    let verify_service = VerifyService::new(
+        state.redis.clone().pool,
+        state.conn.clone(),
+        state.redis.pubsub_manager.clone(),
+        state.config.rss.feed_redis.redis_prefix.clone(),
+        state.config.rss.feed_redis.redis_key_default_expire,
+        state.config.rss.verify_papers_channel.clone(),
+    )
+    .await;
+
+    let statistics = verify_service
+        .get_user_verify_statistics(user.id, None)
+        .await
+        .map_err(|e| ApiError::CustomError {
+            message: format!("get_user_verify_statistics: {e}"),
+            code: ApiCode::COMMON_FEED_ERROR,
+        })?;
+    let info = statistics.verify_info;
+
+    let current_run = (info.total > 0).then(|| {
+        MatchRateSample::from_counts(
+            info.matched_count,
+            info.success_count,
+            info.fail_count,
+            info.token_usage,
+        )
+    });
+    let recommended_max_match_limit = match (current_run, params.token_budget) {
+        (Some(sample), Some(budget)) => sample.recommend_max_match_limit(budget),
+        _ => None,
+    };
+
+    Ok(ApiResponse::data(MatchRateResponse {
+        current_run,
+        default_max_match_limit: state.config.rss.max_match_limit_per_user as i64,
+        recommended_max_match_limit,
+    }))
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct VerifyStatsParams {
+    /// Days to report, ending today (UTC); 30 by default, at most `verify_stats_max_days`
+    pub days: Option<u32>,
+    /// Only count papers of sources in this channel
+    pub channel: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/verify-stats",
+    summary = "Get the user's verification statistics",
+    description = r#"
+Historical verification statistics of the authenticated user over a window ending today.
+
+## Query Parameters
+- `days` (optional): Length of the window in days, 30 by default. At most `verify_stats_max_days` (90 by default).
+- `channel` (optional): Only count papers of sources in this channel.
+
+## Returns
+- `from`, `to`: First and last day of the window
+- `verified`, `matched`, `match_rate`: Totals over the window; `match_rate` is matched / verified, `null` when nothing was verified
+- `daily`: One entry per day, oldest first, days without verifications included as zero
+- `interests`: Per-interest totals and match rate, most matched first. `interest` is `null` for interests removed since.
+
+## Note
+Days are UTC days: a verification counts on the UTC date it was made, whatever the user's timezone.
+Token spend is not recorded per verification, see `/verify/match-rate` for the current run.
+"#,
+    params(VerifyStatsParams),
+    responses(
+        (status = 200, body = VerifyStats, description = "Verification statistics"),
+        (status = 400, description = "Invalid days"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 500, description = "Database error"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn verify_stats(
+    State(state): State<AppState>,
+    User(user): User,
+    Query(params): Query<VerifyStatsParams>,
+) -> Result<ApiResponse<VerifyStats>, ApiError> {
+    let days = verify_stats_window(params.days, server_rss_config().verify_stats_max_days)?;
+    let channel = params.channel.as_deref().filter(|c| !c.is_empty());
+    tracing::info!(user_id = user.id, days, channel, "get verify stats");
+
+    let (from, to) = window_bounds(Utc::now().date_naive(), days);
+    let rows = aggregate_stats(state.read_conn_for(user.id).await, user.id, from, channel)
+        .await
+        .context(DbErrSnafu {
+            stage: "aggregate-verify-stats",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+    let interests = CachedUserContext::new(&state).interests(user.id).await?;
+
+    Ok(ApiResponse::data(VerifyStats::build(
+        from, to, &rows, &interests,
+    )))
+}
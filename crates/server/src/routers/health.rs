@@ -1,8 +1,17 @@
-use axum::response::IntoResponse;
+use std::{
+    fmt::Display,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse};
 use common::{error::api_error::ApiError, prelude::ApiCode};
+use sea_orm::{ConnectionTrait, DatabaseConnection};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use utoipa_axum::{router::OpenApiRouter, routes};
 
-use crate::state::app_state::AppState;
+use crate::{model::base::ApiResponse, state::app_state::AppState};
 
 #[utoipa::path(
     get,
@@ -58,8 +67,227 @@ pub async fn health() -> &'static str {
     "ok"
 }
 
+/// Time a dependency check may take before the dependency counts as down
+pub const CHECK_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// How long a readiness result is reused, so frequent probes do not hammer the dependencies
+const READY_CACHE_TTL: Duration = Duration::from_secs(2);
+
+static READY_CACHE: Mutex<Option<(Instant, Readiness)>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Up,
+    Down,
+}
+
+/// Result of pinging one dependency
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DependencyCheck {
+    pub status: CheckStatus,
+    pub latency_ms: u64,
+    /// Why the dependency is down
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Run `check`, reporting the dependency down when it fails or takes longer than `timeout`
+pub async fn run_check<E: Display>(
+    timeout: Duration,
+    check: impl Future<Output = Result<(), E>>,
+) -> DependencyCheck {
+    let started = Instant::now();
+    let error = match tokio::time::timeout(timeout, check).await {
+        Ok(Ok(())) => None,
+        Ok(Err(e)) => Some(e.to_string()),
+        Err(_) => Some(format!("no answer within {}ms", timeout.as_millis())),
+    };
+    DependencyCheck {
+        status: if error.is_none() {
+            CheckStatus::Up
+        } else {
+            CheckStatus::Down
+        },
+        latency_ms: started.elapsed().as_millis() as u64,
+        error,
+    }
+}
+
+pub async fn check_database(conn: &DatabaseConnection, timeout: Duration) -> DependencyCheck {
+    run_check(timeout, async {
+        conn.execute_unprepared("SELECT 1").await.map(|_| ())
+    })
+    .await
+}
+
+pub async fn check_redis_pool(
+    pool: &bb8::Pool<bb8_redis::RedisConnectionManager>,
+    timeout: Duration,
+) -> DependencyCheck {
+    run_check(timeout, async {
+        let mut conn = pool.get().await.map_err(|e| e.to_string())?;
+        redis::cmd("PING")
+            .query_async::<String>(&mut *conn)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    })
+    .await
+}
+
+pub async fn check_apalis_redis(
+    conn: &apalis_redis::ConnectionManager,
+    timeout: Duration,
+) -> DependencyCheck {
+    let mut conn = conn.clone();
+    run_check(timeout, async move {
+        redis::cmd("PING")
+            .query_async::<String>(&mut conn)
+            .await
+            .map(|_| ())
+    })
+    .await
+}
+
+/// Usage of the database connection pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct PoolStats {
+    pub max_connections: u32,
+    /// Open connections, idle or not
+    pub size: u32,
+    pub idle: u32,
+    /// Connections checked out; at `max_connections`, further queries wait for one
+    pub in_use: u32,
+}
+
+pub fn database_pool_stats(conn: &DatabaseConnection) -> PoolStats {
+    let pool = conn.get_postgres_connection_pool();
+    let size = pool.size();
+    let idle = pool.num_idle() as u32;
+    PoolStats {
+        max_connections: pool.options().get_max_connections(),
+        size,
+        idle,
+        in_use: size.saturating_sub(idle),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Readiness {
+    /// Whether every check passed
+    pub ready: bool,
+    /// Migrations not yet applied to the database
+    pub pending_migrations: Vec<String>,
+    /// `SELECT 1` on the database
+    pub database: DependencyCheck,
+    /// `PING` through the Redis pool
+    pub redis: DependencyCheck,
+    /// `PING` on the job queue connection
+    pub apalis_redis: DependencyCheck,
+    /// Database connection pool usage, informational (does not affect `ready`)
+    pub database_pool: PoolStats,
+}
+
+async fn check_readiness(state: &AppState) -> Readiness {
+    let pending = async {
+        match tokio::time::timeout(CHECK_TIMEOUT, migration::pending_migrations(&state.conn)).await
+        {
+            Ok(Ok(pending)) => pending,
+            Ok(Err(e)) => {
+                tracing::warn!(error = %e, "failed to list pending migrations");
+                vec!["<unknown: migration table unreadable>".to_string()]
+            }
+            Err(_) => vec!["<unknown: migration table unreadable>".to_string()],
+        }
+    };
+    let (pending_migrations, database, redis, apalis_redis) = tokio::join!(
+        pending,
+        check_database(&state.conn, CHECK_TIMEOUT),
+        check_redis_pool(&state.redis.pool, CHECK_TIMEOUT),
+        check_apalis_redis(&state.redis.apalis_conn, CHECK_TIMEOUT),
+    );
+    let ready = pending_migrations.is_empty()
+        && [&database, &redis, &apalis_redis]
+            .iter()
+            .all(|check| check.status == CheckStatus::Up);
+    Readiness {
+        ready,
+        pending_migrations,
+        database,
+        redis,
+        apalis_redis,
+        database_pool: database_pool_stats(&state.conn),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    summary = "Readiness check endpoint",
+    description = r#"
+Check whether the server can serve traffic.
+
+## Checks
+- **Migrations**: every migration of the `migration` crate has been applied. Apply them with `cargo run -p migration -- up` or by starting the server with `--migrate`.
+- **database**: `SELECT 1` on Postgres
+- **redis**: `PING` through the Redis pool
+- **apalis_redis**: `PING` on the job queue connection
+
+Each dependency reports `status` (`up` / `down`), `latency_ms` and, when down, `error`. A check taking longer than 500ms counts as down, so a hung dependency cannot stall the probe.
+
+`database_pool` reports the connection pool (`max_connections`, `size`, `idle`, `in_use`). It does not affect readiness; `in_use` stuck at `max_connections` explains "connection pool timed out" errors.
+
+## Response
+Returns 200 with `ready: true` when every check passes, 503 with `ready: false` otherwise. A failing check is reported in its field (e.g. `pending_migrations` lists the missing migrations).
+
+The result is reused for 2 seconds, so frequent probes do not hammer the dependencies.
+
+## Use Cases
+- Kubernetes readiness probes (use `/health` for liveness)
+- Deployment gates after a schema change
+"#,
+    responses(
+        (status = 200, body = Readiness, description = "Server is ready"),
+        (status = 503, body = Readiness, description = "A readiness check failed"),
+    ),
+    tag = "Common"
+)]
+pub async fn ready(State(state): State<AppState>) -> (StatusCode, ApiResponse<Readiness>) {
+    let cached = READY_CACHE
+        .lock()
+        .unwrap()
+        .as_ref()
+        .filter(|(checked_at, _)| checked_at.elapsed() < READY_CACHE_TTL)
+        .map(|(_, readiness)| readiness.clone());
+    let readiness = match cached {
+        Some(readiness) => readiness,
+        None => {
+            let readiness = check_readiness(&state).await;
+            *READY_CACHE.lock().unwrap() = Some((Instant::now(), readiness.clone()));
+            readiness
+        }
+    };
+    let ready = readiness.ready;
+    let (status, message) = if ready {
+        (StatusCode::OK, "Success")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "Not ready")
+    };
+    (
+        status,
+        ApiResponse {
+            data: readiness,
+            success: ready,
+            message: message.to_string(),
+        },
+    )
+}
+
 pub fn health_routers() -> OpenApiRouter<AppState> {
-    OpenApiRouter::new().routes(routes!(health))
+    OpenApiRouter::new()
+        .routes(routes!(health))
+        .routes(routes!(ready))
 }
 
 /// 404 handler
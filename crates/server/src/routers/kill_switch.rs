@@ -0,0 +1,90 @@
+use axum::{Json, extract::State};
+use common::error::api_error::ApiError;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::{
+    middlewares::auth::User,
+    model::base::ApiResponse,
+    state::{
+        app_state::AppState,
+        kill_switch::{KillSwitch, KillSwitchState, KillSwitches},
+    },
+};
+
+#[utoipa::path(
+    get,
+    path = "/admin/kill-switches",
+    summary = "List kill switches",
+    description = r#"
+List the operational kill switches and who flipped them last.
+
+## Switches
+- `verify_dispatch`: `POST /verify` and `POST /stream-verify` refuse to queue verification (503)
+- `rss_pull`: the RSS pull worker skips its cycles
+- `webhooks`: webhook delivery pauses
+- `sse_streams`: `POST /stream-verify` answers with a single `error` event
+
+`engaged: true` means the subsystem is disabled. Switches are read with a few seconds of in-process caching, and count as released when Redis is unreachable.
+"#,
+    responses(
+        (status = 200, body = Vec<KillSwitchState>, description = "State of every switch"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 403, description = "Admin capability required"),
+        (status = 500, description = "Redis error"),
+    ),
+    tag = "Admin",
+)]
+pub async fn list_kill_switches(
+    State(state): State<AppState>,
+) -> Result<ApiResponse<Vec<KillSwitchState>>, ApiError> {
+    Ok(ApiResponse::data(KillSwitches::new(&state).list().await?))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetKillSwitchRequest {
+    pub switch: KillSwitch,
+    /// `true` disables the subsystem, `false` re-enables it
+    pub engaged: bool,
+    /// Why, kept with the switch and in the audit log
+    pub reason: Option<String>,
+}
+
+#[utoipa::path(
+    put,
+    path = "/admin/kill-switches",
+    summary = "Flip a kill switch",
+    description = r#"
+Engage (disable the subsystem) or release a kill switch. Takes effect within a few seconds on every instance, without a redeploy.
+
+## Request Body
+```json
+{ "switch": "verify_dispatch", "engaged": true, "reason": "LLM provider outage" }
+```
+
+The caller, time and reason are stored with the switch and written to the audit log.
+"#,
+    request_body = SetKillSwitchRequest,
+    responses(
+        (status = 200, body = KillSwitchState, description = "New state of the switch"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 403, description = "Admin capability required"),
+        (status = 500, description = "Redis error"),
+    ),
+    tag = "Admin",
+)]
+pub async fn set_kill_switch(
+    State(state): State<AppState>,
+    User(user): User,
+    Json(payload): Json<SetKillSwitchRequest>,
+) -> Result<ApiResponse<KillSwitchState>, ApiError> {
+    let updated = KillSwitches::new(&state)
+        .set(payload.switch, payload.engaged, user.id, payload.reason)
+        .await?;
+    Ok(ApiResponse::data(updated))
+}
+
+pub fn kill_switch_routers() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new().routes(routes!(list_kill_switches, set_kill_switch))
+}
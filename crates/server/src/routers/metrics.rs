@@ -0,0 +1,36 @@
+use axum::http::header::CONTENT_TYPE;
+use axum::response::IntoResponse;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::state::{app_state::AppState, metrics::metrics};
+
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    summary = "Prometheus metrics",
+    description = r#"
+Metrics of this server replica in the Prometheus text format, for scraping.
+
+## Metrics
+- `http_requests_total{method, route, status}`: requests by route template (e.g. `/rss/{id}`) and status class (`2xx`, `4xx`, ...); unknown paths are labelled `unmatched`
+- `http_request_duration_seconds{method, route}`: request latency histogram
+- `verify_jobs_dispatched_total`: verification jobs queued by `/verify` and the verify schedules
+- `sse_connections_open`: `/stream-verify` connections currently open
+
+No authentication is required; restrict access at the ingress.
+"#,
+    responses(
+        (status = 200, description = "Metrics in the Prometheus text format", body = String, content_type = "text/plain"),
+    ),
+    tag = "Common"
+)]
+pub async fn metrics_text() -> impl IntoResponse {
+    (
+        [(CONTENT_TYPE, "text/plain; version=0.0.4")],
+        metrics().encode(),
+    )
+}
+
+pub fn metrics_routers() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new().routes(routes!(metrics_text))
+}
@@ -1,2 +1,7 @@
+pub mod audit;
+pub mod authz;
 pub mod feed;
 pub mod health;
+pub mod kill_switch;
+pub mod metrics;
+pub mod usage;
@@ -0,0 +1,116 @@
+use axum::extract::{Path, State};
+use chrono::Utc;
+use common::{error::api_error::*, prelude::ApiCode};
+use serde::Deserialize;
+use snafu::ResultExt;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::{
+    middlewares::{auth::User, query::Query},
+    model::{
+        base::ApiResponse,
+        usage::{UsageReport, parse_window, window_bounds},
+    },
+    query::usage::daily_usage,
+    routers::feed::FEED_TAG,
+    state::app_state::AppState,
+};
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct UsageParams {
+    /// Days to report, ending today (UTC), e.g. `30d` (default) or `7d`; at most `366d`
+    pub window: Option<String>,
+}
+
+async fn usage_report(
+    state: &AppState,
+    user_id: i64,
+    window: Option<&str>,
+) -> Result<UsageReport, ApiError> {
+    let days = parse_window(window)?;
+    let (from, to) = window_bounds(Utc::now().date_naive(), days);
+
+    let mut counts = daily_usage(&state.conn, user_id, from, to)
+        .await
+        .context(DbErrSnafu {
+            stage: "daily-usage",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+    // days not rolled up yet
+    let window_days: Vec<_> = from.iter_days().take_while(|day| *day <= to).collect();
+    match state.usage.pending(user_id, &window_days).await {
+        Ok(pending) => counts.extend(pending),
+        Err(e) => tracing::warn!(user_id, error = ?e, "usage: pending counters unavailable"),
+    }
+    Ok(UsageReport::build(user_id, from, to, &counts))
+}
+
+#[utoipa::path(
+    get,
+    path = "/usage",
+    summary = "Get my API usage",
+    description = r#"
+Daily API usage of the authenticated user over a window ending today (UTC).
+
+## Event Types
+- `verify_run`: verification runs queued via `/verify` or `/stream-verify`
+- `sse_seconds`: seconds connected to `/stream-verify`
+- `export`, `webhook_delivery`, `rate_limited`: reserved, reported as zero until those features record them
+
+## Returns
+One series per event type with a zero-filled entry per day and the window total. Counts of the last seconds may not be visible yet: events are buffered and flushed about once a second.
+"#,
+    params(UsageParams),
+    responses(
+        (status = 200, body = UsageReport, description = "Daily usage series"),
+        (status = 400, description = "Invalid window"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 500, description = "Database error"),
+    ),
+    tag = FEED_TAG,
+)]
+pub async fn my_usage(
+    State(state): State<AppState>,
+    User(user): User,
+    Query(params): Query<UsageParams>,
+) -> Result<ApiResponse<UsageReport>, ApiError> {
+    Ok(ApiResponse::data(
+        usage_report(&state, user.id, params.window.as_deref()).await?,
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/usage/{user_id}",
+    summary = "Get a user's API usage",
+    description = r#"
+Same report as `GET /usage`, for any user. Intended for support.
+"#,
+    params(
+        ("user_id" = i64, Path, description = "User to report on"),
+        UsageParams,
+    ),
+    responses(
+        (status = 200, body = UsageReport, description = "Daily usage series"),
+        (status = 400, description = "Invalid window"),
+        (status = 401, description = "Unauthorized - valid authentication required"),
+        (status = 403, description = "Admin capability required"),
+        (status = 500, description = "Database error"),
+    ),
+    tag = "Admin",
+)]
+pub async fn user_usage(
+    State(state): State<AppState>,
+    Path(user_id): Path<i64>,
+    Query(params): Query<UsageParams>,
+) -> Result<ApiResponse<UsageReport>, ApiError> {
+    Ok(ApiResponse::data(
+        usage_report(&state, user_id, params.window.as_deref()).await?,
+    ))
+}
+
+pub fn usage_routers() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(my_usage))
+        .routes(routes!(user_usage))
+}
@@ -3,16 +3,27 @@ use std::sync::Arc;
 use axum::extract::FromRef;
 use conf::config::{AppConfig, app_config};
 use feed::redis::pubsub::RedisPubSubManager;
-use sea_orm::DatabaseConnection;
+use redis::AsyncCommands;
+use sea_orm::{Database, DatabaseConnection};
 use seaorm_db::connection::get_db;
 use tokio::signal::{self, unix::SignalKind};
 use tracing::*;
 
+use super::usage::UsageRecorder;
+use crate::config::{AuthzConfig, authz_config, read_replica_config};
+
 #[derive(Clone)]
 pub struct AppState {
+    /// The primary: every write, and every read that must see them
     pub conn: DatabaseConnection,
+    /// Read replica of the heavy listings, when `database.read_url` is set; see `read_conn_for`
+    pub replica: Option<DatabaseConnection>,
+    /// Seconds a user reads from the primary after a request that may have written
+    pub read_after_write_secs: u64,
     pub redis: RedisService,
     pub config: Arc<AppConfig>,
+    pub authz: Arc<AuthzConfig>,
+    pub usage: UsageRecorder,
 }
 
 #[derive(Clone)]
@@ -24,17 +35,82 @@ pub struct RedisService {
 impl AppState {
     pub async fn new() -> Self {
         let config = app_config();
+        let pool = connect_redis(&config.rss.feed_redis).await;
+        let replica_config = read_replica_config();
+        let replica = match replica_config.connect_options() {
+            Some(options) => Some(
+                Database::connect(options)
+                    .await
+                    .expect("Could not connect the database read replica"),
+            ),
+            None => None,
+        };
         AppState {
             conn: get_db().await.clone(),
+            replica,
+            read_after_write_secs: replica_config.read_after_write_secs,
+            usage: UsageRecorder::start(pool.clone(), &config.rss.feed_redis.redis_prefix),
             redis: RedisService {
-                pool: connect_redis(&config.rss.feed_redis).await,
+                pool,
                 apalis_conn: apalis_redis::connect(config.rss.feed_redis.url.as_str())
                     .await
                     .expect("Could not connect redis"),
                 pubsub_manager: RedisPubSubManager::new(config.rss.feed_redis.url.as_str()).await,
             },
             config,
+            authz: Arc::new(authz_config()),
+        }
+    }
+
+    /// Connection of read-only listings: the replica when configured, else the primary.
+    ///
+    /// Replicas lag, so a handler that writes must not use it, nor read its own writes through it.
+    /// Listings of a user go through `read_conn_for` instead.
+    pub fn read_conn(&self) -> &DatabaseConnection {
+        self.replica.as_ref().unwrap_or(&self.conn)
+    }
+
+    /// `read_conn` for the listings of `user_id`, except during the `read_after_write_secs`
+    /// following a request of theirs that may have written (see `mark_write`): those read the
+    /// primary, so the user sees their own writes. Redis failures read the primary too.
+    pub async fn read_conn_for(&self, user_id: i64) -> &DatabaseConnection {
+        let Some(replica) = &self.replica else {
+            return &self.conn;
+        };
+        let recent_write = match self.redis.pool.get().await {
+            Ok(mut conn) => conn.exists(self.write_key(user_id)).await.unwrap_or(true),
+            Err(_) => true,
+        };
+        if recent_write { &self.conn } else { replica }
+    }
+
+    /// Route the listings of `user_id` to the primary for the next `read_after_write_secs`
+    pub async fn mark_write(&self, user_id: i64) {
+        if self.replica.is_none() {
+            return;
         }
+        match self.redis.pool.get().await {
+            Ok(mut conn) => {
+                let result: redis::RedisResult<()> = conn
+                    .set_ex(
+                        self.write_key(user_id),
+                        1,
+                        self.read_after_write_secs.max(1),
+                    )
+                    .await;
+                if let Err(e) = result {
+                    warn!(user_id, error = %e, "read after write: failed to mark");
+                }
+            }
+            Err(e) => warn!(user_id, error = %e, "read after write: redis unavailable"),
+        }
+    }
+
+    fn write_key(&self, user_id: i64) -> String {
+        format!(
+            "{}:read-after-write:{user_id}",
+            self.config.rss.feed_redis.redis_prefix
+        )
     }
 }
 
@@ -44,7 +120,7 @@ impl FromRef<AppState> for DatabaseConnection {
     }
 }
 
-pub async fn graceful_shutdown(_state: AppState) {
+pub async fn graceful_shutdown(state: AppState) {
     // Wait for Ctrl+C signal
     tokio::select! {
         _ = signal::ctrl_c() => {
@@ -58,6 +134,7 @@ pub async fn graceful_shutdown(_state: AppState) {
         } => {}
     }
 
+    state.usage.flush().await;
     info!("Bye");
 }
 
@@ -0,0 +1,173 @@
+use common::{error::api_error::*, prelude::ApiCode};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+
+use super::app_state::AppState;
+
+/// Successful response of the first request with an idempotency key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+/// What is stored under an idempotency key, with the hash of the first request's body
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+enum IdempotencyEntry {
+    /// The first request with the key is still running
+    InFlight { request_hash: String },
+    /// The first request succeeded
+    Completed {
+        request_hash: String,
+        #[serde(flatten)]
+        response: StoredResponse,
+    },
+}
+
+impl IdempotencyEntry {
+    fn matches(&self, hash: &str) -> bool {
+        let (IdempotencyEntry::InFlight { request_hash }
+        | IdempotencyEntry::Completed { request_hash, .. }) = self;
+        request_hash == hash
+    }
+}
+
+/// Outcome of claiming an idempotency key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdempotencyClaim {
+    /// First use of the key: run the request
+    Claimed,
+    /// A request with the key is running
+    InFlight,
+    /// A request with the key succeeded: replay its response
+    Completed(StoredResponse),
+    /// The key was used with a different request body
+    Mismatch,
+}
+
+/// Fingerprint of a request body, compared on every use of its idempotency key
+pub fn request_hash(body: &[u8]) -> String {
+    format!("{:x}", md5::compute(body))
+}
+
+/// Responses of requests sent with an `Idempotency-Key`, stored under
+/// `{redis_prefix}:idempotency:{user_id}:{route}:{key}`
+pub struct IdempotencyStore<'a> {
+    state: &'a AppState,
+}
+
+fn redis_error(stage: &str, e: impl std::fmt::Display) -> ApiError {
+    ApiError::CustomError {
+        message: format!("{stage}: {e}"),
+        code: ApiCode::COMMON_FEED_ERROR,
+    }
+}
+
+impl<'a> IdempotencyStore<'a> {
+    pub fn new(state: &'a AppState) -> Self {
+        IdempotencyStore { state }
+    }
+
+    /// `route` is the method and path template, e.g. `POST /rss`
+    pub fn key(&self, user_id: i64, route: &str, key: &str) -> String {
+        format!(
+            "{}:idempotency:{user_id}:{route}:{key}",
+            self.state.config.rss.feed_redis.redis_prefix
+        )
+    }
+
+    /// Mark `key` in flight for `in_flight_ttl_secs` unless it is already used; `request_hash`
+    /// fingerprints the request body, a later request with another body is a `Mismatch`
+    pub async fn claim(
+        &self,
+        key: &str,
+        request_hash: &str,
+        in_flight_ttl_secs: u64,
+    ) -> Result<IdempotencyClaim, ApiError> {
+        let mut conn = self
+            .state
+            .redis
+            .pool
+            .get()
+            .await
+            .map_err(|e| redis_error("claim-idempotency-key", e))?;
+        let in_flight = serde_json::to_string(&IdempotencyEntry::InFlight {
+            request_hash: request_hash.to_string(),
+        })
+        .expect("entry serializes");
+        let claimed: Option<String> = redis::cmd("SET")
+            .arg(key)
+            .arg(in_flight)
+            .arg("NX")
+            .arg("EX")
+            .arg(in_flight_ttl_secs.max(1))
+            .query_async(&mut *conn)
+            .await
+            .map_err(|e| redis_error("claim-idempotency-key", e))?;
+        if claimed.is_some() {
+            return Ok(IdempotencyClaim::Claimed);
+        }
+        let stored: Option<String> = conn
+            .get(key)
+            .await
+            .map_err(|e| redis_error("claim-idempotency-key", e))?;
+        Ok(
+            match stored.and_then(|stored| serde_json::from_str::<IdempotencyEntry>(&stored).ok()) {
+                Some(entry) if !entry.matches(request_hash) => IdempotencyClaim::Mismatch,
+                Some(IdempotencyEntry::Completed { response, .. }) => {
+                    IdempotencyClaim::Completed(response)
+                }
+                // expired between the two commands, or unreadable: treat as running
+                _ => IdempotencyClaim::InFlight,
+            },
+        )
+    }
+
+    /// Store the response of the claimed `key` for `ttl_secs`
+    pub async fn complete(
+        &self,
+        key: &str,
+        request_hash: &str,
+        response: StoredResponse,
+        ttl_secs: u64,
+    ) -> Result<(), ApiError> {
+        let mut conn = self
+            .state
+            .redis
+            .pool
+            .get()
+            .await
+            .map_err(|e| redis_error("complete-idempotency-key", e))?;
+        let _: () = conn
+            .set_ex(
+                key,
+                serde_json::to_string(&IdempotencyEntry::Completed {
+                    request_hash: request_hash.to_string(),
+                    response,
+                })
+                .expect("entry serializes"),
+                ttl_secs.max(1),
+            )
+            .await
+            .map_err(|e| redis_error("complete-idempotency-key", e))?;
+        Ok(())
+    }
+
+    /// Free the claimed `key`, so a retry runs the request again (it failed)
+    pub async fn release(&self, key: &str) -> Result<(), ApiError> {
+        let mut conn = self
+            .state
+            .redis
+            .pool
+            .get()
+            .await
+            .map_err(|e| redis_error("release-idempotency-key", e))?;
+        let _: () = conn
+            .del(key)
+            .await
+            .map_err(|e| redis_error("release-idempotency-key", e))?;
+        Ok(())
+    }
+}
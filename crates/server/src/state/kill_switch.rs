@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use common::{error::api_error::*, prelude::ApiCode};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use utoipa::ToSchema;
+
+use super::app_state::AppState;
+use crate::consts::FEED_TEMPORARILY_DISABLED;
+
+/// How long `is_on` answers from the in-process copy before re-reading Redis
+const CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// Subsystems that can be disabled at runtime during incidents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum KillSwitch {
+    /// `POST /verify`, the verify scheduler and the dispatch done by `POST /stream-verify`
+    VerifyDispatch,
+    /// The RSS pull worker
+    RssPull,
+    /// Webhook delivery
+    Webhooks,
+    /// SSE streams (`POST /stream-verify`)
+    SseStreams,
+}
+
+impl KillSwitch {
+    pub const ALL: [KillSwitch; 4] = [
+        KillSwitch::VerifyDispatch,
+        KillSwitch::RssPull,
+        KillSwitch::Webhooks,
+        KillSwitch::SseStreams,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KillSwitch::VerifyDispatch => "verify_dispatch",
+            KillSwitch::RssPull => "rss_pull",
+            KillSwitch::Webhooks => "webhooks",
+            KillSwitch::SseStreams => "sse_streams",
+        }
+    }
+}
+
+/// Stored value of a switch (one field of the Redis hash)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct KillSwitchState {
+    pub switch: KillSwitch,
+    /// `true` means the subsystem is disabled
+    pub engaged: bool,
+    /// User who flipped it last
+    pub updated_by: Option<i64>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub reason: Option<String>,
+}
+
+impl KillSwitchState {
+    fn released(switch: KillSwitch) -> Self {
+        KillSwitchState {
+            switch,
+            engaged: false,
+            updated_by: None,
+            updated_at: None,
+            reason: None,
+        }
+    }
+}
+
+static CACHE: Mutex<Option<(Instant, HashMap<KillSwitch, bool>)>> = Mutex::new(None);
+
+fn redis_error(stage: &str, e: impl std::fmt::Display) -> ApiError {
+    ApiError::CustomError {
+        message: format!("{stage}: {e}"),
+        code: ApiCode::COMMON_FEED_ERROR,
+    }
+}
+
+/// Kill switches stored in the Redis hash `{redis_prefix}:kill-switches` (field = switch name,
+/// value = JSON `KillSwitchState`), shared by the server and the workers.
+///
+/// Checks fail open: when Redis cannot be read every switch counts as released, so the
+/// mechanism itself can never take a subsystem down.
+pub struct KillSwitches<'a> {
+    state: &'a AppState,
+}
+
+impl<'a> KillSwitches<'a> {
+    pub fn new(state: &'a AppState) -> Self {
+        KillSwitches { state }
+    }
+
+    fn key(&self) -> String {
+        format!(
+            "{}:kill-switches",
+            self.state.config.rss.feed_redis.redis_prefix
+        )
+    }
+
+    /// Whether `switch` is engaged, answered from a copy at most `CACHE_TTL` old
+    pub async fn is_engaged(&self, switch: KillSwitch) -> bool {
+        if let Some((loaded_at, engaged)) = CACHE.lock().unwrap().as_ref()
+            && loaded_at.elapsed() < CACHE_TTL
+        {
+            return engaged.get(&switch).copied().unwrap_or(false);
+        }
+        match self.list().await {
+            Ok(states) => {
+                let engaged: HashMap<_, _> = states
+                    .into_iter()
+                    .map(|state| (state.switch, state.engaged))
+                    .collect();
+                let result = engaged.get(&switch).copied().unwrap_or(false);
+                *CACHE.lock().unwrap() = Some((Instant::now(), engaged));
+                result
+            }
+            Err(e) => {
+                warn!(switch = switch.as_str(), error = %e, "kill switches unreadable, failing open");
+                false
+            }
+        }
+    }
+
+    /// `Err` (503, `FEED_TEMPORARILY_DISABLED`) when `switch` is engaged
+    pub async fn ensure_released(&self, switch: KillSwitch) -> Result<(), ApiError> {
+        if self.is_engaged(switch).await {
+            return Err(ApiError::CustomError {
+                message: format!("{} is temporarily disabled", switch.as_str()),
+                code: FEED_TEMPORARILY_DISABLED,
+            });
+        }
+        Ok(())
+    }
+
+    /// Current state of every switch, read from Redis
+    pub async fn list(&self) -> Result<Vec<KillSwitchState>, ApiError> {
+        let mut conn = self
+            .state
+            .redis
+            .pool
+            .get()
+            .await
+            .map_err(|e| redis_error("list-kill-switches", e))?;
+        let stored: HashMap<String, String> = conn
+            .hgetall(self.key())
+            .await
+            .map_err(|e| redis_error("list-kill-switches", e))?;
+        Ok(KillSwitch::ALL
+            .into_iter()
+            .map(|switch| {
+                stored
+                    .get(switch.as_str())
+                    .and_then(|raw| serde_json::from_str::<KillSwitchState>(raw).ok())
+                    .unwrap_or_else(|| KillSwitchState::released(switch))
+            })
+            .collect())
+    }
+
+    /// Engage or release `switch`, recording who did it and why
+    pub async fn set(
+        &self,
+        switch: KillSwitch,
+        engaged: bool,
+        updated_by: i64,
+        reason: Option<String>,
+    ) -> Result<KillSwitchState, ApiError> {
+        let state = KillSwitchState {
+            switch,
+            engaged,
+            updated_by: Some(updated_by),
+            updated_at: Some(Utc::now()),
+            reason,
+        };
+        let raw =
+            serde_json::to_string(&state).map_err(|e| redis_error("encode-kill-switch", e))?;
+        let mut conn = self
+            .state
+            .redis
+            .pool
+            .get()
+            .await
+            .map_err(|e| redis_error("set-kill-switch", e))?;
+        let _: () = conn
+            .hset(self.key(), switch.as_str(), raw)
+            .await
+            .map_err(|e| redis_error("set-kill-switch", e))?;
+        *CACHE.lock().unwrap() = None;
+
+        warn!(
+            target: "audit",
+            switch = switch.as_str(),
+            engaged,
+            updated_by,
+            reason = state.reason.as_deref().unwrap_or(""),
+            "kill switch flipped"
+        );
+        Ok(state)
+    }
+}
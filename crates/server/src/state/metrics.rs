@@ -0,0 +1,97 @@
+use std::sync::LazyLock;
+
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder,
+};
+
+/// Process-wide Prometheus metrics, served by `GET /metrics`
+pub struct Metrics {
+    registry: Registry,
+    /// Requests by method, matched route and status class (`2xx`, `4xx`, ...)
+    pub http_requests: IntCounterVec,
+    /// Request latency by method and matched route
+    pub http_request_duration: HistogramVec,
+    /// Verification jobs queued by `/verify` and the verify schedules
+    pub verify_jobs_dispatched: IntCounter,
+    /// `/stream-verify` connections currently open
+    pub sse_connections: IntGauge,
+}
+
+static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+pub fn metrics() -> &'static Metrics {
+    &METRICS
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+        let http_requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP requests handled"),
+            &["method", "route", "status"],
+        )
+        .expect("valid metric");
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "HTTP request latency in seconds",
+            ),
+            &["method", "route"],
+        )
+        .expect("valid metric");
+        let verify_jobs_dispatched =
+            IntCounter::new("verify_jobs_dispatched_total", "Verification jobs queued")
+                .expect("valid metric");
+        let sse_connections = IntGauge::new(
+            "sse_connections_open",
+            "stream-verify connections currently open",
+        )
+        .expect("valid metric");
+
+        registry
+            .register(Box::new(http_requests.clone()))
+            .expect("unique metric");
+        registry
+            .register(Box::new(http_request_duration.clone()))
+            .expect("unique metric");
+        registry
+            .register(Box::new(verify_jobs_dispatched.clone()))
+            .expect("unique metric");
+        registry
+            .register(Box::new(sse_connections.clone()))
+            .expect("unique metric");
+
+        Metrics {
+            registry,
+            http_requests,
+            http_request_duration,
+            verify_jobs_dispatched,
+            sse_connections,
+        }
+    }
+
+    /// Count an open SSE connection until the guard is dropped
+    pub fn sse_connection(&self) -> SseConnectionGuard {
+        self.sse_connections.inc();
+        SseConnectionGuard
+    }
+
+    /// Every metric in the Prometheus text format
+    pub fn encode(&self) -> String {
+        let mut buffer = Vec::new();
+        if let Err(e) = TextEncoder::new().encode(&self.registry.gather(), &mut buffer) {
+            tracing::warn!(error = %e, "failed to encode metrics");
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+/// Decrements `sse_connections_open` when dropped
+pub struct SseConnectionGuard;
+
+impl Drop for SseConnectionGuard {
+    fn drop(&mut self) {
+        metrics().sse_connections.dec();
+    }
+}
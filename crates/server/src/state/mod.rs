@@ -1 +1,12 @@
 pub mod app_state;
+pub mod idempotency;
+pub mod kill_switch;
+pub mod metrics;
+pub mod pending_interests;
+pub mod rate_limit;
+pub mod read_undo;
+pub mod usage;
+pub mod user_context;
+pub mod verify_job;
+pub mod verify_schedule;
+pub mod version;
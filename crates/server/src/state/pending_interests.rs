@@ -0,0 +1,140 @@
+use std::time::Duration;
+
+use common::{error::api_error::*, prelude::ApiCode};
+use redis::AsyncCommands;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use crate::{config::server_rss_config, consts::CONFLICT};
+
+/// Longest a change may hold the lock, should its server die before releasing it
+const LOCK_TTL_MS: u64 = 10_000;
+/// Longest a change waits for the one before it
+const LOCK_WAIT: Duration = Duration::from_secs(3);
+const LOCK_RETRY: Duration = Duration::from_millis(25);
+
+/// Delete the lock only while `ARGV[1]` still holds it
+const UNLOCK: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+"#;
+
+/// The interest list last queued on the `UpdateTaskManager` for a user, kept under
+/// `{redis_prefix}:interests:{user_id}:pending` for `rss.update_apply_window_secs`.
+///
+/// Updates are full replacements applied ~500ms later, so a change computed from the database
+/// would drop one queued just before it. Changes take the user's lock
+/// (`{redis_prefix}:interests:{user_id}:lock`), start from the pending list when there is one,
+/// and record the list they queue.
+pub struct PendingInterests<'a> {
+    state: &'a AppState,
+}
+
+fn redis_error(stage: &str, e: impl std::fmt::Display) -> ApiError {
+    ApiError::CustomError {
+        message: format!("{stage}: {e}"),
+        code: ApiCode::COMMON_FEED_ERROR,
+    }
+}
+
+impl<'a> PendingInterests<'a> {
+    pub fn new(state: &'a AppState) -> Self {
+        PendingInterests { state }
+    }
+
+    pub fn key(&self, user_id: i64, kind: &str) -> String {
+        format!(
+            "{}:interests:{user_id}:{kind}",
+            self.state.config.rss.feed_redis.redis_prefix
+        )
+    }
+
+    /// Wait for the user's lock; returns the token `unlock` needs. 409 when the change before
+    /// takes longer than `LOCK_WAIT`.
+    pub async fn lock(&self, user_id: i64) -> Result<String, ApiError> {
+        let token = Uuid::new_v4().to_string();
+        let mut conn = self
+            .state
+            .redis
+            .pool
+            .get()
+            .await
+            .map_err(|e| redis_error("lock-interests", e))?;
+        let started = tokio::time::Instant::now();
+        loop {
+            let locked: Option<String> = redis::cmd("SET")
+                .arg(self.key(user_id, "lock"))
+                .arg(&token)
+                .arg("NX")
+                .arg("PX")
+                .arg(LOCK_TTL_MS)
+                .query_async(&mut *conn)
+                .await
+                .map_err(|e| redis_error("lock-interests", e))?;
+            if locked.is_some() {
+                return Ok(token);
+            }
+            if started.elapsed() >= LOCK_WAIT {
+                return Err(ApiError::CustomError {
+                    message: "Another change of your interests is still being queued".to_string(),
+                    code: CONFLICT,
+                });
+            }
+            tokio::time::sleep(LOCK_RETRY).await;
+        }
+    }
+
+    pub async fn unlock(&self, user_id: i64, token: &str) {
+        let result = match self.state.redis.pool.get().await {
+            Ok(mut conn) => redis::Script::new(UNLOCK)
+                .key(self.key(user_id, "lock"))
+                .arg(token)
+                .invoke_async::<i64>(&mut *conn)
+                .await
+                .map(|_| ())
+                .map_err(|e| redis_error("unlock-interests", e)),
+            Err(e) => Err(redis_error("unlock-interests", e)),
+        };
+        if let Err(e) = result {
+            warn!(user_id, error = ?e, "failed to unlock interests, expires on its own");
+        }
+    }
+
+    /// The list queued last, while it may not be applied yet
+    pub async fn get(&self, user_id: i64) -> Result<Option<Vec<String>>, ApiError> {
+        let mut conn = self
+            .state
+            .redis
+            .pool
+            .get()
+            .await
+            .map_err(|e| redis_error("get-pending-interests", e))?;
+        let pending: Option<String> = conn
+            .get(self.key(user_id, "pending"))
+            .await
+            .map_err(|e| redis_error("get-pending-interests", e))?;
+        Ok(pending.and_then(|pending| serde_json::from_str(&pending).ok()))
+    }
+
+    pub async fn set(&self, user_id: i64, interests: &[String]) -> Result<(), ApiError> {
+        let mut conn = self
+            .state
+            .redis
+            .pool
+            .get()
+            .await
+            .map_err(|e| redis_error("set-pending-interests", e))?;
+        let _: () = conn
+            .set_ex(
+                self.key(user_id, "pending"),
+                serde_json::to_string(interests).expect("interests serialize"),
+                server_rss_config().update_apply_window_secs.max(1),
+            )
+            .await
+            .map_err(|e| redis_error("set-pending-interests", e))?;
+        Ok(())
+    }
+}
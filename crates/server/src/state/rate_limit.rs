@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use common::{error::api_error::*, prelude::ApiCode};
+use tracing::warn;
+
+use super::app_state::AppState;
+
+/// Token bucket refilled continuously at `capacity` tokens per minute.
+///
+/// The clock is Redis' own (`TIME`) so every replica sees the same bucket. The key expires once
+/// the bucket would be full again, when it is indistinguishable from a missing one.
+///
+/// Returns `{allowed, retry_after_ms}`.
+const TOKEN_BUCKET: &str = r#"
+local capacity = tonumber(ARGV[1])
+local refill_per_ms = capacity / 60000
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+local stored = redis.call('HMGET', KEYS[1], 'tokens', 'ts')
+local tokens = tonumber(stored[1]) or capacity
+local ts = tonumber(stored[2]) or now
+tokens = math.min(capacity, tokens + math.max(0, now - ts) * refill_per_ms)
+local allowed = 0
+local retry_after_ms = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+else
+    retry_after_ms = math.ceil((1 - tokens) / refill_per_ms)
+end
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'ts', now)
+redis.call('PEXPIRE', KEYS[1], math.ceil((capacity - tokens) / refill_per_ms) + 1)
+return {allowed, retry_after_ms}
+"#;
+
+/// Outcome of taking a token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateDecision {
+    Allowed,
+    /// The bucket is empty; the next token is available after `retry_after`
+    Limited {
+        retry_after: Duration,
+    },
+}
+
+/// Per-user token buckets stored under `{redis_prefix}:rate-limit:{bucket}:{user_id}`
+pub struct RateLimiter<'a> {
+    state: &'a AppState,
+}
+
+fn redis_error(stage: &str, e: impl std::fmt::Display) -> ApiError {
+    ApiError::CustomError {
+        message: format!("{stage}: {e}"),
+        code: ApiCode::COMMON_FEED_ERROR,
+    }
+}
+
+impl<'a> RateLimiter<'a> {
+    pub fn new(state: &'a AppState) -> Self {
+        RateLimiter { state }
+    }
+
+    pub fn key(&self, bucket: &str, user_id: i64) -> String {
+        format!(
+            "{}:rate-limit:{bucket}:{user_id}",
+            self.state.config.rss.feed_redis.redis_prefix
+        )
+    }
+
+    /// Take a token from the user's `bucket`, which holds `per_minute` tokens
+    pub async fn acquire(
+        &self,
+        bucket: &str,
+        user_id: i64,
+        per_minute: u32,
+    ) -> Result<RateDecision, ApiError> {
+        let mut conn = self
+            .state
+            .redis
+            .pool
+            .get()
+            .await
+            .map_err(|e| redis_error("acquire-rate-limit", e))?;
+        let (allowed, retry_after_ms): (i64, i64) = redis::Script::new(TOKEN_BUCKET)
+            .key(self.key(bucket, user_id))
+            .arg(per_minute.max(1))
+            .invoke_async(&mut *conn)
+            .await
+            .map_err(|e| redis_error("acquire-rate-limit", e))?;
+        Ok(if allowed == 1 {
+            RateDecision::Allowed
+        } else {
+            RateDecision::Limited {
+                retry_after: Duration::from_millis(retry_after_ms.max(0) as u64),
+            }
+        })
+    }
+
+    /// Like `acquire`, but lets the request through when Redis cannot be reached, so the
+    /// limiter itself can never take the endpoint down
+    pub async fn acquire_or_allow(
+        &self,
+        bucket: &str,
+        user_id: i64,
+        per_minute: u32,
+    ) -> RateDecision {
+        match self.acquire(bucket, user_id, per_minute).await {
+            Ok(decision) => decision,
+            Err(e) => {
+                warn!(bucket, user_id, error = ?e, "rate limiter unavailable, failing open");
+                RateDecision::Allowed
+            }
+        }
+    }
+}
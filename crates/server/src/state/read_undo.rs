@@ -0,0 +1,117 @@
+use common::{error::api_error::*, prelude::ApiCode};
+use redis::AsyncCommands;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use crate::{config::server_rss_config, query::mark_read_undo::ReadUndoSnapshot};
+
+/// Result of claiming an undo token
+#[derive(Debug)]
+pub enum UndoClaim {
+    Snapshot(ReadUndoSnapshot),
+    /// The token was already redeemed
+    AlreadyUsed,
+    /// Unknown token, or the undo window has passed
+    Expired,
+}
+
+/// Redis storage of "mark all as read" snapshots, keyed by user and undo token
+pub struct ReadUndoStore<'a> {
+    state: &'a AppState,
+}
+
+fn redis_error(stage: &str, e: impl std::fmt::Display) -> ApiError {
+    ApiError::CustomError {
+        message: format!("{stage}: {e}"),
+        code: ApiCode::COMMON_FEED_ERROR,
+    }
+}
+
+impl<'a> ReadUndoStore<'a> {
+    pub fn new(state: &'a AppState) -> Self {
+        ReadUndoStore { state }
+    }
+
+    fn key(&self, user_id: i64, token: &str) -> String {
+        format!(
+            "{}:mark-read-undo:{user_id}:{token}",
+            self.state.config.rss.feed_redis.redis_prefix
+        )
+    }
+
+    /// Store the snapshot for the undo window and return its token
+    pub async fn save(
+        &self,
+        user_id: i64,
+        snapshot: &ReadUndoSnapshot,
+    ) -> Result<String, ApiError> {
+        let token = Uuid::new_v4().simple().to_string();
+        let raw = serde_json::to_string(snapshot).map_err(|e| redis_error("encode-undo", e))?;
+        let mut conn = self
+            .state
+            .redis
+            .pool
+            .get()
+            .await
+            .map_err(|e| redis_error("save-undo", e))?;
+        let _: () = conn
+            .set_ex(
+                self.key(user_id, &token),
+                raw,
+                server_rss_config().mark_read_undo_window_secs,
+            )
+            .await
+            .map_err(|e| redis_error("save-undo", e))?;
+        Ok(token)
+    }
+
+    /// Claim the token once: the first call gets the snapshot, later calls get `AlreadyUsed`
+    pub async fn claim(&self, user_id: i64, token: &str) -> Result<UndoClaim, ApiError> {
+        let key = self.key(user_id, token);
+        let mut conn = self
+            .state
+            .redis
+            .pool
+            .get()
+            .await
+            .map_err(|e| redis_error("claim-undo", e))?;
+
+        let marked: Option<String> = redis::cmd("SET")
+            .arg(format!("{key}:used"))
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(server_rss_config().mark_read_undo_window_secs)
+            .query_async(&mut *conn)
+            .await
+            .map_err(|e| redis_error("claim-undo", e))?;
+        let first = marked.is_some();
+
+        let raw: Option<String> = conn
+            .get(&key)
+            .await
+            .map_err(|e| redis_error("claim-undo", e))?;
+        let Some(raw) = raw else {
+            return Ok(UndoClaim::Expired);
+        };
+        if !first {
+            return Ok(UndoClaim::AlreadyUsed);
+        }
+        let snapshot = serde_json::from_str(&raw).map_err(|e| redis_error("decode-undo", e))?;
+        Ok(UndoClaim::Snapshot(snapshot))
+    }
+
+    /// Make a claimed token redeemable again, e.g. after the restore failed
+    pub async fn release(&self, user_id: i64, token: &str) {
+        let key = format!("{}:used", self.key(user_id, token));
+        match self.state.redis.pool.get().await {
+            Ok(mut conn) => {
+                let result: redis::RedisResult<()> = conn.del(&key).await;
+                if let Err(e) = result {
+                    tracing::warn!(user_id, error = %e, "failed to release undo token");
+                }
+            }
+            Err(e) => tracing::warn!(user_id, error = %e, "failed to release undo token"),
+        }
+    }
+}
@@ -0,0 +1,408 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{NaiveDate, Utc};
+use common::{error::api_error::*, prelude::ApiCode};
+use redis::AsyncCommands;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use crate::{
+    config::usage_config,
+    query::usage::{UsageCount, apply_rollup},
+};
+
+/// Daily counters are dropped from Redis after this long, even if never rolled up
+const DAY_KEY_TTL_SECS: i64 = 35 * 24 * 60 * 60;
+
+/// Buffered counters kept while Redis is unreachable; beyond this, events are dropped
+const MAX_BUFFERED_COUNTERS: usize = 100_000;
+
+/// Lease of the rollup lock, longer than any rollup should take
+const ROLLUP_LOCK_SECS: u64 = 10 * 60;
+
+/// Event types counted per user and day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageEvent {
+    /// A verification run was queued (`/verify` or `/stream-verify`)
+    VerifyRun,
+    /// Seconds spent connected to `/stream-verify`
+    SseSeconds,
+    /// An export was generated
+    Export,
+    /// A webhook was delivered
+    WebhookDelivery,
+    /// A request was rejected by rate limiting
+    RateLimited,
+}
+
+impl UsageEvent {
+    pub const ALL: [UsageEvent; 5] = [
+        UsageEvent::VerifyRun,
+        UsageEvent::SseSeconds,
+        UsageEvent::Export,
+        UsageEvent::WebhookDelivery,
+        UsageEvent::RateLimited,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UsageEvent::VerifyRun => "verify_run",
+            UsageEvent::SseSeconds => "sse_seconds",
+            UsageEvent::Export => "export",
+            UsageEvent::WebhookDelivery => "webhook_delivery",
+            UsageEvent::RateLimited => "rate_limited",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        UsageEvent::ALL
+            .into_iter()
+            .find(|event| event.as_str() == value)
+    }
+}
+
+/// Where a key under `{redis_prefix}:usage:` stands in the rollup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UsageKey<'a> {
+    /// `{day}`: live counters of a day
+    Day(NaiveDate),
+    /// `{day}:rollup:{token}`: counters of a day taken out for rollup
+    Rollup(NaiveDate, &'a str),
+}
+
+impl<'a> UsageKey<'a> {
+    /// Parse the part of a key after `{redis_prefix}:usage:`
+    pub fn parse(rest: &'a str) -> Option<Self> {
+        let (day, rollup) = match rest.split_once(':') {
+            Some((day, tail)) => (day, Some(tail.strip_prefix("rollup:")?)),
+            None => (rest, None),
+        };
+        let day = NaiveDate::parse_from_str(day, "%Y-%m-%d").ok()?;
+        Some(match rollup {
+            Some(token) if !token.is_empty() => UsageKey::Rollup(day, token),
+            Some(_) => return None,
+            None => UsageKey::Day(day),
+        })
+    }
+}
+
+type Buffer = HashMap<(NaiveDate, i64, UsageEvent), i64>;
+
+fn redis_error(stage: &str, e: impl std::fmt::Display) -> ApiError {
+    ApiError::CustomError {
+        message: format!("{stage}: {e}"),
+        code: ApiCode::COMMON_FEED_ERROR,
+    }
+}
+
+/// Fire-and-forget per-user usage accounting.
+///
+/// `record` only adds to an in-process buffer; a background task flushes it to the Redis hash
+/// `{redis_prefix}:usage:{day}` (field `{user_id}:{event}`) every `usage.flush_interval_ms`.
+/// `roll_up` later moves finished days into `user_api_usage`.
+#[derive(Clone)]
+pub struct UsageRecorder {
+    buffer: Arc<Mutex<Buffer>>,
+    pool: bb8::Pool<bb8_redis::RedisConnectionManager>,
+    prefix: String,
+}
+
+/// Records the seconds between its creation and its drop
+pub struct UsageTimer {
+    recorder: UsageRecorder,
+    user_id: i64,
+    event: UsageEvent,
+    started: Instant,
+}
+
+impl Drop for UsageTimer {
+    fn drop(&mut self) {
+        let seconds = self.started.elapsed().as_secs_f64().round() as i64;
+        self.recorder.record(self.user_id, self.event, seconds);
+    }
+}
+
+impl UsageRecorder {
+    /// Create the recorder and spawn its flush task
+    pub fn start(pool: bb8::Pool<bb8_redis::RedisConnectionManager>, redis_prefix: &str) -> Self {
+        let recorder = UsageRecorder {
+            buffer: Arc::new(Mutex::new(HashMap::new())),
+            pool,
+            prefix: format!("{redis_prefix}:usage"),
+        };
+        let flusher = recorder.clone();
+        let flush_interval = Duration::from_millis(usage_config().flush_interval_ms.max(10));
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(flush_interval);
+            loop {
+                interval.tick().await;
+                flusher.flush().await;
+            }
+        });
+        recorder
+    }
+
+    fn day_key(&self, day: NaiveDate) -> String {
+        format!("{}:{day}", self.prefix)
+    }
+
+    fn field(user_id: i64, event: UsageEvent) -> String {
+        format!("{user_id}:{}", event.as_str())
+    }
+
+    /// Count `amount` occurrences of `event` for today. Never blocks on I/O.
+    pub fn record(&self, user_id: i64, event: UsageEvent, amount: i64) {
+        if amount <= 0 {
+            return;
+        }
+        let day = Utc::now().date_naive();
+        let mut buffer = self.buffer.lock().unwrap();
+        if buffer.len() >= MAX_BUFFERED_COUNTERS && !buffer.contains_key(&(day, user_id, event)) {
+            warn!(
+                user_id,
+                event = event.as_str(),
+                "usage buffer full, dropping event"
+            );
+            return;
+        }
+        *buffer.entry((day, user_id, event)).or_default() += amount;
+    }
+
+    /// Count the seconds until the returned timer is dropped
+    pub fn timer(&self, user_id: i64, event: UsageEvent) -> UsageTimer {
+        UsageTimer {
+            recorder: self.clone(),
+            user_id,
+            event,
+            started: Instant::now(),
+        }
+    }
+
+    /// Push the buffered counters to Redis; on failure they go back to the buffer
+    pub async fn flush(&self) {
+        let pending = std::mem::take(&mut *self.buffer.lock().unwrap());
+        if pending.is_empty() {
+            return;
+        }
+
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        let mut days = BTreeSet::new();
+        for ((day, user_id, event), count) in &pending {
+            pipe.hincr(self.day_key(*day), Self::field(*user_id, *event), *count)
+                .ignore();
+            days.insert(*day);
+        }
+        for day in days {
+            pipe.expire(self.day_key(day), DAY_KEY_TTL_SECS).ignore();
+        }
+
+        let result = match self.pool.get().await {
+            Ok(mut conn) => pipe
+                .query_async::<()>(&mut *conn)
+                .await
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = result {
+            warn!(counters = pending.len(), error = %e, "failed to flush usage counters");
+            let mut buffer = self.buffer.lock().unwrap();
+            for (key, count) in pending {
+                if buffer.len() < MAX_BUFFERED_COUNTERS || buffer.contains_key(&key) {
+                    *buffer.entry(key).or_default() += count;
+                }
+            }
+        }
+    }
+
+    /// Counters of `user_id` still in Redis (not rolled up yet) for `days`
+    pub async fn pending(
+        &self,
+        user_id: i64,
+        days: &[NaiveDate],
+    ) -> Result<Vec<UsageCount>, ApiError> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| redis_error("read-usage", e))?;
+        let fields: Vec<String> = UsageEvent::ALL
+            .iter()
+            .map(|event| Self::field(user_id, *event))
+            .collect();
+        let mut pipe = redis::pipe();
+        for day in days {
+            pipe.hget(self.day_key(*day), &fields);
+        }
+        let values: Vec<Vec<Option<i64>>> = pipe
+            .query_async(&mut *conn)
+            .await
+            .map_err(|e| redis_error("read-usage", e))?;
+
+        let mut counts = Vec::new();
+        for (day, values) in days.iter().zip(values) {
+            for (event, count) in UsageEvent::ALL.iter().zip(values) {
+                if let Some(count) = count.filter(|count| *count > 0) {
+                    counts.push(UsageCount {
+                        user_id,
+                        day: *day,
+                        event: event.as_str().to_string(),
+                        count,
+                    });
+                }
+            }
+        }
+        Ok(counts)
+    }
+
+    async fn usage_keys(
+        &self,
+        conn: &mut redis::aio::MultiplexedConnection,
+    ) -> Result<Vec<String>, redis::RedisError> {
+        let mut keys = Vec::new();
+        let mut cursor: u64 = 0;
+        loop {
+            let (next, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(format!("{}:*", self.prefix))
+                .arg("COUNT")
+                .arg(500)
+                .query_async(conn)
+                .await?;
+            keys.extend(batch);
+            if next == 0 {
+                return Ok(keys);
+            }
+            cursor = next;
+        }
+    }
+
+    /// Move the counters of every day before `before` from Redis into `user_api_usage`.
+    ///
+    /// A day's hash is first renamed to a unique rollup key, so late increments start a fresh
+    /// hash instead of racing the rollup. Each rollup key is applied with its name as batch id
+    /// and deleted afterwards; a rollup key left behind by a crash is re-applied on the next run
+    /// and skipped by `apply_rollup` if it was already counted. Returns the counters applied.
+    pub async fn roll_up(
+        &self,
+        db: &DatabaseConnection,
+        before: NaiveDate,
+    ) -> Result<u64, ApiError> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| redis_error("usage-rollup", e))?;
+
+        let lock_key = format!("{}-rollup-lock", self.prefix);
+        let locked: Option<String> = redis::cmd("SET")
+            .arg(&lock_key)
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(ROLLUP_LOCK_SECS)
+            .query_async(&mut *conn)
+            .await
+            .map_err(|e| redis_error("usage-rollup", e))?;
+        if locked.is_none() {
+            return Ok(0);
+        }
+
+        let result = self.roll_up_locked(&mut conn, db, before).await;
+        let released: redis::RedisResult<()> = conn.del(&lock_key).await;
+        if let Err(e) = released {
+            warn!(error = %e, "failed to release usage rollup lock");
+        }
+        result
+    }
+
+    async fn roll_up_locked(
+        &self,
+        conn: &mut redis::aio::MultiplexedConnection,
+        db: &DatabaseConnection,
+        before: NaiveDate,
+    ) -> Result<u64, ApiError> {
+        let keys = self
+            .usage_keys(conn)
+            .await
+            .map_err(|e| redis_error("usage-rollup", e))?;
+
+        let key_prefix = format!("{}:", self.prefix);
+        let mut batches = Vec::new();
+        for key in keys {
+            let Some(parsed) = key.strip_prefix(&key_prefix).and_then(UsageKey::parse) else {
+                continue;
+            };
+            match parsed {
+                UsageKey::Rollup(day, _) => batches.push((day, key)),
+                UsageKey::Day(day) if day < before => {
+                    let rollup_key = format!("{key}:rollup:{}", Uuid::new_v4().simple());
+                    let renamed: redis::RedisResult<()> = conn.rename(&key, &rollup_key).await;
+                    match renamed {
+                        Ok(()) => batches.push((day, rollup_key)),
+                        // expired or renamed by a concurrent run between SCAN and RENAME
+                        Err(e) => warn!(key, error = %e, "failed to take usage day for rollup"),
+                    }
+                }
+                UsageKey::Day(_) => {}
+            }
+        }
+
+        let mut applied = 0;
+        for (day, key) in batches {
+            let fields: HashMap<String, i64> = conn
+                .hgetall(&key)
+                .await
+                .map_err(|e| redis_error("usage-rollup", e))?;
+            let rows: Vec<UsageCount> = fields
+                .into_iter()
+                .filter_map(|(field, count)| {
+                    let (user_id, event) = field.split_once(':')?;
+                    Some(UsageCount {
+                        user_id: user_id.parse().ok()?,
+                        day,
+                        event: UsageEvent::parse(event)?.as_str().to_string(),
+                        count,
+                    })
+                })
+                .collect();
+
+            let counted = apply_rollup(db, &key, &rows).await.context(DbErrSnafu {
+                stage: "usage-rollup",
+                code: ApiCode::COMMON_DATABASE_ERROR,
+            })?;
+            if counted {
+                applied += rows.len() as u64;
+            }
+            let _: () = conn
+                .del(&key)
+                .await
+                .map_err(|e| redis_error("usage-rollup", e))?;
+        }
+        Ok(applied)
+    }
+}
+
+/// Roll up finished days every `usage.rollup_interval_secs`
+pub async fn run_usage_rollup(state: AppState) {
+    let mut interval = tokio::time::interval(Duration::from_secs(
+        usage_config().rollup_interval_secs.max(1),
+    ));
+    loop {
+        interval.tick().await;
+        let today = Utc::now().date_naive();
+        match state.usage.roll_up(&state.conn, today).await {
+            Ok(applied) => info!(applied, "usage rollup finished"),
+            Err(e) => warn!(error = ?e, "usage rollup failed"),
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+use std::future::Future;
+
+use common::{error::api_error::*, prelude::ApiCode};
+use redis::AsyncCommands;
+use seaorm_db::{
+    entities::feed::rss_sources,
+    query::feed::{
+        rss_sources::RssSourcesQuery, rss_subscriptions::RssSubscriptionsQuery,
+        user_interests::UserInterestsQuery,
+    },
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use snafu::ResultExt;
+use tracing::{debug, warn};
+
+use super::{app_state::AppState, version::VersionCounter};
+use crate::config::server_rss_config;
+
+/// How long a cached interest map / subscription list / source map stays valid
+pub const USER_CONTEXT_TTL_SECS: u64 = 30;
+
+#[derive(Serialize, Deserialize)]
+struct Entry<T> {
+    version: i64,
+    data: T,
+}
+
+/// Per-user read-through cache (Redis) of the interest map, subscribed source ids and the
+/// subscribed sources.
+///
+/// Every entry is stamped with the user's context version read *before* loading from the
+/// database; invalidating bumps the version, so an entry written by a reader that raced a
+/// mutation is never served. Updates queued to the `UpdateTaskManager` are applied later by the
+/// worker, so `begin_update` also suspends caching until they can have landed.
+/// Loads always read the primary, never the replica. Redis failures fall back to the database.
+pub struct CachedUserContext<'a> {
+    state: &'a AppState,
+    bypass: bool,
+}
+
+impl<'a> CachedUserContext<'a> {
+    pub fn new(state: &'a AppState) -> Self {
+        CachedUserContext {
+            state,
+            bypass: false,
+        }
+    }
+
+    /// With `bypass`, always load from the database (the cache is still refreshed)
+    pub fn bypass_cache(mut self, bypass: bool) -> Self {
+        self.bypass = bypass;
+        self
+    }
+
+    fn key(&self, user_id: i64, kind: &str) -> String {
+        format!(
+            "{}:user-context:{user_id}:{kind}",
+            self.state.config.rss.feed_redis.redis_prefix
+        )
+    }
+
+    /// Interest id -> interest text
+    pub async fn interests(&self, user_id: i64) -> Result<HashMap<i64, String>, ApiError> {
+        let items: Vec<(i64, String)> = self
+            .cached(user_id, "interests", || async {
+                let items = UserInterestsQuery::list_by_user_id(&self.state.conn, user_id)
+                    .await
+                    .context(DbErrSnafu {
+                        stage: "list-user-interests",
+                        code: ApiCode::COMMON_DATABASE_ERROR,
+                    })?;
+                Ok(items.into_iter().map(|m| (m.id, m.interest)).collect())
+            })
+            .await?;
+        Ok(items.into_iter().collect())
+    }
+
+    /// Sorted, deduplicated ids of the sources the user is subscribed to
+    pub async fn subscriptions(&self, user_id: i64) -> Result<Vec<i32>, ApiError> {
+        self.cached(user_id, "subscriptions", || async {
+            let subscriptions =
+                RssSubscriptionsQuery::list_by_user_id(&self.state.conn, user_id, None)
+                    .await
+                    .context(DbErrSnafu {
+                        stage: "get-rss-subscriptions",
+                        code: ApiCode::COMMON_DATABASE_ERROR,
+                    })?;
+            let mut source_ids: Vec<i32> = subscriptions.into_iter().map(|s| s.source_id).collect();
+            source_ids.sort_unstable();
+            source_ids.dedup();
+            Ok(source_ids)
+        })
+        .await
+    }
+
+    /// Source id -> source, for the sources the user is subscribed to.
+    ///
+    /// Entries are also keyed by the `rss_sources` version, so creating, updating or deleting a
+    /// source is seen at once.
+    pub async fn sources(
+        &self,
+        user_id: i64,
+    ) -> Result<HashMap<i32, rss_sources::Model>, ApiError> {
+        let kind = match VersionCounter::rss_sources(self.state).current().await {
+            Some(sources_version) => format!("sources:{sources_version}"),
+            None => "sources".to_string(),
+        };
+        let sources: Vec<rss_sources::Model> = self
+            .cached(user_id, &kind, || async {
+                let source_ids = self.subscriptions(user_id).await?;
+                if source_ids.is_empty() {
+                    return Ok(Vec::new());
+                }
+                RssSourcesQuery::get_by_ids(&self.state.conn, source_ids)
+                    .await
+                    .context(DbErrSnafu {
+                        stage: "get-rss-sources",
+                        code: ApiCode::COMMON_DATABASE_ERROR,
+                    })
+            })
+            .await?;
+        Ok(sources.into_iter().map(|m| (m.id, m)).collect())
+    }
+
+    fn version_counter(&self, user_id: i64) -> VersionCounter<'a> {
+        VersionCounter::new(self.state, self.key(user_id, "version"))
+    }
+
+    /// Current context version of the user, `None` when Redis is unavailable or an update is
+    /// pending. Changes whenever `invalidate` is called.
+    pub async fn version(&self, user_id: i64) -> Option<i64> {
+        if self.update_pending(user_id).await {
+            return None;
+        }
+        self.version_counter(user_id).current().await
+    }
+
+    /// Drop every cached entry of the user. Call after any change to interests or subscriptions.
+    pub async fn invalidate(&self, user_id: i64) {
+        self.version_counter(user_id).bump().await;
+    }
+
+    /// Invalidate before submitting a change to the `UpdateTaskManager`, and read the context
+    /// uncached for `update_apply_window_secs`, while the worker applies it.
+    ///
+    /// The window is kept in Redis, so it survives a restart of the server.
+    pub async fn begin_update(&self, user_id: i64) {
+        let window = server_rss_config().update_apply_window_secs;
+        match self.state.redis.pool.get().await {
+            Ok(mut conn) => {
+                let result: redis::RedisResult<()> = conn
+                    .set_ex(self.key(user_id, "update-pending"), 1, window)
+                    .await;
+                if let Err(e) = result {
+                    warn!(user_id, error = %e, "user context: failed to mark update pending");
+                }
+            }
+            Err(e) => warn!(user_id, error = %e, "user context: redis unavailable"),
+        }
+        self.invalidate(user_id).await;
+    }
+
+    /// Whether an update queued by `begin_update` may still be unapplied
+    async fn update_pending(&self, user_id: i64) -> bool {
+        let Ok(mut conn) = self.state.redis.pool.get().await else {
+            return false;
+        };
+        conn.exists(self.key(user_id, "update-pending"))
+            .await
+            .unwrap_or(false)
+    }
+
+    async fn cached<T, F, Fut>(&self, user_id: i64, kind: &str, load: F) -> Result<T, ApiError>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, ApiError>>,
+    {
+        let key = self.key(user_id, kind);
+        let mut conn = match self.state.redis.pool.get().await {
+            Ok(conn) => Some(conn),
+            Err(e) => {
+                warn!(user_id, error = %e, "user context: redis unavailable");
+                None
+            }
+        };
+
+        let mut version = None;
+        if let Some(conn) = conn.as_mut() {
+            let result: redis::RedisResult<(Option<i64>, Option<String>, bool)> = redis::pipe()
+                .get(self.key(user_id, "version"))
+                .get(&key)
+                .exists(self.key(user_id, "update-pending"))
+                .query_async(&mut **conn)
+                .await;
+            match result {
+                Ok((current, cached, pending)) => {
+                    let current = current.unwrap_or(0);
+                    // nothing read while an update is pending may be kept
+                    version = (!pending).then_some(current);
+                    let hit = cached
+                        .filter(|_| !self.bypass && !pending)
+                        .and_then(|raw| serde_json::from_str::<Entry<T>>(&raw).ok())
+                        .filter(|entry| entry.version == current);
+                    debug!(
+                        user_id,
+                        kind,
+                        hit = hit.is_some(),
+                        bypass = self.bypass,
+                        pending,
+                        "user context: cache lookup"
+                    );
+                    if let Some(entry) = hit {
+                        return Ok(entry.data);
+                    }
+                }
+                Err(e) => warn!(user_id, kind, error = %e, "user context: failed to read cache"),
+            }
+        }
+
+        let data = load().await?;
+
+        if let (Some(conn), Some(version)) = (conn.as_mut(), version) {
+            match serde_json::to_string(&Entry {
+                version,
+                data: &data,
+            }) {
+                Ok(raw) => {
+                    let result: redis::RedisResult<()> =
+                        conn.set_ex(&key, raw, USER_CONTEXT_TTL_SECS).await;
+                    if let Err(e) = result {
+                        warn!(user_id, kind, error = %e, "user context: failed to write cache");
+                    }
+                }
+                Err(e) => warn!(user_id, kind, error = %e, "user context: failed to encode"),
+            }
+        }
+        Ok(data)
+    }
+}
@@ -0,0 +1,84 @@
+use common::{error::api_error::*, prelude::ApiCode};
+use redis::AsyncCommands;
+use tracing::warn;
+
+use super::app_state::AppState;
+use crate::config::server_rss_config;
+
+/// Marker `{redis_prefix}:verify-job:user:{user_id}` set while a `VerifyAllUserPapersInput` job
+/// of the user is queued or running, so `/verify` does not pile up duplicate jobs.
+///
+/// The marker expires after `rss.verify_job_dedupe_ttl_secs`; the worker may delete it earlier
+/// once the job completes or fails.
+pub struct VerifyJobMarker<'a> {
+    state: &'a AppState,
+}
+
+fn redis_error(stage: &str, e: impl std::fmt::Display) -> ApiError {
+    ApiError::CustomError {
+        message: format!("{stage}: {e}"),
+        code: ApiCode::COMMON_FEED_ERROR,
+    }
+}
+
+impl<'a> VerifyJobMarker<'a> {
+    pub fn new(state: &'a AppState) -> Self {
+        VerifyJobMarker { state }
+    }
+
+    pub fn key(&self, user_id: i64) -> String {
+        format!(
+            "{}:verify-job:user:{user_id}",
+            self.state.config.rss.feed_redis.redis_prefix
+        )
+    }
+
+    /// Set the marker; `false` when the user already has a job queued.
+    ///
+    /// Fails open: when Redis cannot be written the job is dispatched as before.
+    pub async fn claim(&self, user_id: i64) -> bool {
+        match self.try_claim(user_id).await {
+            Ok(claimed) => claimed,
+            Err(e) => {
+                warn!(user_id, error = ?e, "verify job marker unavailable, dispatching anyway");
+                true
+            }
+        }
+    }
+
+    async fn try_claim(&self, user_id: i64) -> Result<bool, ApiError> {
+        let mut conn = self
+            .state
+            .redis
+            .pool
+            .get()
+            .await
+            .map_err(|e| redis_error("claim-verify-job", e))?;
+        let marked: Option<String> = redis::cmd("SET")
+            .arg(self.key(user_id))
+            .arg(chrono::Utc::now().timestamp())
+            .arg("NX")
+            .arg("EX")
+            .arg(server_rss_config().verify_job_dedupe_ttl_secs.max(1))
+            .query_async(&mut *conn)
+            .await
+            .map_err(|e| redis_error("claim-verify-job", e))?;
+        Ok(marked.is_some())
+    }
+
+    /// Remove the marker, e.g. when the dispatch failed
+    pub async fn release(&self, user_id: i64) -> Result<(), ApiError> {
+        let mut conn = self
+            .state
+            .redis
+            .pool
+            .get()
+            .await
+            .map_err(|e| redis_error("release-verify-job", e))?;
+        let _: () = conn
+            .del(self.key(user_id))
+            .await
+            .map_err(|e| redis_error("release-verify-job", e))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use std::{collections::HashSet, time::Duration};
+
+use chrono::{DateTime, Utc};
+use common::{error::api_error::*, prelude::ApiCode};
+use feed::services::VerifyService;
+use snafu::ResultExt;
+use tracing::{info, warn};
+
+use super::{
+    app_state::AppState,
+    kill_switch::{KillSwitch, KillSwitches},
+    metrics::metrics,
+};
+use crate::{config::server_rss_config, query::verify_schedules};
+
+/// Queue every user whose schedule is due at `now` and who is not already being verified.
+///
+/// Each slot is claimed in `verify_schedules` before queueing, so several server replicas
+/// running this concurrently queue a user once. Returns how many users were queued.
+pub async fn dispatch_due_schedules(
+    state: &AppState,
+    now: DateTime<Utc>,
+) -> Result<usize, ApiError> {
+    if KillSwitches::new(state)
+        .is_engaged(KillSwitch::VerifyDispatch)
+        .await
+    {
+        info!("verify_dispatch kill switch engaged, scheduled verification skipped");
+        return Ok(0);
+    }
+
+    let schedules = verify_schedules::list_enabled(&state.conn)
+        .await
+        .context(DbErrSnafu {
+            stage: "list-verify-schedules",
+            code: ApiCode::COMMON_DATABASE_ERROR,
+        })?;
+    let due: Vec<_> = schedules
+        .into_iter()
+        .filter_map(|schedule| schedule.due_slot(now).map(|slot| (schedule, slot)))
+        .collect();
+    if due.is_empty() {
+        return Ok(0);
+    }
+
+    let verify_service = VerifyService::new(
+        state.redis.clone().pool,
+        state.conn.clone(),
+        state.redis.pubsub_manager.clone(),
+        state.config.rss.feed_redis.redis_prefix.clone(),
+        state.config.rss.feed_redis.redis_key_default_expire,
+        state.config.rss.verify_papers_channel.clone(),
+    )
+    .await;
+    // users already in the verify list are left for a later tick, once their run is over
+    let active: HashSet<i64> = verify_service
+        .get_active_verification_users()
+        .await?
+        .into_iter()
+        .collect();
+
+    let mut queued = 0;
+    for (schedule, slot) in due {
+        let user_id = schedule.user_id;
+        if active.contains(&user_id) {
+            continue;
+        }
+        let claimed = verify_schedules::claim(&state.conn, user_id, slot, now)
+            .await
+            .context(DbErrSnafu {
+                stage: "claim-verify-schedule",
+                code: ApiCode::COMMON_DATABASE_ERROR,
+            })?;
+        if !claimed {
+            continue;
+        }
+
+        let max_match_limit = schedule
+            .max_match_limit
+            .unwrap_or(state.config.rss.max_match_limit_per_user as i32);
+        if let Err(e) = verify_service
+            .append_user_to_verify_list(
+                user_id,
+                Some(state.config.rss.max_rss_paper as i32),
+                schedule.channel.clone(),
+                max_match_limit,
+            )
+            .await
+        {
+            warn!(user_id, error = %e, "failed to queue scheduled verification");
+            if let Err(e) =
+                verify_schedules::release(&state.conn, user_id, now, schedule.last_run_at).await
+            {
+                warn!(user_id, error = %e, "failed to release verify schedule claim");
+            }
+            continue;
+        }
+        info!(user_id, %slot, "scheduled verification queued");
+        metrics().verify_jobs_dispatched.inc();
+        queued += 1;
+    }
+    Ok(queued)
+}
+
+/// Look for due schedules every `rss.verify_schedule_interval_secs`
+pub async fn run_verify_scheduler(state: AppState) {
+    let mut interval = tokio::time::interval(Duration::from_secs(
+        server_rss_config().verify_schedule_interval_secs.max(1),
+    ));
+    loop {
+        interval.tick().await;
+        match dispatch_due_schedules(&state, Utc::now()).await {
+            Ok(0) => {}
+            Ok(queued) => info!(queued, "scheduled verifications queued"),
+            Err(e) => warn!(error = ?e, "scheduled verification failed"),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use redis::RedisResult;
+use tracing::warn;
+
+use super::app_state::AppState;
+
+/// Minimum lifetime of a version counter
+const MIN_VERSION_TTL_SECS: i64 = 60;
+
+/// Monotonic version counters in Redis, used to invalidate caches.
+///
+/// A missing counter is (re)initialized to the current time in milliseconds, so a counter that
+/// expired never comes back with a value it already had (unless it was bumped more than once
+/// per millisecond of its lifetime).
+pub struct VersionCounter<'a> {
+    state: &'a AppState,
+    key: String,
+}
+
+fn now_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+impl<'a> VersionCounter<'a> {
+    pub fn new(state: &'a AppState, key: String) -> Self {
+        VersionCounter { state, key }
+    }
+
+    /// Bumped whenever an RSS source is created, updated or deleted
+    pub fn rss_sources(state: &'a AppState) -> Self {
+        let key = format!(
+            "{}:rss-sources:version",
+            state.config.rss.feed_redis.redis_prefix
+        );
+        VersionCounter::new(state, key)
+    }
+
+    fn ttl(&self) -> i64 {
+        (self.state.config.rss.feed_redis.redis_key_default_expire as i64).max(MIN_VERSION_TTL_SECS)
+    }
+
+    /// Current value, `None` when Redis is unavailable
+    pub async fn current(&self) -> Option<i64> {
+        let mut conn = match self.state.redis.pool.get().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                warn!(key = self.key, error = %e, "version: redis unavailable");
+                return None;
+            }
+        };
+        let result: RedisResult<(Option<i64>,)> = redis::pipe()
+            .cmd("SET")
+            .arg(&self.key)
+            .arg(now_millis())
+            .arg("NX")
+            .arg("EX")
+            .arg(self.ttl())
+            .ignore()
+            .get(&self.key)
+            .query_async(&mut *conn)
+            .await;
+        match result {
+            Ok((version,)) => version,
+            Err(e) => {
+                warn!(key = self.key, error = %e, "version: failed to read");
+                None
+            }
+        }
+    }
+
+    /// Move to a new value
+    pub async fn bump(&self) {
+        let mut conn = match self.state.redis.pool.get().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                warn!(key = self.key, error = %e, "version: redis unavailable");
+                return;
+            }
+        };
+        let result: RedisResult<()> = redis::pipe()
+            .cmd("SET")
+            .arg(&self.key)
+            .arg(now_millis())
+            .arg("NX")
+            .ignore()
+            .incr(&self.key, 1)
+            .ignore()
+            .expire(&self.key, self.ttl())
+            .ignore()
+            .query_async(&mut *conn)
+            .await;
+        if let Err(e) = result {
+            warn!(key = self.key, error = %e, "version: failed to bump");
+        }
+    }
+}
@@ -0,0 +1,176 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::{TEST_ADMIN_ID, TEST_USER_BASE, TestApp};
+use serde_json::json;
+use server::model::audit::{AuditAction, AuditEntry, AuditLogResponse};
+use server::routers::feed::subscriptions::SubscriptionCreateResult;
+
+/// Latest entries of `actor_id` for `action`
+async fn entries(app: &TestApp, actor_id: i64, action: AuditAction) -> Vec<AuditEntry> {
+    let response = app
+        .get(
+            &format!(
+                "/admin/audit-log?actor_id={actor_id}&action={}&page_size=50",
+                action.as_str()
+            ),
+            TEST_ADMIN_ID,
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let entries = response.json::<AuditLogResponse>().data.entries;
+    assert!(entries.iter().all(|entry| entry.action == action));
+    entries
+}
+
+/// Latest entry of `actor_id` for `action`
+async fn latest(app: &TestApp, actor_id: i64, action: AuditAction) -> AuditEntry {
+    entries(app, actor_id, action)
+        .await
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| panic!("no {} entry for {actor_id}", action.as_str()))
+}
+
+#[tokio::test]
+async fn test_audit_log_records_deletes() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 45;
+
+    let response = app
+        .post(
+            "/rss",
+            TEST_ADMIN_ID,
+            &json!({
+                "channel": "test-harness",
+                "skip_validation": true,
+                "name": "Harness|Audit|Source",
+                "url": format!("https://example.com/harness/audit-{user_id}.xml"),
+            }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let source_id = response.json::<i32>().data;
+
+    let response = app
+        .post(
+            "/subscriptions/one",
+            user_id,
+            &json!({ "source_id": source_id }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let subscription_id = response
+        .json::<SubscriptionCreateResult>()
+        .data
+        .id()
+        .expect("created");
+
+    let response = app
+        .delete(&format!("/subscriptions/{subscription_id}"), user_id)
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let entry = latest(&app, user_id, AuditAction::SubscriptionDelete).await;
+    assert_eq!(entry.target_type, "subscription");
+    assert_eq!(entry.target_ids, [subscription_id]);
+
+    let response = app
+        .post("/batch-delete", user_id, &json!({ "ids": [-1, -2] }))
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let entry = latest(&app, user_id, AuditAction::PapersBatchDelete).await;
+    assert_eq!(entry.target_type, "paper");
+    assert_eq!(entry.target_ids, [-1, -2]);
+    assert_eq!(entry.metadata["deleted"], 0);
+
+    let response = app
+        .send(
+            app.request(
+                Method::PUT,
+                &format!("/rss/{source_id}"),
+                Some(TEST_ADMIN_ID),
+            )
+            .json(&json!({ "name": "Harness|Audit|Renamed" }))
+            .build(),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let entry = entries(&app, TEST_ADMIN_ID, AuditAction::RssSourceUpdate)
+        .await
+        .into_iter()
+        .find(|entry| entry.target_ids == [source_id as i64])
+        .expect("rss source update recorded");
+    assert_eq!(entry.target_type, "rss_source");
+    assert_eq!(
+        entry.metadata["changes"],
+        json!({ "name": { "from": "Harness|Audit|Source", "to": "Harness|Audit|Renamed" } })
+    );
+
+    let response = app
+        .send(
+            app.request(
+                Method::DELETE,
+                &format!("/rss/{source_id}"),
+                Some(TEST_ADMIN_ID),
+            )
+            .header("x-request-id", &format!("audit-{user_id}"))
+            .build(),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    // other tests delete sources as the admin too
+    let entry = entries(&app, TEST_ADMIN_ID, AuditAction::RssSourceDelete)
+        .await
+        .into_iter()
+        .find(|entry| entry.target_ids == [source_id as i64])
+        .expect("rss source delete recorded");
+    assert_eq!(entry.actor_id, TEST_ADMIN_ID);
+    assert_eq!(entry.target_type, "rss_source");
+    assert_eq!(entry.metadata["request_id"], format!("audit-{user_id}"));
+}
+
+#[tokio::test]
+async fn test_audit_log_records_clears() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 46;
+
+    let response = app
+        .post("/interests", user_id, &json!({ "interests": [] }))
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let update_request_id = response.json::<String>().data;
+    let entry = latest(&app, user_id, AuditAction::InterestsClear).await;
+    assert_eq!(entry.target_type, "interest");
+    assert_eq!(entry.metadata["update_request_id"], update_request_id);
+
+    let response = app
+        .post("/subscriptions", user_id, &json!({ "source_ids": [] }))
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let entry = latest(&app, user_id, AuditAction::SubscriptionsClear).await;
+    assert_eq!(entry.target_type, "rss_source");
+    assert!(entry.metadata["request_id"].is_string());
+}
+
+#[tokio::test]
+async fn test_audit_log_is_admin_only() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let response = app.get("/admin/audit-log", TEST_USER_BASE + 47).await;
+    assert_eq!(
+        response.status,
+        StatusCode::FORBIDDEN,
+        "{}",
+        response.text()
+    );
+
+    let response = app
+        .get("/admin/audit-log?action=drop_everything", TEST_ADMIN_ID)
+        .await;
+    assert_ne!(response.status, StatusCode::OK);
+}
@@ -0,0 +1,168 @@
+mod common;
+
+use std::collections::HashSet;
+
+use axum::http::{Method, StatusCode};
+use common::{TEST_ADMIN_ID, TEST_USER_BASE, TestApp};
+use server::{
+    app::{api_routers, unassigned_routes},
+    middlewares::authz::Capability,
+    routers::authz::ROUTE_CAPABILITIES,
+};
+
+const PREFIX: &str = "/api/v1";
+
+#[test]
+fn test_every_documented_route_has_a_capability() {
+    let (_, api) = api_routers(PREFIX).split_for_parts();
+    let unassigned = unassigned_routes(&api, PREFIX);
+    assert!(unassigned.is_empty(), "unassigned routes: {unassigned:?}");
+}
+
+#[test]
+fn test_registry_has_no_stale_or_duplicate_entries() {
+    let (_, api) = api_routers(PREFIX).split_for_parts();
+    let mut seen = HashSet::new();
+    for (method, path, _) in ROUTE_CAPABILITIES {
+        assert!(
+            seen.insert((*method, *path)),
+            "duplicate entry {method} {path}"
+        );
+        let item = api
+            .paths
+            .paths
+            .get(&format!("{PREFIX}{path}"))
+            .unwrap_or_else(|| panic!("stale entry {method} {path}"));
+        let present = match *method {
+            "GET" => item.get.is_some(),
+            "POST" => item.post.is_some(),
+            "PUT" => item.put.is_some(),
+            "PATCH" => item.patch.is_some(),
+            "DELETE" => item.delete.is_some(),
+            other => panic!("unexpected method {other}"),
+        };
+        assert!(present, "stale entry {method} {path}");
+    }
+}
+
+/// Replace `{param}` segments with an id that does not exist
+fn concrete_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| {
+            if segment.starts_with('{') {
+                "2147483000"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn is_denied(status: StatusCode) -> bool {
+    status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN
+}
+
+#[tokio::test]
+async fn test_authz_matrix_probes() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 7;
+
+    for (method, path, capability) in ROUTE_CAPABILITIES {
+        let method = Method::from_bytes(method.as_bytes()).unwrap();
+        let path = concrete_path(path);
+        let probe = |caller: Option<i64>| {
+            let request = app.request(method.clone(), &path, caller).build();
+            app.status(request)
+        };
+        let anonymous = probe(None).await;
+        let user = probe(Some(user_id)).await;
+        let admin = probe(Some(TEST_ADMIN_ID)).await;
+        let context = format!("{method} {path}: anonymous={anonymous} user={user} admin={admin}");
+
+        match capability {
+            Capability::Public => {
+                assert!(!is_denied(anonymous), "{context}");
+                assert!(!is_denied(user), "{context}");
+                assert!(!is_denied(admin), "{context}");
+            }
+            Capability::User => {
+                assert_eq!(anonymous, StatusCode::UNAUTHORIZED, "{context}");
+                assert!(!is_denied(user), "{context}");
+                assert!(!is_denied(admin), "{context}");
+            }
+            Capability::Admin => {
+                assert_eq!(anonymous, StatusCode::UNAUTHORIZED, "{context}");
+                assert_eq!(user, StatusCode::FORBIDDEN, "{context}");
+                assert!(!is_denied(admin), "{context}");
+            }
+            Capability::Service => {
+                assert_eq!(anonymous, StatusCode::UNAUTHORIZED, "{context}");
+                assert_eq!(user, StatusCode::FORBIDDEN, "{context}");
+                assert_eq!(admin, StatusCode::FORBIDDEN, "{context}");
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_authz_matrix_endpoint() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+
+    let response = app.get("/admin/authz/matrix", TEST_ADMIN_ID).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let entries = response.json::<Vec<serde_json::Value>>().data;
+    assert_eq!(entries.len(), ROUTE_CAPABILITIES.len());
+    assert!(entries.iter().any(|e| e["path"] == "/admin/authz/matrix"
+        && e["method"] == "GET"
+        && e["capability"] == "admin"));
+
+    let response = app.get("/admin/authz/matrix", TEST_USER_BASE + 7).await;
+    assert_eq!(
+        response.status,
+        StatusCode::FORBIDDEN,
+        "{}",
+        response.text()
+    );
+}
+
+#[tokio::test]
+async fn test_all_users_verify_info_requires_admin() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 7;
+
+    let response = app.get("/all-users-verify-info", user_id).await;
+    assert_eq!(
+        response.status,
+        StatusCode::FORBIDDEN,
+        "{}",
+        response.text()
+    );
+
+    let response = app.get("/all-users-verify-info", TEST_ADMIN_ID).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    response.json::<Vec<serde_json::Value>>();
+
+    // user_rss stays open to every user
+    let response = app.get("/user_rss", user_id).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+}
+
+#[test]
+fn test_verify_info_routes_are_documented() {
+    let (_, api) = api_routers(PREFIX).split_for_parts();
+    for path in ["/all-users-verify-info", "/user_rss"] {
+        let item = api
+            .paths
+            .paths
+            .get(&format!("{PREFIX}{path}"))
+            .unwrap_or_else(|| panic!("{path} missing from the OpenAPI document"));
+        assert!(item.get.is_some(), "{path}");
+    }
+}
@@ -0,0 +1,77 @@
+use serde::Deserialize;
+use server::model::list::{CommaSeparated, de_opt_comma_separated};
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(default, deserialize_with = "de_opt_comma_separated")]
+    ids: Option<CommaSeparated<i64>>,
+    #[serde(default, deserialize_with = "de_opt_comma_separated")]
+    names: Option<CommaSeparated<String>>,
+}
+
+fn parse(query: &str) -> Result<Params, String> {
+    serde_urlencoded::from_str::<Params>(query).map_err(|e| e.to_string())
+}
+
+fn ids(query: &str) -> Option<Vec<i64>> {
+    parse(query).unwrap().ids.map(CommaSeparated::into_inner)
+}
+
+#[test]
+fn test_missing_and_empty_are_none() {
+    assert_eq!(ids(""), None);
+    assert_eq!(ids("ids="), None);
+    assert_eq!(ids("ids=%20%20"), None);
+    assert_eq!(ids("ids=,,"), None);
+    assert_eq!(ids("ids=%20,%20,"), None);
+}
+
+#[test]
+fn test_values_are_trimmed_and_blank_entries_skipped() {
+    assert_eq!(ids("ids=1"), Some(vec![1]));
+    assert_eq!(ids("ids=1,2,3"), Some(vec![1, 2, 3]));
+    assert_eq!(ids("ids=%201%20,%202"), Some(vec![1, 2]));
+    assert_eq!(ids("ids=1,,2,"), Some(vec![1, 2]));
+    assert_eq!(ids("ids=,1"), Some(vec![1]));
+    assert_eq!(ids("ids=-4,0"), Some(vec![-4, 0]));
+}
+
+#[test]
+fn test_order_and_duplicates_are_kept() {
+    assert_eq!(ids("ids=3,1,3"), Some(vec![3, 1, 3]));
+}
+
+#[test]
+fn test_unparsable_entry_names_the_token() {
+    let err = parse("ids=1,abc,3").unwrap_err();
+    assert!(err.contains("`abc`"), "{err}");
+
+    let err = parse("ids=1.5").unwrap_err();
+    assert!(err.contains("`1.5`"), "{err}");
+
+    let err = parse("ids=99999999999999999999").unwrap_err();
+    assert!(err.contains("`99999999999999999999`"), "{err}");
+}
+
+#[test]
+fn test_string_lists() {
+    let params = parse("names=yes,%20partial%20,,no").unwrap();
+    assert_eq!(
+        params.names.map(CommaSeparated::into_inner),
+        Some(vec![
+            "yes".to_string(),
+            "partial".to_string(),
+            "no".to_string()
+        ])
+    );
+}
+
+#[test]
+fn test_from_str_and_serialize_round_trip() {
+    let list: CommaSeparated<i64> = " 4, 5 ,6".parse().unwrap();
+    assert_eq!(list, CommaSeparated(vec![4, 5, 6]));
+    assert_eq!(serde_json::to_string(&list).unwrap(), r#""4,5,6""#);
+
+    let empty: CommaSeparated<i64> = "".parse().unwrap();
+    assert_eq!(empty, CommaSeparated(vec![]));
+}
@@ -0,0 +1,378 @@
+#![allow(dead_code)]
+
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    extract::Request,
+    http::{HeaderMap, HeaderValue, Method, StatusCode},
+    middleware::{self, Next},
+    response::Response,
+};
+use dotenvy::dotenv;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DbBackend, SqlxPostgresConnector, Statement,
+    sqlx::postgres::{PgConnectOptions, PgPoolOptions},
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use server::{
+    app::build_router,
+    consts::WIS_TOKEN_LOWERCASE,
+    middlewares::auth::UserInfo,
+    state::{app_state::AppState, usage::UsageRecorder},
+};
+use std::sync::Arc;
+use test_support::infra_required;
+use tokio::sync::OnceCell;
+use tower::ServiceExt;
+use tracing::warn;
+use tracing_subscriber::EnvFilter;
+
+/// Header understood by the fake auth layer: a numeric user id
+pub const TEST_USER_HEADER: &str = "x-test-user-id";
+
+/// User ids far outside the range of real accounts
+pub const TEST_USER_BASE: i64 = 9_900_000_000;
+
+/// Test user granted the admin capability
+pub const TEST_ADMIN_ID: i64 = TEST_USER_BASE + 999;
+
+static INIT_TRACING: std::sync::Once = std::sync::Once::new();
+
+fn init_test_tracing() {
+    INIT_TRACING.call_once(|| {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter(
+                EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+            )
+            .with_writer(std::io::stderr)
+            .compact()
+            .try_init();
+        dotenv().ok();
+    });
+}
+
+/// Same envelope as `ApiResponse`, deserializable for assertions
+#[derive(Debug, Deserialize)]
+pub struct ApiBody<T> {
+    pub data: T,
+    pub success: bool,
+    pub message: String,
+}
+
+pub fn test_user(id: i64) -> UserInfo {
+    UserInfo {
+        id,
+        open_id: format!("test-open-id-{id}"),
+        name: Some(format!("test user {id}")),
+        given_name: None,
+        family_name: None,
+        nickname: None,
+        preferred_username: None,
+        profile: None,
+        picture: None,
+        website: None,
+        email: None,
+        email_verified: None,
+        gender: None,
+        birthdate: None,
+        zoneinfo: None,
+        locale: None,
+        phone_number: None,
+        phone_number_verified: None,
+        address: None,
+    }
+}
+
+/// Map `x-test-user-id` to the `X-User-Info` payload the gateway would inject
+async fn fake_auth(mut request: Request, next: Next) -> Response {
+    let user_id = request
+        .headers()
+        .get(TEST_USER_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<i64>().ok());
+    if let Some(user_id) = user_id {
+        let payload = serde_json::to_string(&test_user(user_id)).expect("serialize test user");
+        request.headers_mut().insert(
+            WIS_TOKEN_LOWERCASE,
+            HeaderValue::from_str(&payload).expect("valid header value"),
+        );
+    }
+    next.run(request).await
+}
+
+/// The full application router wired to the configured database and Redis
+pub struct TestApp {
+    pub router: Router,
+    pub state: AppState,
+    pub prefix: String,
+}
+
+pub struct TestResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: bytes::Bytes,
+}
+
+impl TestResponse {
+    pub fn json<T: DeserializeOwned>(&self) -> ApiBody<T> {
+        serde_json::from_slice(&self.body).unwrap_or_else(|e| {
+            panic!(
+                "unexpected body ({e}): {}",
+                String::from_utf8_lossy(&self.body)
+            )
+        })
+    }
+
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).to_string()
+    }
+}
+
+/// Schema of this test process: `feed_test_<pid>`, recreated and migrated once per process
+static TEST_SCHEMA: OnceCell<String> = OnceCell::const_new();
+
+/// `conn`'s server with `search_path` set to the schema of this test process
+async fn test_database(conn: &DatabaseConnection) -> DatabaseConnection {
+    let options = conn
+        .get_postgres_connection_pool()
+        .connect_options()
+        .as_ref()
+        .clone();
+    let schema = TEST_SCHEMA
+        .get_or_init(|| async {
+            let schema = format!("feed_test_{}", std::process::id());
+            conn.execute_unprepared(&format!(
+                "DROP SCHEMA IF EXISTS {schema} CASCADE; CREATE SCHEMA {schema};"
+            ))
+            .await
+            .expect("create test schema");
+            // a short-lived pool: this runtime ends with the test
+            let setup = connect_schema(options.clone(), &schema);
+            Migrator::up(&setup, None)
+                .await
+                .expect("migrate test schema");
+            setup.close().await.ok();
+            schema
+        })
+        .await;
+    connect_schema(options, schema)
+}
+
+fn connect_schema(options: PgConnectOptions, schema: &str) -> DatabaseConnection {
+    SqlxPostgresConnector::from_sqlx_postgres_pool(
+        PgPoolOptions::new()
+            .max_connections(5)
+            .connect_lazy_with(options.options([("search_path", schema)])),
+    )
+}
+
+/// Insert a row into `table` of the test schema and return its `id`. `values` are SQL
+/// expressions per column; the other `NOT NULL` columns without a default get a placeholder of
+/// their type, so seeding does not depend on every column of the `seaorm_db` entities.
+pub async fn insert_row(conn: &DatabaseConnection, table: &str, values: &[(&str, &str)]) -> i64 {
+    let required = conn
+        .query_all(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT column_name::TEXT AS name, data_type::TEXT AS data_type, udt_name::TEXT AS udt \
+             FROM information_schema.columns \
+             WHERE table_schema = current_schema() AND table_name = $1 \
+               AND is_nullable = 'NO' AND column_default IS NULL AND is_identity = 'NO'",
+            [table.into()],
+        ))
+        .await
+        .expect("list required columns");
+    let mut columns: Vec<(String, String)> = values
+        .iter()
+        .map(|(column, value)| (format!("\"{column}\""), value.to_string()))
+        .collect();
+    for row in required {
+        let name: String = row.try_get("", "name").unwrap();
+        if values.iter().any(|(column, _)| *column == name) {
+            continue;
+        }
+        let data_type: String = row.try_get("", "data_type").unwrap();
+        let udt: String = row.try_get("", "udt").unwrap();
+        let placeholder = match data_type.as_str() {
+            "smallint" | "integer" | "bigint" | "numeric" | "real" | "double precision" => {
+                "0".to_string()
+            }
+            "boolean" => "FALSE".to_string(),
+            "date" => "CURRENT_DATE".to_string(),
+            t if t.starts_with("timestamp") => "NOW()".to_string(),
+            "json" | "jsonb" => "'{}'".to_string(),
+            "ARRAY" => "'{}'".to_string(),
+            "uuid" => "gen_random_uuid()".to_string(),
+            "USER-DEFINED" => format!(
+                "(SELECT e.enumlabel FROM pg_enum e JOIN pg_type t ON t.oid = e.enumtypid \
+                 WHERE t.typname = '{udt}' ORDER BY e.enumsortorder LIMIT 1)::\"{udt}\""
+            ),
+            _ => "'harness'".to_string(),
+        };
+        columns.push((format!("\"{name}\""), placeholder));
+    }
+    let (names, exprs): (Vec<String>, Vec<String>) = columns.into_iter().unzip();
+    let sql = format!(
+        "INSERT INTO {table} ({}) VALUES ({}) RETURNING id::BIGINT AS id",
+        names.join(", "),
+        exprs.join(", ")
+    );
+    conn.query_one(Statement::from_string(DbBackend::Postgres, sql.clone()))
+        .await
+        .unwrap_or_else(|e| panic!("{sql}: {e}"))
+        .expect("inserted row")
+        .try_get("", "id")
+        .unwrap()
+}
+
+impl TestApp {
+    /// Build the app on a schema and a Redis prefix of its own (see `test_database`), or `None`
+    /// (skip the test) when the database or Redis is unreachable.
+    ///
+    /// Under CI (see `infra_required`) missing infrastructure panics instead, so tests cannot
+    /// pass without running.
+    pub async fn try_new() -> Option<Self> {
+        init_test_tracing();
+        let mut state = match tokio::spawn(AppState::new()).await {
+            Ok(state) => state,
+            Err(err) if infra_required() => panic!("cannot build app state: {err}"),
+            Err(err) => {
+                warn!(error = %err, "skip test: cannot build app state");
+                return None;
+            }
+        };
+        if let Err(err) = state.conn.ping().await {
+            if infra_required() {
+                panic!("database unreachable: {err}");
+            }
+            warn!(error = %err, "skip test: database unreachable");
+            return None;
+        }
+        state.conn = test_database(&state.conn).await;
+        state.replica = None;
+
+        let prefix = format!(
+            "{}:test-{}",
+            state.config.rss.feed_redis.redis_prefix,
+            std::process::id()
+        );
+        Arc::make_mut(&mut state.config).rss.feed_redis.redis_prefix = prefix.clone();
+        state.usage = UsageRecorder::start(state.redis.pool.clone(), &prefix);
+        Arc::make_mut(&mut state.authz)
+            .admin_user_ids
+            .push(TEST_ADMIN_ID);
+        Some(Self::with_state(state))
+    }
+
+    /// The app over `state`, e.g. one from `try_new` with a connection swapped
+    pub fn with_state(state: AppState) -> Self {
+        let prefix = state
+            .config
+            .server
+            .api_prefix
+            .trim_end_matches('/')
+            .to_string();
+        let router = build_router(state.clone()).layer(middleware::from_fn(fake_auth));
+        TestApp {
+            router,
+            state,
+            prefix,
+        }
+    }
+
+    pub async fn send(&self, request: Request<Body>) -> TestResponse {
+        let response = self
+            .router
+            .clone()
+            .oneshot(request)
+            .await
+            .expect("router is infallible");
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        TestResponse {
+            status,
+            headers,
+            body,
+        }
+    }
+
+    pub fn request(&self, method: Method, path: &str, user_id: Option<i64>) -> RequestBuilder {
+        RequestBuilder {
+            method,
+            uri: format!("{}{}", self.prefix, path),
+            user_id,
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    /// Status only; the body is dropped unread (safe for streaming endpoints)
+    pub async fn status(&self, request: Request<Body>) -> StatusCode {
+        self.router
+            .clone()
+            .oneshot(request)
+            .await
+            .expect("router is infallible")
+            .status()
+    }
+
+    pub async fn get(&self, path: &str, user_id: i64) -> TestResponse {
+        self.send(self.request(Method::GET, path, Some(user_id)).build())
+            .await
+    }
+
+    pub async fn post<B: Serialize>(&self, path: &str, user_id: i64, body: &B) -> TestResponse {
+        self.send(
+            self.request(Method::POST, path, Some(user_id))
+                .json(body)
+                .build(),
+        )
+        .await
+    }
+
+    pub async fn delete(&self, path: &str, user_id: i64) -> TestResponse {
+        self.send(self.request(Method::DELETE, path, Some(user_id)).build())
+            .await
+    }
+}
+
+pub struct RequestBuilder {
+    method: Method,
+    uri: String,
+    user_id: Option<i64>,
+    headers: Vec<(String, String)>,
+    body: Option<Vec<u8>>,
+}
+
+impl RequestBuilder {
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn json<B: Serialize>(mut self, body: &B) -> Self {
+        self.body = Some(serde_json::to_vec(body).expect("serialize body"));
+        self
+    }
+
+    pub fn build(self) -> Request<Body> {
+        let mut builder = Request::builder().method(self.method).uri(self.uri);
+        if let Some(user_id) = self.user_id {
+            builder = builder.header(TEST_USER_HEADER, user_id.to_string());
+        }
+        for (name, value) in self.headers {
+            builder = builder.header(name, value);
+        }
+        let body = match self.body {
+            Some(bytes) => {
+                builder = builder.header("content-type", "application/json");
+                Body::from(bytes)
+            }
+            None => Body::empty(),
+        };
+        builder.body(body).expect("valid request")
+    }
+}
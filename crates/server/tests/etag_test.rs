@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+use server::{
+    app::api_routers,
+    middlewares::etag::{Conditional, IfNoneMatch, content_etag},
+};
+
+fn header(value: &str) -> IfNoneMatch {
+    IfNoneMatch(Some(value.to_string()))
+}
+
+#[test]
+fn test_content_etag() {
+    let tag = content_etag(&json!({ "a": 1, "b": [1, 2] })).unwrap();
+    assert!(tag.starts_with('"') && tag.ends_with('"'), "{tag}");
+    assert_eq!(tag.len(), 34);
+    // map order does not matter, content does
+    let ordered = HashMap::from([("a", 1), ("b", 2)]);
+    let reversed: HashMap<_, _> = [("b", 2), ("a", 1)].into_iter().collect();
+    assert_eq!(content_etag(&ordered), content_etag(&reversed));
+    assert_ne!(
+        content_etag(&ordered),
+        content_etag(&HashMap::from([("a", 1), ("b", 3)]))
+    );
+}
+
+#[test]
+fn test_if_none_match_comparison() {
+    let tag = "\"1-2\"";
+    assert!(!IfNoneMatch(None).matches(tag));
+    assert!(header("\"1-2\"").matches(tag));
+    assert!(header("W/\"1-2\"").matches(tag));
+    assert!(header("\"0-1\", \"1-2\"").matches(tag));
+    assert!(header("*").matches(tag));
+    assert!(!header("\"1-3\"").matches(tag));
+    assert!(!header("1-2").matches(tag));
+}
+
+#[tokio::test]
+async fn test_respond_tags_the_loaded_body() {
+    let tag = content_etag(&7).unwrap();
+
+    let response: Conditional<i32> = header(&tag).respond(|| async { Ok(7) }).await.unwrap();
+    assert!(matches!(
+        response,
+        Conditional::NotModified(ref t) if *t == tag
+    ));
+
+    // the data changed behind the tag the client holds
+    let response = header(&tag).respond(|| async { Ok(8) }).await.unwrap();
+    assert!(matches!(
+        response,
+        Conditional::Fresh { etag: Some(ref t), body: 8 } if *t != tag
+    ));
+
+    let response = IfNoneMatch(None).respond(|| async { Ok(7) }).await.unwrap();
+    assert!(matches!(
+        response,
+        Conditional::Fresh { etag: Some(ref t), body: 7 } if *t == tag
+    ));
+}
+
+#[test]
+fn test_etag_documented_in_openapi() {
+    let (_, api) = api_routers("").split_for_parts();
+    let doc = serde_json::to_value(&api).unwrap();
+
+    for path in ["/interests", "/subscriptions", "/user_rss"] {
+        let get = &doc["paths"][path]["get"];
+        assert!(
+            get["parameters"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|p| p["name"] == "If-None-Match" && p["in"] == "header"),
+            "{path}: If-None-Match not documented"
+        );
+        assert!(
+            get["responses"]["200"]["headers"].get("ETag").is_some(),
+            "{path}: ETag not documented on 200"
+        );
+        assert!(
+            get["responses"]["304"]["headers"].get("ETag").is_some(),
+            "{path}: 304 not documented"
+        );
+    }
+}
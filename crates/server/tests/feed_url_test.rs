@@ -0,0 +1,325 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::{Router, http::StatusCode, response::Redirect, routing::get};
+use common::{TEST_ADMIN_ID, TEST_USER_BASE, TestApp};
+use serde_json::json;
+use server::model::feed_url::{
+    FeedFetchLimits, FeedKind, is_public_ip, normalize_feed_url, sniff_feed, validate_feed_url,
+};
+
+const RSS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- generated -->
+<rss version="2.0"><channel><title>t</title></channel></rss>"#;
+const ATOM: &str =
+    r#"<?xml version="1.0"?><feed xmlns="http://www.w3.org/2005/Atom"><title>t</title></feed>"#;
+const RDF: &str = r#"<?xml version="1.0"?><rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"></rdf:RDF>"#;
+const HTML: &str = "<!DOCTYPE html><html><body>Not Found</body></html>";
+
+#[test]
+fn test_sniff_feed() {
+    assert_eq!(sniff_feed(RSS.as_bytes()), Ok(FeedKind::Rss));
+    assert_eq!(sniff_feed(ATOM.as_bytes()), Ok(FeedKind::Atom));
+    assert_eq!(sniff_feed(RDF.as_bytes()), Ok(FeedKind::Rdf));
+
+    let err = sniff_feed(HTML.as_bytes()).unwrap_err();
+    assert!(err.contains("<html>"), "{err}");
+    assert!(sniff_feed(b"").is_err());
+    assert!(sniff_feed(b"{\"items\": []}").is_err());
+}
+
+/// A local feed server: `/rss`, `/html`, `/missing` (404), `/slow` (answers after 5s),
+/// `/redirect` (to `/rss`), `/loop` (redirects to itself), `/big-rss` (a 1 MiB feed) and
+/// `/big-prolog` (1 MiB of comment before the root element)
+async fn feed_server() -> String {
+    let padding = "x".repeat(1024 * 1024);
+    let big_rss = format!("<rss version=\"2.0\"><channel><title>{padding}</title></channel></rss>");
+    let big_prolog =
+        format!("<?xml version=\"1.0\"?><!-- {padding} --><rss version=\"2.0\"></rss>");
+    let router = Router::new()
+        .route("/rss", get(|| async { RSS }))
+        .route("/html", get(|| async { HTML }))
+        .route(
+            "/missing",
+            get(|| async { (StatusCode::NOT_FOUND, "gone") }),
+        )
+        .route(
+            "/slow",
+            get(|| async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                RSS
+            }),
+        )
+        .route("/redirect", get(|| async { Redirect::temporary("/rss") }))
+        .route("/loop", get(|| async { Redirect::temporary("/loop") }))
+        .route("/big-rss", get(|| async move { big_rss }))
+        .route("/big-prolog", get(|| async move { big_prolog }));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router).await });
+    format!("http://{addr}")
+}
+
+/// The defaults, but the local test server is reachable
+fn local_limits(timeout: Duration) -> FeedFetchLimits {
+    FeedFetchLimits {
+        timeout,
+        max_bytes: 256 * 1024,
+        max_redirects: 3,
+        allow_private_addresses: true,
+    }
+}
+
+#[tokio::test]
+async fn test_validate_feed_url() {
+    let base = feed_server().await;
+    let limits = local_limits(Duration::from_millis(500));
+
+    assert_eq!(
+        validate_feed_url(&format!("{base}/rss"), &limits).await,
+        Ok(FeedKind::Rss)
+    );
+
+    let err = validate_feed_url(&format!("{base}/html"), &limits)
+        .await
+        .unwrap_err();
+    assert!(err.contains("not an RSS or Atom feed"), "{err}");
+
+    let err = validate_feed_url(&format!("{base}/missing"), &limits)
+        .await
+        .unwrap_err();
+    assert!(err.contains("404"), "{err}");
+
+    let err = validate_feed_url(&format!("{base}/slow"), &limits)
+        .await
+        .unwrap_err();
+    assert!(err.contains("no response within"), "{err}");
+
+    let err = validate_feed_url("ftp://example.com/feed.xml", &limits)
+        .await
+        .unwrap_err();
+    assert!(err.contains("unsupported URL scheme"), "{err}");
+}
+
+#[tokio::test]
+async fn test_validate_feed_url_limits_redirects_and_body() {
+    let base = feed_server().await;
+    let limits = local_limits(Duration::from_secs(5));
+
+    assert_eq!(
+        validate_feed_url(&format!("{base}/redirect"), &limits).await,
+        Ok(FeedKind::Rss)
+    );
+    let err = validate_feed_url(&format!("{base}/loop"), &limits)
+        .await
+        .unwrap_err();
+    assert!(err.contains("more than 3 redirects"), "{err}");
+
+    // the root element comes first: the rest of the body is not read
+    assert_eq!(
+        validate_feed_url(&format!("{base}/big-rss"), &limits).await,
+        Ok(FeedKind::Rss)
+    );
+    let err = validate_feed_url(&format!("{base}/big-prolog"), &limits)
+        .await
+        .unwrap_err();
+    assert!(
+        err.contains("no RSS or Atom root element in the first 262144 bytes"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_is_public_ip() {
+    for public in [
+        "93.184.216.34",
+        "8.8.8.8",
+        "2606:4700::1111",
+        "::ffff:8.8.8.8",
+    ] {
+        assert!(is_public_ip(public.parse().unwrap()), "{public}");
+    }
+    for internal in [
+        "127.0.0.1",
+        "10.1.2.3",
+        "172.16.0.1",
+        "192.168.1.1",
+        "169.254.169.254",
+        "100.64.0.1",
+        "0.0.0.0",
+        "255.255.255.255",
+        "224.0.0.1",
+        "240.0.0.1",
+        "::1",
+        "::",
+        "fe80::1",
+        "fd00::1",
+        "::ffff:127.0.0.1",
+        "::ffff:169.254.169.254",
+        "64:ff9b::a00:1",
+        "2001:db8::1",
+    ] {
+        assert!(!is_public_ip(internal.parse().unwrap()), "{internal}");
+    }
+}
+
+#[tokio::test]
+async fn test_validate_feed_url_refuses_internal_addresses() {
+    let base = feed_server().await;
+    let limits = FeedFetchLimits {
+        allow_private_addresses: false,
+        ..local_limits(Duration::from_secs(2))
+    };
+
+    for url in [
+        format!("{base}/rss"),
+        "http://169.254.169.254/latest/meta-data/".to_string(),
+        "http://[::1]/feed.xml".to_string(),
+        "http://10.0.0.1/feed.xml".to_string(),
+    ] {
+        let err = validate_feed_url(&url, &limits).await.unwrap_err();
+        assert!(err.contains("is not allowed"), "{url}: {err}");
+    }
+
+    // a name resolving to loopback is refused when connecting
+    let port = base.rsplit(':').next().unwrap();
+    let err = validate_feed_url(&format!("http://localhost:{port}/rss"), &limits)
+        .await
+        .unwrap_err();
+    assert!(err.contains("resolves to no public address"), "{err}");
+}
+
+#[tokio::test]
+async fn test_rss_create_rejects_invalid_feeds() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let base = feed_server().await;
+    let user_id = TEST_USER_BASE + 14;
+
+    // the local server is an internal address
+    let response = app
+        .post(
+            "/rss",
+            user_id,
+            &json!({
+                "channel": "test-harness",
+                "name": "Harness|Invalid",
+                "url": format!("{base}/rss"),
+            }),
+        )
+        .await;
+    assert_eq!(
+        response.status,
+        StatusCode::BAD_REQUEST,
+        "{}",
+        response.text()
+    );
+    assert!(response.text().contains("is not allowed"));
+
+    // only admins may skip the check
+    let response = app
+        .post(
+            "/rss",
+            user_id,
+            &json!({
+                "channel": "test-harness",
+                "name": "Harness|Invalid",
+                "url": format!("{base}/html"),
+                "skip_validation": true,
+            }),
+        )
+        .await;
+    assert_eq!(
+        response.status,
+        StatusCode::FORBIDDEN,
+        "{}",
+        response.text()
+    );
+}
+
+#[test]
+fn test_normalize_feed_url() {
+    let key = normalize_feed_url("https://export.arxiv.org/rss/cs.AI");
+    for same in [
+        "https://export.arxiv.org/rss/cs.AI/",
+        "HTTPS://Export.ArXiv.org/rss/cs.AI",
+        "https://export.arxiv.org:443/rss/cs.AI",
+        "https://export.arxiv.org/rss/cs.AI#top",
+        "  https://export.arxiv.org/rss/cs.AI  ",
+    ] {
+        assert_eq!(normalize_feed_url(same), key, "{same}");
+    }
+    for distinct in [
+        "http://export.arxiv.org/rss/cs.AI",
+        "https://export.arxiv.org/rss/cs.ai",
+        "https://export.arxiv.org:8443/rss/cs.AI",
+        "https://export.arxiv.org/rss/cs.AI?format=atom",
+    ] {
+        assert_ne!(normalize_feed_url(distinct), key, "{distinct}");
+    }
+    assert_eq!(
+        normalize_feed_url("https://example.com/"),
+        normalize_feed_url("https://example.com")
+    );
+    assert_eq!(normalize_feed_url("not a url/"), "not a url");
+}
+
+#[tokio::test]
+async fn test_rss_create_detects_duplicate_urls() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 14;
+    let create = |url: String, force: bool| {
+        let body = json!({
+            "channel": "test-harness",
+            "name": "Harness|Duplicate",
+            "url": url,
+            "skip_validation": true,
+            "force": force,
+        });
+        async move { app.post("/rss", TEST_ADMIN_ID, &body).await }
+    };
+
+    let response = create(
+        format!("https://Example.com/harness/dup-{user_id}.xml"),
+        false,
+    )
+    .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let source_id = response.json::<i32>().data;
+
+    let response = create(
+        format!("https://example.com/harness/dup-{user_id}.xml/"),
+        false,
+    )
+    .await;
+    assert_eq!(response.status, StatusCode::CONFLICT, "{}", response.text());
+    let body = response.json::<i32>();
+    assert!(!body.success);
+    assert_eq!(body.data, source_id);
+
+    // intentional duplicate, and another scheme is another feed
+    let mut created = vec![source_id];
+    for (url, force) in [
+        (
+            format!("https://example.com/harness/dup-{user_id}.xml"),
+            true,
+        ),
+        (
+            format!("http://example.com/harness/dup-{user_id}.xml"),
+            false,
+        ),
+    ] {
+        let response = create(url, force).await;
+        assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+        created.push(response.json::<i32>().data);
+    }
+
+    for id in created {
+        let response = app.delete(&format!("/rss/{id}"), TEST_ADMIN_ID).await;
+        assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    }
+}
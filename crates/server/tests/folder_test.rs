@@ -0,0 +1,148 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::{TEST_ADMIN_ID, TEST_USER_BASE, TestApp};
+use serde_json::{Value, json};
+use server::routers::feed::subscriptions::{SubscriptionSource, SubscriptionsCreateRequest};
+
+#[test]
+fn test_subscription_sources_accept_both_shapes() {
+    let request: SubscriptionsCreateRequest = serde_json::from_value(json!({
+        "source_ids": [1, { "source_id": 2, "folder": "Reading" }, { "source_id": 3 }],
+    }))
+    .unwrap();
+    let ids: Vec<i32> = request
+        .source_ids
+        .iter()
+        .map(SubscriptionSource::source_id)
+        .collect();
+    assert_eq!(ids, [1, 2, 3]);
+    assert!(matches!(request.source_ids[0], SubscriptionSource::Id(1)));
+    assert!(matches!(
+        &request.source_ids[1],
+        SubscriptionSource::WithFolder { folder: Some(f), .. } if f == "Reading"
+    ));
+    assert!(matches!(
+        request.source_ids[2],
+        SubscriptionSource::WithFolder { folder: None, .. }
+    ));
+}
+
+fn folder_of(subscriptions: &[Value], source_id: i32) -> Value {
+    subscriptions
+        .iter()
+        .find(|s| s["source_id"] == source_id)
+        .expect("subscription is listed")["folder"]
+        .clone()
+}
+
+#[tokio::test]
+async fn test_subscription_folders() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 15;
+
+    let response = app
+        .post(
+            "/rss",
+            TEST_ADMIN_ID,
+            &json!({
+                "channel": "test-harness",
+                "skip_validation": true,
+                "name": "Harness|Folders",
+                "url": format!("https://example.com/harness/folders-{user_id}.xml"),
+            }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let source_id = response.json::<i32>().data;
+
+    let response = app
+        .post(
+            "/subscriptions/one",
+            user_id,
+            &json!({ "source_id": source_id, "folder": "  Reading " }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+
+    let subscriptions = app
+        .get("/subscriptions", user_id)
+        .await
+        .json::<Vec<Value>>()
+        .data;
+    assert_eq!(folder_of(&subscriptions, source_id), "Reading");
+    let subscription_id = subscriptions
+        .iter()
+        .find(|s| s["source_id"] == source_id)
+        .unwrap()["id"]
+        .as_i64()
+        .unwrap();
+
+    let path = format!("/subscriptions/{subscription_id}/folder");
+    let response = app
+        .send(
+            app.request(Method::PATCH, &path, Some(user_id))
+                .json(&json!({ "folder": "Later" }))
+                .build(),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    assert_eq!(response.json::<Value>().data["folder"], "Later");
+
+    let response = app.get("/user_rss?group_by_folder=true", user_id).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let folders = response.json::<Value>().data["folders"].clone();
+    let later = folders
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|f| f["folder"] == "Later")
+        .expect("folder is listed");
+    assert!(
+        later["sources"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|s| s["id"] == source_id)
+    );
+    // without the flag the response keeps its old shape
+    let response = app.get("/user_rss", user_id).await;
+    assert!(response.json::<Value>().data.get("folders").is_none());
+
+    let response = app
+        .send(
+            app.request(Method::PATCH, &path, Some(user_id))
+                .json(&json!({ "folder": null }))
+                .build(),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let subscriptions = app
+        .get("/subscriptions", user_id)
+        .await
+        .json::<Vec<Value>>()
+        .data;
+    assert!(folder_of(&subscriptions, source_id).is_null());
+
+    // someone else's subscription
+    let response = app
+        .send(
+            app.request(Method::PATCH, &path, Some(user_id + 1))
+                .json(&json!({ "folder": "Stolen" }))
+                .build(),
+        )
+        .await;
+    assert_eq!(
+        response.status,
+        StatusCode::NOT_FOUND,
+        "{}",
+        response.text()
+    );
+
+    app.delete(&format!("/subscriptions/{subscription_id}"), user_id)
+        .await;
+    app.delete(&format!("/rss/{source_id}"), TEST_ADMIN_ID)
+        .await;
+}
@@ -0,0 +1,108 @@
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use serde_json::json;
+use server::model::group::group_by_day;
+
+/// (id, published at) fixtures straddling midnight in both Shanghai (UTC+8) and New York (UTC-4)
+fn fixtures() -> Vec<(i32, Option<DateTime<Utc>>)> {
+    [
+        (1, Some("2024-05-12T17:30:00Z")), // 05-13 01:30 Shanghai, 05-12 13:30 New York
+        (2, Some("2024-05-12T15:59:00Z")), // 05-12 23:59 Shanghai, 05-12 11:59 New York
+        (3, Some("2024-05-12T03:30:00Z")), // 05-12 11:30 Shanghai, 05-11 23:30 New York
+        (4, None),
+        (5, Some("2024-05-11T20:00:00Z")), // 05-12 04:00 Shanghai, 05-11 16:00 New York
+    ]
+    .into_iter()
+    .map(|(id, date)| (id, date.map(|d| d.parse().unwrap())))
+    .collect()
+}
+
+fn snapshot(tz: Tz, next: Option<Option<DateTime<Utc>>>) -> serde_json::Value {
+    let (sections, continues) = group_by_day(fixtures(), tz, |item| item.1, next);
+    let sections: Vec<_> = sections
+        .into_iter()
+        .map(|s| {
+            json!({
+                "date": s.date,
+                "count": s.count,
+                "ids": s.papers.iter().map(|p| p.0).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+    json!({ "sections": sections, "section_continues": continues })
+}
+
+#[test]
+fn test_sections_in_shanghai() {
+    assert_eq!(
+        snapshot(Tz::Asia__Shanghai, None),
+        json!({
+            "sections": [
+                { "date": "2024-05-13", "count": 1, "ids": [1] },
+                { "date": "2024-05-12", "count": 3, "ids": [2, 3, 5] },
+                { "date": null, "count": 1, "ids": [4] },
+            ],
+            "section_continues": false,
+        })
+    );
+}
+
+#[test]
+fn test_sections_in_new_york() {
+    assert_eq!(
+        snapshot(Tz::America__New_York, None),
+        json!({
+            "sections": [
+                { "date": "2024-05-12", "count": 2, "ids": [1, 2] },
+                { "date": "2024-05-11", "count": 2, "ids": [3, 5] },
+                { "date": null, "count": 1, "ids": [4] },
+            ],
+            "section_continues": false,
+        })
+    );
+}
+
+/// `section_continues` of the dated fixtures followed by a page starting at `next`
+fn continues(tz: Tz, next: Option<Option<DateTime<Utc>>>) -> bool {
+    let dated: Vec<_> = fixtures()
+        .into_iter()
+        .filter(|item| item.1.is_some())
+        .collect();
+    group_by_day(dated, tz, |item| item.1, next).1
+}
+
+#[test]
+fn test_section_continues_on_next_page() {
+    // 05-11 23:00 New York, same day as the last section
+    let next: DateTime<Utc> = "2024-05-12T03:00:00Z".parse().unwrap();
+    assert!(continues(Tz::America__New_York, Some(Some(next))));
+    // 05-12 11:00 Shanghai: same instant, but the last Shanghai section is 05-12 as well
+    assert!(continues(Tz::Asia__Shanghai, Some(Some(next))));
+
+    // 05-11 07:59 Shanghai: a new day
+    let next: DateTime<Utc> = "2024-05-10T23:59:00Z".parse().unwrap();
+    assert!(!continues(Tz::Asia__Shanghai, Some(Some(next))));
+    assert!(!continues(Tz::Asia__Shanghai, Some(None)));
+
+    // the undated section comes last and continues into more undated papers
+    assert_eq!(
+        snapshot(Tz::Asia__Shanghai, Some(None))["section_continues"],
+        json!(true)
+    );
+    assert_eq!(
+        snapshot(Tz::Asia__Shanghai, Some(Some(next)))["section_continues"],
+        json!(false)
+    );
+}
+
+#[test]
+fn test_empty_page_has_no_sections() {
+    let (sections, continues) = group_by_day(
+        Vec::<(i32, Option<DateTime<Utc>>)>::new(),
+        Tz::UTC,
+        |item| item.1,
+        None,
+    );
+    assert!(sections.is_empty());
+    assert!(!continues);
+}
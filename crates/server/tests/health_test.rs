@@ -0,0 +1,109 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use common::TestApp;
+use sea_orm::{
+    ConnAcquireErr, ConnectionTrait, DbErr, SqlxPostgresConnector, TransactionTrait,
+    sqlx::postgres::PgPoolOptions,
+};
+use serde_json::Value;
+use server::routers::health::{
+    CHECK_TIMEOUT, CheckStatus, PoolStats, check_redis_pool, database_pool_stats, run_check,
+};
+
+#[tokio::test]
+async fn test_check_times_out() {
+    let check = run_check::<String>(
+        Duration::from_millis(50),
+        std::future::pending::<Result<(), String>>(),
+    )
+    .await;
+    assert_eq!(check.status, CheckStatus::Down);
+    assert!(check.error.unwrap().contains("50ms"));
+
+    let check = run_check::<String>(CHECK_TIMEOUT, async { Ok(()) }).await;
+    assert_eq!(check.status, CheckStatus::Up);
+    assert!(check.error.is_none());
+}
+
+#[tokio::test]
+async fn test_unreachable_redis_is_down() {
+    // nothing listens on port 1
+    let manager = bb8_redis::RedisConnectionManager::new("redis://127.0.0.1:1").unwrap();
+    let pool = bb8::Pool::builder()
+        .connection_timeout(Duration::from_secs(5))
+        .build_unchecked(manager);
+
+    let check = check_redis_pool(&pool, CHECK_TIMEOUT).await;
+    assert_eq!(check.status, CheckStatus::Down);
+    assert!(check.error.is_some());
+    // the probe gives up on time even though the pool would wait longer
+    assert!(check.latency_ms < 2 * CHECK_TIMEOUT.as_millis() as u64);
+}
+
+#[tokio::test]
+async fn test_ready_reports_dependencies() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let response = app.get("/health/ready", 1).await;
+    let body = response.json::<Value>().data;
+    for dependency in ["database", "redis", "apalis_redis"] {
+        assert!(
+            body[dependency]["latency_ms"].is_u64(),
+            "{dependency}: {body}"
+        );
+        assert_eq!(body[dependency]["status"], "up", "{dependency}: {body}");
+    }
+    let expected = if body["ready"] == true {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    assert_eq!(response.status, expected);
+}
+
+#[tokio::test]
+async fn test_ready_reports_database_pool() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let response = app.get("/health/ready", 1).await;
+    let pool: PoolStats =
+        serde_json::from_value(response.json::<Value>().data["database_pool"].clone())
+            .expect("database_pool");
+    assert!(pool.max_connections > 0);
+    assert!(pool.size <= pool.max_connections);
+    assert_eq!(pool.in_use, pool.size - pool.idle);
+}
+
+#[tokio::test]
+async fn test_pool_exhaustion_is_a_typed_error() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let options = app
+        .state
+        .conn
+        .get_postgres_connection_pool()
+        .connect_options()
+        .as_ref()
+        .clone();
+    let tiny = PgPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_millis(200))
+        .connect_lazy_with(options);
+    let conn = SqlxPostgresConnector::from_sqlx_postgres_pool(tiny);
+
+    // the transaction holds the only connection
+    let txn = conn.begin().await.unwrap();
+    let err = conn.execute_unprepared("SELECT 1").await.unwrap_err();
+    assert!(
+        matches!(err, DbErr::ConnectionAcquire(ConnAcquireErr::Timeout)),
+        "{err:?}"
+    );
+    assert_eq!(database_pool_stats(&conn).in_use, 1);
+    txn.rollback().await.unwrap();
+}
@@ -0,0 +1,206 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::{TEST_ADMIN_ID, TEST_USER_BASE, TestApp, TestResponse};
+use redis::AsyncCommands;
+use serde::Serialize;
+use serde_json::json;
+use server::consts::{IDEMPOTENCY_KEY_HEADER, IDEMPOTENT_REPLAYED_HEADER};
+use server::middlewares::idempotency::MAX_IDEMPOTENT_REQUEST_BYTES;
+use server::routers::feed::subscriptions::SubscriptionCreateResult;
+use server::state::idempotency::{IdempotencyClaim, IdempotencyStore, request_hash};
+
+async fn post_with_key<B: Serialize>(
+    app: &TestApp,
+    path: &str,
+    user_id: i64,
+    key: &str,
+    body: &B,
+) -> TestResponse {
+    app.send(
+        app.request(Method::POST, path, Some(user_id))
+            .header(IDEMPOTENCY_KEY_HEADER, key)
+            .json(body)
+            .build(),
+    )
+    .await
+}
+
+fn replayed(response: &TestResponse) -> bool {
+    response.headers.get(IDEMPOTENT_REPLAYED_HEADER).is_some()
+}
+
+#[tokio::test]
+async fn test_idempotency_key_replays_the_first_response() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 49;
+    let key = format!("replay-{}", uuid::Uuid::new_v4());
+
+    let response = app
+        .post(
+            "/rss",
+            TEST_ADMIN_ID,
+            &json!({
+                "channel": "test-harness",
+                "skip_validation": true,
+                "name": "Harness|Idempotency|Source",
+                "url": format!("https://example.com/harness/idempotency-{user_id}.xml"),
+            }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let source_id = response.json::<i32>().data;
+
+    let body = json!({ "source_id": source_id });
+    let first = post_with_key(&app, "/subscriptions/one", user_id, &key, &body).await;
+    assert_eq!(first.status, StatusCode::OK, "{}", first.text());
+    assert!(!replayed(&first));
+    let created = first.json::<SubscriptionCreateResult>().data;
+    assert!(matches!(created, SubscriptionCreateResult::Created { .. }));
+
+    // run again, the subscription would already exist: the retry gets the first response
+    let retry = post_with_key(&app, "/subscriptions/one", user_id, &key, &body).await;
+    assert_eq!(retry.status, StatusCode::OK, "{}", retry.text());
+    assert!(replayed(&retry));
+    assert_eq!(retry.body, first.body);
+
+    // without the key the request runs
+    let response = app.post("/subscriptions/one", user_id, &body).await;
+    assert!(!replayed(&response));
+    assert!(matches!(
+        response.json::<SubscriptionCreateResult>().data,
+        SubscriptionCreateResult::AlreadySubscribed { .. }
+    ));
+
+    // keys are not usable with malformed values
+    let response = post_with_key(&app, "/subscriptions/one", user_id, "with space", &body).await;
+    assert_eq!(
+        response.status,
+        StatusCode::BAD_REQUEST,
+        "{}",
+        response.text()
+    );
+
+    app.delete(
+        &format!("/subscriptions/{}", created.id().unwrap()),
+        user_id,
+    )
+    .await;
+    app.delete(&format!("/rss/{source_id}"), TEST_ADMIN_ID)
+        .await;
+}
+
+#[tokio::test]
+async fn test_idempotency_key_expires() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 50;
+    let key = format!("expiry-{}", uuid::Uuid::new_v4());
+    let body = json!({ "ids": [i32::MAX - 1] });
+
+    let first = post_with_key(&app, "/batch-delete", user_id, &key, &body).await;
+    assert_eq!(first.status, StatusCode::OK, "{}", first.text());
+    let retry = post_with_key(&app, "/batch-delete", user_id, &key, &body).await;
+    assert!(replayed(&retry));
+
+    let redis_key = IdempotencyStore::new(&app.state).key(user_id, "POST /batch-delete", &key);
+    let mut conn = app.state.redis.pool.get().await.unwrap();
+    let ttl: i64 = conn.ttl(&redis_key).await.unwrap();
+    assert!(ttl > 0);
+    let _: () = conn.pexpire(&redis_key, 1).await.unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+    let response = post_with_key(&app, "/batch-delete", user_id, &key, &body).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    assert!(!replayed(&response));
+}
+
+#[tokio::test]
+async fn test_idempotency_key_in_flight_conflicts() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 51;
+    let body = json!({ "paper_ids": [i32::MAX - 1], "read_all": false });
+
+    // a first request still running
+    let key = format!("in-flight-{}", uuid::Uuid::new_v4());
+    let store = IdempotencyStore::new(&app.state);
+    let redis_key = store.key(user_id, "POST /mark-as-read", &key);
+    assert_eq!(
+        store
+            .claim(
+                &redis_key,
+                &request_hash(&serde_json::to_vec(&body).unwrap()),
+                60
+            )
+            .await
+            .unwrap(),
+        IdempotencyClaim::Claimed
+    );
+    let response = post_with_key(&app, "/mark-as-read", user_id, &key, &body).await;
+    assert_eq!(response.status, StatusCode::CONFLICT, "{}", response.text());
+    store.release(&redis_key).await.unwrap();
+
+    // two submissions at once: one runs, the other is refused or replayed
+    let key = format!("concurrent-{}", uuid::Uuid::new_v4());
+    let (a, b) = tokio::join!(
+        post_with_key(&app, "/mark-as-read", user_id, &key, &body),
+        post_with_key(&app, "/mark-as-read", user_id, &key, &body),
+    );
+    let (ran, other) = if a.status == StatusCode::OK && !replayed(&a) {
+        (&a, &b)
+    } else {
+        (&b, &a)
+    };
+    assert_eq!(ran.status, StatusCode::OK, "{}", ran.text());
+    assert!(!replayed(ran));
+    assert!(
+        other.status == StatusCode::CONFLICT || (replayed(other) && other.body == ran.body),
+        "{}",
+        other.text()
+    );
+}
+
+#[tokio::test]
+async fn test_idempotency_key_is_bound_to_the_body() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 58;
+    let key = format!("body-{}", uuid::Uuid::new_v4());
+    let body = json!({ "ids": [i32::MAX - 1] });
+
+    let first = post_with_key(&app, "/batch-delete", user_id, &key, &body).await;
+    assert_eq!(first.status, StatusCode::OK, "{}", first.text());
+
+    // same key, another body: refused rather than answered with the first response
+    let other = json!({ "ids": [i32::MAX - 2] });
+    let response = post_with_key(&app, "/batch-delete", user_id, &key, &other).await;
+    assert_eq!(
+        response.status,
+        StatusCode::UNPROCESSABLE_ENTITY,
+        "{}",
+        response.text()
+    );
+    assert!(!replayed(&response));
+
+    // the original body still replays
+    let retry = post_with_key(&app, "/batch-delete", user_id, &key, &body).await;
+    assert!(replayed(&retry));
+    assert_eq!(retry.body, first.body);
+
+    // bodies too large to fingerprint are refused
+    let key = format!("large-{}", uuid::Uuid::new_v4());
+    let large = json!({ "ids": [0], "padding": "x".repeat(MAX_IDEMPOTENT_REQUEST_BYTES) });
+    let response = post_with_key(&app, "/batch-delete", user_id, &key, &large).await;
+    assert_eq!(
+        response.status,
+        StatusCode::PAYLOAD_TOO_LARGE,
+        "{}",
+        response.text()
+    );
+}
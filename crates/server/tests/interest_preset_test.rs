@@ -0,0 +1,156 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TEST_ADMIN_ID, TEST_USER_BASE, TestApp};
+use serde_json::{Value, json};
+use server::model::preset::{InterestPresetInput, PresetMerge, merge_preset};
+
+fn strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_merge_keeps_existing_and_adds_missing() {
+    let existing = strings(&["RLHF", "diffusion models"]);
+    let preset = strings(&["large language models", "rlhf", " instruction tuning "]);
+
+    assert_eq!(
+        merge_preset(&existing, &preset, 10),
+        PresetMerge::Merged {
+            interests: strings(&[
+                "RLHF",
+                "diffusion models",
+                "large language models",
+                "instruction tuning"
+            ]),
+            added: strings(&["large language models", "instruction tuning"]),
+        }
+    );
+}
+
+#[test]
+fn test_merge_is_idempotent() {
+    let preset = strings(&["large language models", "RLHF"]);
+    let PresetMerge::Merged { interests, .. } = merge_preset(&[], &preset, 10) else {
+        panic!("expected a merge");
+    };
+    assert_eq!(
+        merge_preset(&interests, &preset, 10),
+        PresetMerge::Unchanged
+    );
+    // even at the limit
+    assert_eq!(merge_preset(&interests, &preset, 2), PresetMerge::Unchanged);
+}
+
+#[test]
+fn test_merge_over_limit_is_a_conflict() {
+    let existing = strings(&["a", "b", "RLHF"]);
+    let preset = strings(&["rlhf", "c", "d"]);
+
+    assert_eq!(
+        merge_preset(&existing, &preset, 4),
+        PresetMerge::Conflict {
+            must_remove: 1,
+            removable: strings(&["a", "b"]),
+        }
+    );
+}
+
+#[test]
+fn test_validate_normalizes_input() {
+    let input = InterestPresetInput {
+        name: "  LLM researcher starter pack ".to_string(),
+        description: Some("  ".to_string()),
+        interests: strings(&["LLMs", " llms", "", "RLHF"]),
+        channel_hints: strings(&["arxiv", "arxiv"]),
+    };
+    let input = input.validate(10).unwrap();
+    assert_eq!(input.name, "LLM researcher starter pack");
+    assert_eq!(input.description, None);
+    assert_eq!(input.interests, strings(&["LLMs", "RLHF"]));
+    assert_eq!(input.channel_hints, strings(&["arxiv"]));
+
+    let too_many = InterestPresetInput {
+        name: "big".to_string(),
+        description: None,
+        interests: strings(&["a", "b", "c"]),
+        channel_hints: Vec::new(),
+    };
+    assert!(too_many.validate(2).is_err());
+    let empty = InterestPresetInput {
+        name: "empty".to_string(),
+        description: None,
+        interests: strings(&[" "]),
+        channel_hints: Vec::new(),
+    };
+    assert!(empty.validate(10).is_err());
+}
+
+#[tokio::test]
+async fn test_preset_lifecycle() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 11;
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    let interest = format!("preset interest {suffix}");
+    let body = json!({
+        "name": format!("test preset {suffix}"),
+        "description": "integration test",
+        "interests": [interest],
+        "channel_hints": ["arxiv"],
+    });
+
+    // admin only
+    let response = app.post("/admin/interest-presets", user_id, &body).await;
+    assert_eq!(response.status, StatusCode::FORBIDDEN);
+
+    let response = app
+        .post("/admin/interest-presets", TEST_ADMIN_ID, &body)
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let id = response.json::<Value>().data["id"].as_i64().unwrap();
+
+    // duplicate name
+    let response = app
+        .post("/admin/interest-presets", TEST_ADMIN_ID, &body)
+        .await;
+    assert_eq!(response.status, StatusCode::CONFLICT);
+
+    let response = app.get("/interest-presets", user_id).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let presets = response.json::<Vec<Value>>().data;
+    assert!(presets.iter().any(|p| p["id"] == id));
+
+    let response = app
+        .post(
+            &format!("/interest-presets/{id}/apply"),
+            user_id,
+            &json!({}),
+        )
+        .await;
+    let data = response.json::<Value>().data;
+    match response.status {
+        StatusCode::OK => {
+            assert_eq!(data["status"], "applied");
+            assert_eq!(data["added"], json!([interest]));
+            assert!(data["request_id"].is_string());
+        }
+        // the test user already sits at the interest limit
+        StatusCode::CONFLICT => assert_eq!(data["status"], "conflict"),
+        status => panic!("unexpected status {status}"),
+    }
+
+    let response = app
+        .delete(&format!("/admin/interest-presets/{id}"), TEST_ADMIN_ID)
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let response = app
+        .post(
+            &format!("/interest-presets/{id}/apply"),
+            user_id,
+            &json!({}),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,67 @@
+use server::model::suggestion::{
+    InterestSuggestion, ReadPaper, suggest_interests, suggestion_window,
+};
+
+fn paper(title: &str, categories: Option<&str>) -> ReadPaper {
+    ReadPaper {
+        title: title.to_string(),
+        categories: categories.map(str::to_string),
+    }
+}
+
+#[test]
+fn test_suggestions_rank_by_paper_count() {
+    let papers = [
+        paper(
+            "Diffusion Transformers for Protein Design",
+            Some("[\"q-bio.BM\", \"cs.LG\"]"),
+        ),
+        paper("Protein folding with diffusion", Some("{cs.LG,q-bio.BM}")),
+        paper("Diffusion diffusion diffusion", Some("cs.CV")),
+    ];
+    let suggestions = suggest_interests(&papers, &[], 3);
+    assert_eq!(
+        suggestions,
+        [
+            InterestSuggestion {
+                term: "diffusion".to_string(),
+                paper_count: 3,
+            },
+            InterestSuggestion {
+                term: "cs.lg".to_string(),
+                paper_count: 2,
+            },
+            InterestSuggestion {
+                term: "protein".to_string(),
+                paper_count: 2,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_suggestions_skip_existing_interests() {
+    let papers = [
+        paper("Graph neural networks for molecules", Some("cs.LG")),
+        paper("Molecules and graph transformers", Some("cs.LG")),
+    ];
+    let existing = ["Graph Learning".to_string(), "CS.LG".to_string()];
+    let terms: Vec<String> = suggest_interests(&papers, &existing, 10)
+        .into_iter()
+        .map(|suggestion| suggestion.term)
+        .collect();
+    assert!(!terms.iter().any(|term| term == "graph" || term == "cs.lg"));
+    assert_eq!(terms[0], "molecules");
+    // stopwords and short words are never suggested
+    assert!(!terms.iter().any(|term| term == "networks" || term == "for"));
+}
+
+#[test]
+fn test_suggestion_window_bounds() {
+    assert_eq!(suggestion_window(None, None).unwrap(), (10, 30));
+    assert_eq!(suggestion_window(Some(50), Some(365)).unwrap(), (50, 365));
+    assert!(suggestion_window(Some(0), None).is_err());
+    assert!(suggestion_window(Some(51), None).is_err());
+    assert!(suggestion_window(None, Some(0)).is_err());
+    assert!(suggestion_window(None, Some(366)).is_err());
+}
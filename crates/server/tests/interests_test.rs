@@ -0,0 +1,139 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TEST_USER_BASE, TestApp};
+use serde_json::{Value, json};
+use server::{
+    query::user_interests::InterestDetail,
+    routers::feed::interests::{InterestChange, InterestChangeStatus},
+    state::pending_interests::PendingInterests,
+};
+
+#[tokio::test]
+async fn test_add_and_remove_one_interest() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 28;
+    let interest = format!("single interest {}", uuid::Uuid::new_v4().simple());
+
+    let response = app
+        .post("/interests/one", user_id, &json!({ "interest": "   " }))
+        .await;
+    assert!(!response.status.is_success(), "{}", response.text());
+
+    let response = app
+        .post(
+            "/interests/one",
+            user_id,
+            &json!({ "interest": format!("  {interest} ") }),
+        )
+        .await;
+    match response.status {
+        StatusCode::OK => {
+            let change = response.json::<InterestChange>().data;
+            assert_eq!(change.status, InterestChangeStatus::Added);
+            assert!(change.request_id.is_some());
+            assert!(change.interests.contains(&interest));
+        }
+        // the test user already sits at the interest limit
+        StatusCode::CONFLICT => assert!(response.text().contains("limit")),
+        status => panic!("unexpected status {status}: {}", response.text()),
+    }
+
+    // ids can only be removed by their owner
+    let response = app.delete("/interests/9223372036854775807", user_id).await;
+    assert_eq!(
+        response.status,
+        StatusCode::NOT_FOUND,
+        "{}",
+        response.text()
+    );
+
+    let response = app.get("/interests?with_ids=true", user_id).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let items = response.json::<Vec<Value>>().data;
+    for item in &items {
+        assert!(item["id"].is_i64(), "{item}");
+        assert!(item["interest"].is_string(), "{item}");
+    }
+
+    // plain strings stay the default, with their own ETag
+    let plain = app.get("/interests", user_id).await;
+    assert_eq!(plain.status, StatusCode::OK, "{}", plain.text());
+    assert!(
+        plain
+            .json::<Vec<Value>>()
+            .data
+            .iter()
+            .all(|item| item.is_string())
+    );
+    if let (Some(a), Some(b)) = (plain.headers.get("etag"), response.headers.get("etag"))
+        && !items.is_empty()
+    {
+        assert_ne!(a, b);
+    }
+}
+
+#[tokio::test]
+async fn test_detailed_interests() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 29;
+
+    let response = app.get("/interests/detailed", user_id).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let detailed = response.json::<Vec<InterestDetail>>().data;
+
+    let mut plain = app
+        .get("/interests", user_id)
+        .await
+        .json::<Vec<String>>()
+        .data;
+    let mut names: Vec<String> = detailed.iter().map(|item| item.interest.clone()).collect();
+    plain.sort();
+    names.sort();
+    assert_eq!(names, plain);
+    // the harness user never had a verification
+    assert!(detailed.iter().all(|item| item.matched_paper_count == 0));
+}
+
+#[tokio::test]
+async fn test_quick_successive_adds_keep_both() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 59;
+    let first = format!("quick interest a {}", uuid::Uuid::new_v4().simple());
+    let second = format!("quick interest b {}", uuid::Uuid::new_v4().simple());
+
+    // both sent before either update is applied
+    let (a, b) = tokio::join!(
+        app.post("/interests/one", user_id, &json!({ "interest": first })),
+        app.post("/interests/one", user_id, &json!({ "interest": second })),
+    );
+    if [a.status, b.status].contains(&StatusCode::CONFLICT) {
+        // the test user already sits at the interest limit
+        return;
+    }
+    let a = a.json::<InterestChange>().data;
+    let b = b.json::<InterestChange>().data;
+    assert_eq!(a.status, InterestChangeStatus::Added);
+    assert_eq!(b.status, InterestChangeStatus::Added);
+
+    // whichever was queued second started from the first
+    let later = if a.interests.len() > b.interests.len() {
+        &a
+    } else {
+        &b
+    };
+    assert!(later.interests.contains(&first), "{:?}", later.interests);
+    assert!(later.interests.contains(&second), "{:?}", later.interests);
+    let queued = PendingInterests::new(&app.state)
+        .get(user_id)
+        .await
+        .unwrap()
+        .expect("queued list");
+    assert!(queued.contains(&first) && queued.contains(&second));
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TEST_ADMIN_ID, TEST_USER_BASE, TestApp};
+use serde_json::{Value, json};
+use server::state::kill_switch::KillSwitch;
+
+#[test]
+fn test_switch_names() {
+    for switch in KillSwitch::ALL {
+        assert_eq!(
+            serde_json::to_value(switch).unwrap(),
+            Value::String(switch.as_str().to_string())
+        );
+    }
+}
+
+async fn flip(app: &TestApp, switch: &str, engaged: bool) {
+    let response = app
+        .send(
+            app.request(
+                axum::http::Method::PUT,
+                "/admin/kill-switches",
+                Some(TEST_ADMIN_ID),
+            )
+            .json(&json!({ "switch": switch, "engaged": engaged, "reason": "kill switch test" }))
+            .build(),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let state = response.json::<Value>().data;
+    assert_eq!(state["engaged"], engaged);
+    assert_eq!(state["updated_by"], TEST_ADMIN_ID);
+}
+
+#[tokio::test]
+async fn test_kill_switches_are_honored() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 9;
+
+    // only admins flip switches
+    let response = app
+        .send(
+            app.request(
+                axum::http::Method::PUT,
+                "/admin/kill-switches",
+                Some(user_id),
+            )
+            .json(&json!({ "switch": "verify_dispatch", "engaged": true }))
+            .build(),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::FORBIDDEN);
+
+    flip(&app, "verify_dispatch", true).await;
+    let response = app
+        .post("/verify", user_id, &json!({ "channel": "test-harness" }))
+        .await;
+    assert_eq!(
+        response.status,
+        StatusCode::SERVICE_UNAVAILABLE,
+        "{}",
+        response.text()
+    );
+    assert!(response.text().contains("verify_dispatch"));
+    flip(&app, "verify_dispatch", false).await;
+
+    flip(&app, "sse_streams", true).await;
+    let response = app.post("/stream-verify", user_id, &json!({})).await;
+    assert_eq!(response.status, StatusCode::OK);
+    let body = response.text();
+    assert!(body.contains("event: error"), "{body}");
+    assert!(body.contains("sse_streams"), "{body}");
+    flip(&app, "sse_streams", false).await;
+
+    let response = app.get("/admin/kill-switches", TEST_ADMIN_ID).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let states = response.json::<Vec<Value>>().data;
+    assert_eq!(states.len(), KillSwitch::ALL.len());
+    assert!(states.iter().all(|state| state["engaged"] == false));
+    assert!(
+        states
+            .iter()
+            .any(|state| state["switch"] == "sse_streams" && state["reason"] == "kill switch test")
+    );
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use std::collections::HashSet;
+
+use axum::http::StatusCode;
+use common::{TEST_USER_BASE, TestApp};
+use serde_json::json;
+use server::query::mark_read_undo::{MarkReadItem, MarkReadOutcome, MarkReadReport};
+use server::routers::feed::feeds::MarkReadResponse;
+
+#[test]
+fn test_report_mixes_marked_read_and_foreign_ids() {
+    // 1 unread and owned, 2 already read, 3 another user's, 1 repeated
+    let in_scope = HashSet::from([1, 2]);
+    let marked = HashSet::from([1]);
+    let report = MarkReadReport::classify(&[1, 2, 3, 1], &in_scope, &marked);
+    assert_eq!(report.count, 1);
+    assert_eq!(
+        report.results,
+        [
+            MarkReadItem {
+                paper_id: 1,
+                outcome: MarkReadOutcome::Marked,
+            },
+            MarkReadItem {
+                paper_id: 2,
+                outcome: MarkReadOutcome::AlreadyRead,
+            },
+            MarkReadItem {
+                paper_id: 3,
+                outcome: MarkReadOutcome::NotFound,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_report_serializes_snake_case_outcomes() {
+    let report = MarkReadReport::classify(&[7], &HashSet::from([7]), &HashSet::new());
+    assert_eq!(
+        serde_json::to_value(MarkReadResponse::Detailed(report)).unwrap(),
+        json!({ "count": 0, "results": [{ "paper_id": 7, "outcome": "already_read" }] })
+    );
+    assert_eq!(
+        serde_json::to_value(MarkReadResponse::Count(3)).unwrap(),
+        json!(3)
+    );
+}
+
+#[tokio::test]
+async fn test_mark_read_detailed_response() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    // a user without verifications: every id is someone else's or unknown
+    let user_id = TEST_USER_BASE + 33;
+    let ids = [i32::MAX - 1, i32::MAX - 2];
+
+    let response = app
+        .post(
+            "/mark-as-read",
+            user_id,
+            &json!({ "paper_ids": ids, "read_all": false }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    assert_eq!(
+        response.json::<MarkReadResponse>().data,
+        MarkReadResponse::Count(0)
+    );
+
+    let response = app
+        .post(
+            "/mark-as-read",
+            user_id,
+            &json!({ "paper_ids": ids, "read_all": false, "detailed": true }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let MarkReadResponse::Detailed(report) = response.json::<MarkReadResponse>().data else {
+        panic!("expected a detailed report");
+    };
+    assert_eq!(report.count, 0);
+    assert!(
+        report
+            .results
+            .iter()
+            .all(|item| item.outcome == MarkReadOutcome::NotFound)
+    );
+    assert_eq!(report.results.len(), ids.len());
+}
@@ -0,0 +1,20 @@
+use server::routers::feed::verify_stats::MatchRateSample;
+
+#[test]
+fn test_match_rate_without_completions() {
+    let sample = MatchRateSample::from_counts(0, 0, 0, 0);
+    assert_eq!(sample.match_rate, None);
+    assert_eq!(sample.avg_tokens_per_match, None);
+    assert_eq!(sample.recommend_max_match_limit(100_000), None);
+}
+
+#[test]
+fn test_match_rate_and_recommendation() {
+    // 10 matched out of 40 completed (35 success + 5 fail), 50k tokens spent
+    let sample = MatchRateSample::from_counts(10, 35, 5, 50_000);
+    assert_eq!(sample.completed, 40);
+    assert_eq!(sample.match_rate, Some(0.25));
+    assert_eq!(sample.avg_tokens_per_match, Some(5_000.0));
+    assert_eq!(sample.recommend_max_match_limit(120_000), Some(24));
+    assert_eq!(sample.recommend_max_match_limit(0), None);
+}
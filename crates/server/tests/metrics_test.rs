@@ -0,0 +1,49 @@
+mod common;
+
+use axum::http::{StatusCode, header::CONTENT_TYPE};
+use common::{TEST_USER_BASE, TestApp};
+
+#[tokio::test]
+async fn test_metrics_endpoint_exposes_families() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 43;
+    for path in ["/health", "/rss/channels", "/rss/2147483647"] {
+        app.get(path, user_id).await;
+    }
+
+    // public, like the health checks
+    let response = app
+        .send(
+            app.request(axum::http::Method::GET, "/metrics", None)
+                .build(),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    assert!(
+        response
+            .headers
+            .get(CONTENT_TYPE)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain")
+    );
+    let body = response.text();
+    for family in [
+        "http_requests_total",
+        "http_request_duration_seconds",
+        "verify_jobs_dispatched_total",
+        "sse_connections_open",
+    ] {
+        assert!(
+            body.contains(&format!("# TYPE {family} ")),
+            "{family} missing:\n{body}"
+        );
+    }
+    // path parameters stay templated
+    assert!(body.contains("/rss/{id}"), "{body}");
+    assert!(!body.contains("/rss/2147483647"), "{body}");
+    assert!(body.contains(r#"status="4xx""#), "{body}");
+}
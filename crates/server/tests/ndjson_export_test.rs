@@ -0,0 +1,83 @@
+mod common;
+
+use axum::http::{Method, StatusCode, header::CONTENT_TYPE};
+use common::{TEST_USER_BASE, TestApp};
+use serde_json::Value;
+
+/// Ids of the papers of an NDJSON body, checking every line is one JSON object
+fn ndjson_ids(body: &str) -> Vec<Value> {
+    body.lines()
+        .map(|line| {
+            let paper: Value = serde_json::from_str(line).expect("one JSON object per line");
+            assert!(paper.is_object(), "{line}");
+            paper["id"].clone()
+        })
+        .collect()
+}
+
+/// Total of the `export` series of a usage report
+fn exports(report: &Value) -> i64 {
+    report["series"]
+        .as_array()
+        .expect("series")
+        .iter()
+        .find(|series| series["event"] == "export")
+        .expect("export series")["total"]
+        .as_i64()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_ndjson_export_matches_json_listing() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 42;
+    let response = app.get("/usage?window=7d", user_id).await;
+    let exports_before = exports(&response.json::<Value>().data);
+
+    for query in [
+        "ignore_pagination=true",
+        "ignore_pagination=true&channel=arxiv&keyword=learning",
+    ] {
+        let path = format!("/all-verified-papers?{query}");
+        let response = app.get(&path, user_id).await;
+        assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+        let expected: Vec<Value> = response.json::<Value>().data["papers"]
+            .as_array()
+            .expect("papers")
+            .iter()
+            .map(|paper| paper["id"].clone())
+            .collect();
+
+        let response = app
+            .send(
+                app.request(Method::GET, &path, Some(user_id))
+                    .header("accept", "application/json;q=0.5, application/x-ndjson")
+                    .build(),
+            )
+            .await;
+        assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+        assert_eq!(
+            response.headers.get(CONTENT_TYPE).unwrap(),
+            "application/x-ndjson"
+        );
+        assert_eq!(ndjson_ids(&response.text()), expected, "{query}");
+    }
+
+    // each stream counts as one export
+    app.state.usage.flush().await;
+    let response = app.get("/usage?window=7d", user_id).await;
+    assert_eq!(exports(&response.json::<Value>().data), exports_before + 2);
+
+    // paginated requests keep the JSON envelope
+    let response = app
+        .send(
+            app.request(Method::GET, "/all-verified-papers", Some(user_id))
+                .header("accept", "application/x-ndjson")
+                .build(),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    assert!(response.json::<Value>().data["pagination"].is_object());
+}
@@ -0,0 +1,69 @@
+use serde_json::json;
+use server::model::page::Pagination;
+use server::routers::feed::feeds::AllVerifiedPapersRequest;
+
+#[test]
+fn test_pagination_last_partial_page() {
+    // 45 items, 20 per page -> pages of 20, 20, 5
+    let first = Pagination::new(1, 20, 45);
+    assert_eq!(first.total_pages, 3);
+    assert!(first.has_next);
+    assert!(!first.has_prev);
+
+    let last = Pagination::new(3, 20, 45);
+    assert_eq!(last.total_pages, 3);
+    assert!(
+        !last.has_next,
+        "last partial page must not report a next page"
+    );
+    assert!(last.has_prev);
+}
+
+#[test]
+fn test_pagination_exact_and_empty() {
+    let exact = Pagination::new(2, 20, 40);
+    assert_eq!(exact.total_pages, 2);
+    assert!(!exact.has_next);
+
+    let empty = Pagination::new(1, 20, 0);
+    assert_eq!(empty.total_pages, 0);
+    assert!(!empty.has_next);
+    assert!(!empty.has_prev);
+
+    let all = Pagination::all(12);
+    assert_eq!(all.page_size, 12);
+    assert!(!all.has_next && !all.has_prev);
+}
+
+#[test]
+fn test_applied_filters_ignore_blank_values() {
+    let request: AllVerifiedPapersRequest = serde_json::from_value(json!({
+        "page": "2",
+        "user_interest_ids": "  ",
+        "keyword": "  graph neural  ",
+        "channel": "",
+    }))
+    .expect("valid request");
+
+    let filters = request.applied_filters();
+    assert_eq!(filters.user_interest_ids, None);
+    assert_eq!(filters.channel, None);
+    assert_eq!(filters.keyword.as_deref(), Some("graph neural"));
+
+    let body = serde_json::to_value(&filters).unwrap();
+    assert!(body.get("user_interest_ids").is_none());
+    assert!(body.get("channel").is_none());
+}
+
+#[test]
+fn test_applied_filters_parse_interest_ids() {
+    let request: AllVerifiedPapersRequest = serde_json::from_value(json!({
+        "user_interest_ids": "1, 2,,3",
+        "rss_source_id": "42",
+    }))
+    .expect("valid request");
+
+    let filters = request.applied_filters();
+    assert_eq!(filters.user_interest_ids, Some(vec![1, 2, 3]));
+    assert_eq!(filters.rss_source_id, Some(42));
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::{TEST_USER_BASE, TestApp};
+use serde_json::json;
+use server::model::note::{MAX_NOTE_BYTES, PaperNoteInput};
+
+fn input(note: &str) -> PaperNoteInput {
+    PaperNoteInput {
+        note: note.to_string(),
+    }
+}
+
+#[test]
+fn test_note_is_trimmed_and_limited() {
+    assert_eq!(
+        input("  re-read for related work \n").validate().unwrap(),
+        "re-read for related work"
+    );
+    assert!(input(" \n ").validate().is_err());
+    assert!(input(&"a".repeat(MAX_NOTE_BYTES)).validate().is_ok());
+    assert!(input(&"a".repeat(MAX_NOTE_BYTES + 1)).validate().is_err());
+    // the limit is in bytes, not characters
+    assert!(
+        input(&"é".repeat(MAX_NOTE_BYTES / 2 + 1))
+            .validate()
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_note_requires_a_visible_paper() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 35;
+    let path = format!("/papers/{}/note", i32::MAX - 3);
+
+    let request = app
+        .request(Method::PUT, &path, Some(user_id))
+        .json(&json!({ "note": "read later" }))
+        .build();
+    let response = app.send(request).await;
+    assert_eq!(
+        response.status,
+        StatusCode::NOT_FOUND,
+        "{}",
+        response.text()
+    );
+
+    let response = app.delete(&path, user_id).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    assert!(!response.json::<bool>().data);
+}
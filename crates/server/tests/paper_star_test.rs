@@ -0,0 +1,48 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TEST_USER_BASE, TestApp};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_star_requires_a_visible_paper() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 36;
+    let path = format!("/papers/{}/star", i32::MAX - 4);
+
+    let response = app.post(&path, user_id, &json!({})).await;
+    assert_eq!(
+        response.status,
+        StatusCode::NOT_FOUND,
+        "{}",
+        response.text()
+    );
+
+    // unstarring is idempotent
+    for _ in 0..2 {
+        let response = app.delete(&path, user_id).await;
+        assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+        assert!(!response.json::<bool>().data);
+    }
+
+    let response = app.get("/starred-count", user_id).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    assert_eq!(response.json::<u64>().data, 0);
+}
+
+#[tokio::test]
+async fn test_batch_delete_accepts_force() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 36;
+    let ids = [i32::MAX - 4, i32::MAX - 5];
+
+    for body in [json!({ "ids": ids }), json!({ "ids": ids, "force": true })] {
+        let response = app.post("/batch-delete", user_id, &body).await;
+        assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+        assert_eq!(response.json::<u64>().data, 0);
+    }
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::http::{StatusCode, header::RETRY_AFTER};
+use common::{TEST_USER_BASE, TestApp};
+use redis::AsyncCommands;
+use serde_json::json;
+use server::{
+    config::server_rss_config,
+    middlewares::rate_limit::VERIFY_BUCKET,
+    state::rate_limit::{RateDecision, RateLimiter},
+};
+
+#[tokio::test]
+async fn test_token_bucket_limits_and_refills() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 25;
+    let limiter = RateLimiter::new(&app.state);
+    let key = limiter.key("harness", user_id);
+    let mut conn = app.state.redis.pool.get().await.unwrap();
+    let _: () = conn.del(&key).await.unwrap();
+
+    // 60 per minute: a full bucket of 60, then one token back every second
+    for _ in 0..60 {
+        assert_eq!(
+            limiter.acquire("harness", user_id, 60).await.unwrap(),
+            RateDecision::Allowed
+        );
+    }
+    let RateDecision::Limited { retry_after } =
+        limiter.acquire("harness", user_id, 60).await.unwrap()
+    else {
+        panic!("61st call within a minute was allowed");
+    };
+    assert!(retry_after <= Duration::from_secs(1), "{retry_after:?}");
+
+    // the limiter state goes away on its own
+    let ttl: i64 = conn.pttl(&key).await.unwrap();
+    assert!(ttl > 0 && ttl <= 61_000, "ttl {ttl}");
+
+    tokio::time::sleep(retry_after + Duration::from_millis(50)).await;
+    assert_eq!(
+        limiter.acquire("harness", user_id, 60).await.unwrap(),
+        RateDecision::Allowed
+    );
+    assert!(matches!(
+        limiter.acquire("harness", user_id, 60).await.unwrap(),
+        RateDecision::Limited { .. }
+    ));
+
+    let _: () = conn.del(&key).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_verify_rejected_with_retry_after() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let per_minute = server_rss_config().verify_rate_limit_per_minute;
+    if per_minute == 0 {
+        return;
+    }
+    let user_id = TEST_USER_BASE + 26;
+    let limiter = RateLimiter::new(&app.state);
+    let key = limiter.key(VERIFY_BUCKET, user_id);
+    let mut conn = app.state.redis.pool.get().await.unwrap();
+    let _: () = conn.del(&key).await.unwrap();
+
+    // use up the bucket without queueing real jobs
+    for _ in 0..per_minute {
+        limiter
+            .acquire(VERIFY_BUCKET, user_id, per_minute)
+            .await
+            .unwrap();
+    }
+
+    for path in ["/verify", "/stream-verify"] {
+        let response = app.post(path, user_id, &json!({})).await;
+        assert_eq!(
+            response.status,
+            StatusCode::TOO_MANY_REQUESTS,
+            "{path}: {}",
+            response.text()
+        );
+        let retry_after: u64 = response.headers[RETRY_AFTER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((1..=60).contains(&retry_after), "{retry_after}");
+    }
+
+    let _: () = conn.del(&key).await.unwrap();
+}
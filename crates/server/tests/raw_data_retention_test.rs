@@ -0,0 +1,138 @@
+//! Papers whose `raw_data` the worker's retention job pruned still render from their parsed
+//! columns.
+
+mod common;
+
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use common::{TEST_ADMIN_ID, TEST_USER_BASE, TestApp, insert_row};
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+use serde_json::{Value, json};
+use worker::retention::prune_raw_data;
+
+#[tokio::test]
+async fn test_pruned_paper_still_renders() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 60;
+    let conn = &app.state.conn;
+
+    let response = app
+        .post(
+            "/rss",
+            TEST_ADMIN_ID,
+            &json!({
+                "channel": "test-harness",
+                "skip_validation": true,
+                "name": "Harness|Retention",
+                "url": format!("https://example.com/harness/retention-{user_id}.xml"),
+            }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let source_id = response.json::<i32>().data;
+    let response = app
+        .post(
+            "/subscriptions/one",
+            user_id,
+            &json!({ "source_id": source_id }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+
+    // an enriched paper older than the cutoff, verified as a match for the user
+    let interest_id = insert_row(
+        conn,
+        "user_interests",
+        &[
+            ("user_id", &user_id.to_string()),
+            ("interest", "'retention harness'"),
+        ],
+    )
+    .await;
+    let created_at = format!("'{}'", (Utc::now() - Duration::days(40)).to_rfc3339());
+    let paper_id = insert_row(
+        conn,
+        "rss_papers",
+        &[
+            ("source_id", &source_id.to_string()),
+            ("title", "'Pruned but parsed'"),
+            ("link", "'https://example.com/harness/pruned'"),
+            ("description", "'Parsed description'"),
+            (
+                "raw_data",
+                "'<item><title>Pruned but parsed</title></item>'",
+            ),
+            ("pub_date", &created_at),
+            ("created_at", &created_at),
+        ],
+    )
+    .await;
+    insert_row(
+        conn,
+        "user_paper_verifications",
+        &[
+            ("user_id", &user_id.to_string()),
+            ("paper_id", &paper_id.to_string()),
+            ("interest_id", &interest_id.to_string()),
+            ("match", "'Yes'"),
+            ("unread", "TRUE"),
+        ],
+    )
+    .await;
+
+    let report = prune_raw_data(conn, Utc::now() - Duration::days(30), 100, false)
+        .await
+        .expect("prune raw data");
+    assert!(report.pruned >= 1);
+    let row = conn
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT raw_data IS NULL AS pruned FROM rss_papers WHERE id = $1",
+            [paper_id.into()],
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(row.try_get::<bool>("", "pruned").unwrap());
+
+    let response = app
+        .get(
+            &format!("/all-verified-papers?rss_source_id={source_id}&cache_bypass=true"),
+            user_id,
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let papers = response.json::<Value>().data["papers"].clone();
+    let paper = papers
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|paper| paper["id"] == paper_id)
+        .unwrap_or_else(|| panic!("pruned paper missing from {papers}"));
+    assert_eq!(paper["title"], "Pruned but parsed");
+    assert_eq!(paper["description"], "Parsed description");
+
+    let response = app.get(&format!("/papers/{paper_id}"), user_id).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let detail = response.json::<Value>().data;
+    assert_eq!(detail["paper"]["id"], paper_id);
+    assert_eq!(detail["paper"]["title"], "Pruned but parsed");
+    assert_eq!(detail["paper"]["description"], "Parsed description");
+
+    for (table, id) in [
+        ("user_paper_verifications", "paper_id"),
+        ("rss_papers", "id"),
+    ] {
+        conn.execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            format!("DELETE FROM {table} WHERE {id} = $1"),
+            [paper_id.into()],
+        ))
+        .await
+        .unwrap();
+    }
+    app.delete(&format!("/rss/{source_id}"), TEST_ADMIN_ID)
+        .await;
+}
@@ -0,0 +1,243 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use common::{TEST_ADMIN_ID, TEST_USER_BASE, TestApp};
+use redis::AsyncCommands;
+use sea_orm::{ConnectionTrait, SqlxPostgresConnector, sqlx::postgres::PgPoolOptions};
+use serde_json::json;
+use server::config::ReadReplicaConfig;
+use server::routers::feed::subscriptions::SubscriptionCreateResult;
+use server::routers::health::database_pool_stats;
+
+#[test]
+fn test_read_replica_falls_back_without_read_url() {
+    assert!(ReadReplicaConfig::default().connect_options().is_none());
+    let blank = ReadReplicaConfig {
+        read_url: Some("  ".to_string()),
+        ..Default::default()
+    };
+    assert!(blank.connect_options().is_none());
+
+    let config = ReadReplicaConfig {
+        read_url: Some("postgresql://replica:5432/wisland_feed".to_string()),
+        max_connections: Some(7),
+        acquire_timeout_ms: Some(1500),
+        ..Default::default()
+    };
+    let options = config.connect_options().expect("replica configured");
+    assert_eq!(options.get_url(), "postgresql://replica:5432/wisland_feed");
+    assert_eq!(options.get_max_connections(), Some(7));
+    assert_eq!(
+        options.get_acquire_timeout(),
+        Some(Duration::from_millis(1500))
+    );
+}
+
+#[tokio::test]
+async fn test_read_conn_is_the_primary_without_replica() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    if app.state.replica.is_some() {
+        return;
+    }
+    assert!(std::ptr::eq(app.state.read_conn(), &app.state.conn));
+    assert!(std::ptr::eq(
+        app.state.read_conn_for(TEST_USER_BASE + 48).await,
+        &app.state.conn
+    ));
+}
+
+/// A user's listings go to the replica until they write, then to the primary for
+/// `read_after_write_secs`. Cached and ETagged responses never come from the replica.
+///
+/// The "replica" is unreachable, so any query routed to it fails.
+#[tokio::test]
+async fn test_reads_follow_the_users_writes() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let (user_id, other_id) = (TEST_USER_BASE + 56, TEST_USER_BASE + 57);
+    let options = app
+        .state
+        .conn
+        .get_postgres_connection_pool()
+        .connect_options()
+        .as_ref()
+        .clone()
+        .port(1);
+    let unreachable = SqlxPostgresConnector::from_sqlx_postgres_pool(
+        PgPoolOptions::new()
+            .acquire_timeout(Duration::from_secs(2))
+            .connect_lazy_with(options),
+    );
+    let mut state = app.state.clone();
+    state.replica = Some(unreachable);
+    let app = TestApp::with_state(state);
+    let mut conn = app.state.redis.pool.get().await.unwrap();
+    let write_key = |user_id: i64| {
+        format!(
+            "{}:read-after-write:{user_id}",
+            app.state.config.rss.feed_redis.redis_prefix
+        )
+    };
+    let _: () = conn
+        .del(vec![write_key(user_id), write_key(other_id)])
+        .await
+        .unwrap();
+
+    let response = app.get("/all-verified-papers", user_id).await;
+    assert_ne!(response.status, StatusCode::OK, "served by the replica");
+    assert!(!std::ptr::eq(
+        app.state.read_conn_for(user_id).await,
+        &app.state.conn
+    ));
+    for path in ["/interests", "/subscriptions", "/user_rss"] {
+        let response = app.get(path, user_id).await;
+        assert_eq!(
+            response.status,
+            StatusCode::OK,
+            "{path}: {}",
+            response.text()
+        );
+        assert!(response.headers.contains_key("etag"), "{path}");
+    }
+
+    let response = app
+        .post(
+            "/mark-as-read",
+            user_id,
+            &json!({ "paper_ids": [i32::MAX - 1], "read_all": false }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let ttl: i64 = conn.ttl(write_key(user_id)).await.unwrap();
+    assert!(
+        (1..=app.state.read_after_write_secs as i64).contains(&ttl),
+        "{ttl}"
+    );
+    assert!(std::ptr::eq(
+        app.state.read_conn_for(user_id).await,
+        &app.state.conn
+    ));
+    for path in [
+        "/all-verified-papers",
+        "/unverified-papers?page=1&page_size=5",
+        "/rss",
+        "/rss/channels",
+    ] {
+        let response = app.get(path, user_id).await;
+        assert_eq!(
+            response.status,
+            StatusCode::OK,
+            "{path}: {}",
+            response.text()
+        );
+    }
+
+    // other users still read the replica
+    let response = app.get("/all-verified-papers", other_id).await;
+    assert_ne!(response.status, StatusCode::OK, "served by the replica");
+}
+
+/// Listings are served by the replica, while writes never reach it.
+///
+/// The "replica" is the test database in read-only mode, so a write routed to it fails.
+#[tokio::test]
+async fn test_writes_stay_on_the_primary() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 48;
+    let options = app
+        .state
+        .conn
+        .get_postgres_connection_pool()
+        .connect_options()
+        .as_ref()
+        .clone()
+        .options([("default_transaction_read_only", "on")]);
+    let read_only = SqlxPostgresConnector::from_sqlx_postgres_pool(
+        PgPoolOptions::new().connect_lazy_with(options),
+    );
+    // the read-only pool refuses writes
+    assert!(
+        read_only
+            .execute_unprepared("DELETE FROM rss_sources WHERE id = -1")
+            .await
+            .is_err()
+    );
+    let mut state = app.state.clone();
+    state.replica = Some(read_only.clone());
+    let app = TestApp::with_state(state);
+
+    // listings
+    for path in [
+        "/all-verified-papers",
+        "/unverified-papers?page=1&page_size=5",
+        "/rss",
+        "/rss/channels",
+    ] {
+        let response = app.get(path, user_id).await;
+        assert_eq!(
+            response.status,
+            StatusCode::OK,
+            "{path}: {}",
+            response.text()
+        );
+    }
+    assert!(database_pool_stats(&read_only).size > 0);
+
+    // writes
+    let response = app
+        .post(
+            "/rss",
+            TEST_ADMIN_ID,
+            &json!({
+                "channel": "test-harness",
+                "skip_validation": true,
+                "name": "Harness|Replica|Source",
+                "url": format!("https://example.com/harness/replica-{user_id}.xml"),
+            }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let source_id = response.json::<i32>().data;
+
+    let response = app
+        .post(
+            "/subscriptions/one",
+            user_id,
+            &json!({ "source_id": source_id }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let subscription_id = response
+        .json::<SubscriptionCreateResult>()
+        .data
+        .id()
+        .expect("created");
+
+    let response = app
+        .post(
+            "/mark-as-read",
+            user_id,
+            &json!({ "paper_ids": [i32::MAX - 1], "read_all": false }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let response = app
+        .post("/batch-delete", user_id, &json!({ "ids": [i32::MAX - 1] }))
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let response = app
+        .delete(&format!("/subscriptions/{subscription_id}"), user_id)
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let response = app
+        .delete(&format!("/rss/{source_id}"), TEST_ADMIN_ID)
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+}
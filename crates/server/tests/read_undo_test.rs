@@ -0,0 +1,106 @@
+use server::query::mark_read_undo::ReadUndoSnapshot;
+
+#[test]
+fn test_snapshot_keeps_exact_ids_under_cap() {
+    let rows = [(1, true), (2, false), (5, true), (9, true)];
+    assert_eq!(
+        ReadUndoSnapshot::from_rows(&rows, None, 3),
+        Some(ReadUndoSnapshot::Ids { ids: vec![1, 5, 9] })
+    );
+}
+
+#[test]
+fn test_snapshot_without_unread_rows_is_empty() {
+    let rows = [(1, false), (2, false)];
+    assert_eq!(
+        ReadUndoSnapshot::from_rows(&rows, None, 10),
+        Some(ReadUndoSnapshot::Ids { ids: vec![] })
+    );
+    assert_eq!(
+        ReadUndoSnapshot::from_rows(&[], Some("arxiv"), 10),
+        Some(ReadUndoSnapshot::Ids { ids: vec![] })
+    );
+}
+
+#[test]
+fn test_large_snapshot_falls_back_to_ranges() {
+    // 1..=4 unread, 7 read, 8..=9 unread, 12 read, 20 unread
+    let rows = [
+        (1, true),
+        (2, true),
+        (3, true),
+        (4, true),
+        (7, false),
+        (8, true),
+        (9, true),
+        (12, false),
+        (20, true),
+    ];
+    assert_eq!(
+        ReadUndoSnapshot::from_rows(&rows, Some("arxiv"), 3),
+        Some(ReadUndoSnapshot::Ranges {
+            channel: Some("arxiv".to_string()),
+            ranges: vec![(1, 4), (8, 9), (20, 20)],
+        })
+    );
+}
+
+#[test]
+fn test_ranges_skip_leading_and_trailing_read_rows() {
+    let rows = [(1, false), (2, true), (3, true), (4, true), (5, false)];
+    assert_eq!(
+        ReadUndoSnapshot::from_rows(&rows, None, 2),
+        Some(ReadUndoSnapshot::Ranges {
+            channel: None,
+            ranges: vec![(2, 4)],
+        })
+    );
+}
+
+#[test]
+fn test_ranges_only_need_the_read_row_ending_each_run() {
+    // what `list_read_state` returns: unread rows and the first read row after each run
+    let all = [
+        (1, false),
+        (2, true),
+        (3, true),
+        (4, false),
+        (5, false),
+        (6, false),
+        (8, true),
+        (9, false),
+        (10, false),
+    ];
+    let trimmed = [(2, true), (3, true), (4, false), (8, true), (9, false)];
+    assert_eq!(
+        ReadUndoSnapshot::from_rows(&trimmed, None, 2),
+        ReadUndoSnapshot::from_rows(&all, None, 2)
+    );
+    assert_eq!(
+        ReadUndoSnapshot::from_rows(&trimmed, None, 2),
+        Some(ReadUndoSnapshot::Ranges {
+            channel: None,
+            ranges: vec![(2, 3), (8, 8)],
+        })
+    );
+}
+
+#[test]
+fn test_fragmented_snapshot_is_rejected() {
+    // alternating read state: as many ranges as ids
+    let rows: Vec<(i64, bool)> = (0..10).map(|id| (id, id % 2 == 0)).collect();
+    assert_eq!(ReadUndoSnapshot::from_rows(&rows, None, 4), None);
+}
+
+#[test]
+fn test_snapshot_round_trips_through_json() {
+    let snapshot = ReadUndoSnapshot::Ranges {
+        channel: Some("nature".to_string()),
+        ranges: vec![(3, 8)],
+    };
+    let raw = serde_json::to_string(&snapshot).unwrap();
+    assert_eq!(
+        serde_json::from_str::<ReadUndoSnapshot>(&raw).unwrap(),
+        snapshot
+    );
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::{TEST_USER_BASE, TestApp};
+use serde_json::Value;
+use server::middlewares::log::REQUEST_ID_HEADER;
+
+#[tokio::test]
+async fn test_request_id_round_trips() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 44;
+
+    let response = app
+        .send(
+            app.request(Method::GET, "/rss/channels", Some(user_id))
+                .header("x-request-id", "trace-abc-123")
+                .build(),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    assert_eq!(
+        response.headers.get(&REQUEST_ID_HEADER).unwrap(),
+        "trace-abc-123"
+    );
+
+    // generated when missing, and different for every request
+    let first = app.get("/rss/channels", user_id).await;
+    let second = app.get("/rss/channels", user_id).await;
+    let first = first.headers.get(&REQUEST_ID_HEADER).unwrap();
+    let second = second.headers.get(&REQUEST_ID_HEADER).unwrap();
+    assert!(!first.is_empty());
+    assert_ne!(first, second);
+
+    // unusable incoming ids are replaced
+    let response = app
+        .send(
+            app.request(Method::GET, "/rss/channels", Some(user_id))
+                .header("x-request-id", &"x".repeat(500))
+                .build(),
+        )
+        .await;
+    let id = response.headers.get(&REQUEST_ID_HEADER).unwrap();
+    assert!(id.len() <= 128);
+}
+
+#[tokio::test]
+async fn test_error_body_carries_request_id() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 44;
+    let response = app
+        .send(
+            app.request(Method::GET, "/rss/2147483647/status", Some(user_id))
+                .header("x-request-id", "trace-error-1")
+                .build(),
+        )
+        .await;
+    assert_eq!(
+        response.status,
+        StatusCode::NOT_FOUND,
+        "{}",
+        response.text()
+    );
+    let body: Value = serde_json::from_str(&response.text()).unwrap();
+    assert_eq!(body["request_id"], "trace-error-1");
+    assert_eq!(
+        response.headers.get(&REQUEST_ID_HEADER).unwrap(),
+        "trace-error-1"
+    );
+}
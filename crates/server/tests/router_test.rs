@@ -0,0 +1,715 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::{TEST_ADMIN_ID, TEST_USER_BASE, TestApp};
+use serde_json::{Value, json};
+use server::{query::subscription_folders, routers::feed::subscriptions::SubscriptionCreateResult};
+
+#[tokio::test]
+async fn test_unauthenticated_request_is_rejected() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let response = app
+        .send(
+            app.request(axum::http::Method::GET, "/interests", None)
+                .build(),
+        )
+        .await;
+    assert!(!response.status.is_success(), "{}", response.text());
+}
+
+#[tokio::test]
+async fn test_all_verified_papers_listing() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 1;
+
+    let response = app
+        .get("/all-verified-papers?page=1&page_size=5", user_id)
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let body = response.json::<Value>();
+    assert!(body.success);
+    assert_eq!(body.data["pagination"]["page"], 1);
+    assert_eq!(body.data["pagination"]["page_size"], 5);
+    assert!(body.data["papers"].is_array());
+}
+
+#[tokio::test]
+async fn test_tolerant_query_deserializers() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 2;
+
+    // `page` arrives as a string and `rss_source_id` is empty: both must be accepted
+    let response = app
+        .get(
+            "/all-verified-papers?page=2&page_size=3&rss_source_id=&user_interest_ids=",
+            user_id,
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let body = response.json::<Value>();
+    assert_eq!(body.data["pagination"]["page"], 2);
+    assert_eq!(body.data["pagination"]["page_size"], 3);
+    assert!(body.data["applied_filters"].get("rss_source_id").is_none());
+    assert!(
+        body.data["applied_filters"]
+            .get("user_interest_ids")
+            .is_none()
+    );
+
+    let response = app.get("/all-verified-papers?page=abc", user_id).await;
+    assert!(!response.status.is_success(), "{}", response.text());
+
+    // Blank entries are skipped
+    let response = app
+        .get("/all-verified-papers?user_interest_ids=1,,2,", user_id)
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    assert_eq!(
+        response.json::<Value>().data["applied_filters"]["user_interest_ids"],
+        json!([1, 2])
+    );
+
+    // A bad entry is a 400 naming the parameter and the token
+    let response = app
+        .get("/all-verified-papers?user_interest_ids=1,abc", user_id)
+        .await;
+    assert_eq!(
+        response.status,
+        StatusCode::BAD_REQUEST,
+        "{}",
+        response.text()
+    );
+    let text = response.text();
+    assert!(text.contains("user_interest_ids"), "{text}");
+    assert!(text.contains("`abc`"), "{text}");
+}
+
+#[tokio::test]
+async fn test_subscriptions_crud() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 3;
+
+    let response = app
+        .post(
+            "/rss",
+            TEST_ADMIN_ID,
+            &json!({
+                "channel": "test-harness",
+                "skip_validation": true,
+                "name": "Harness|Subscriptions",
+                "url": format!("https://example.com/harness/{user_id}.xml"),
+            }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let source_id = response.json::<i32>().data;
+
+    let response = app
+        .post(
+            "/subscriptions/one",
+            user_id,
+            &json!({ "source_id": source_id }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+
+    let response = app.get("/subscriptions", user_id).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let subscriptions = response.json::<Vec<Value>>().data;
+    let subscription = subscriptions
+        .iter()
+        .find(|s| s["source_id"] == source_id)
+        .expect("subscription is listed");
+    let subscription_id = subscription["id"].as_i64().expect("subscription id");
+
+    let response = app
+        .delete(&format!("/subscriptions/{subscription_id}"), user_id)
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+
+    let response = app.get("/subscriptions", user_id).await;
+    let subscriptions = response.json::<Vec<Value>>().data;
+    assert!(subscriptions.iter().all(|s| s["source_id"] != source_id));
+
+    // sources are shared by every subscriber: only admins delete them
+    let response = app.delete(&format!("/rss/{source_id}"), user_id).await;
+    assert_eq!(
+        response.status,
+        StatusCode::FORBIDDEN,
+        "{}",
+        response.text()
+    );
+    let response = app
+        .delete(&format!("/rss/{source_id}"), TEST_ADMIN_ID)
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+}
+
+#[tokio::test]
+async fn test_interests_update() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 4;
+
+    let response = app
+        .post(
+            "/interests",
+            user_id,
+            &json!({ "interests": ["graph neural networks"] }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let request_id = response.json::<String>().data;
+    assert!(!request_id.is_empty());
+
+    let too_many: Vec<String> = (0..=app.state.config.rss.max_prompt_number)
+        .map(|i| format!("interest {i}"))
+        .collect();
+    let response = app
+        .post("/interests", user_id, &json!({ "interests": too_many }))
+        .await;
+    assert!(!response.status.is_success(), "{}", response.text());
+}
+
+#[tokio::test]
+async fn test_subscription_change_visible_despite_cache() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 5;
+
+    let response = app
+        .post(
+            "/rss",
+            TEST_ADMIN_ID,
+            &json!({
+                "channel": "test-harness",
+                "skip_validation": true,
+                "name": "Harness|Cache",
+                "url": format!("https://example.com/harness/cache-{user_id}.xml"),
+            }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let source_id = response.json::<i32>().data;
+    let source_key = source_id.to_string();
+
+    // Warm the cache
+    let response = app.get("/all-verified-papers", user_id).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    assert!(
+        response.json::<Value>().data["source_map"]
+            .get(&source_key)
+            .is_none()
+    );
+
+    let response = app
+        .post(
+            "/subscriptions/one",
+            user_id,
+            &json!({ "source_id": source_id }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let subscription_id = response
+        .json::<SubscriptionCreateResult>()
+        .data
+        .id()
+        .expect("subscribed");
+
+    let response = app.get("/all-verified-papers", user_id).await;
+    assert!(
+        response.json::<Value>().data["source_map"]
+            .get(&source_key)
+            .is_some()
+    );
+
+    let response = app
+        .delete(&format!("/subscriptions/{subscription_id}"), user_id)
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+
+    let response = app.get("/all-verified-papers", user_id).await;
+    assert!(
+        response.json::<Value>().data["source_map"]
+            .get(&source_key)
+            .is_none()
+    );
+
+    app.delete(&format!("/rss/{source_id}"), TEST_ADMIN_ID)
+        .await;
+}
+
+#[tokio::test]
+async fn test_mark_all_read_undo() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 6;
+
+    let response = app
+        .post(
+            "/mark-as-read",
+            user_id,
+            &json!({ "paper_ids": [], "read_all": true }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let token = response
+        .headers
+        .get("x-undo-token")
+        .and_then(|v| v.to_str().ok())
+        .expect("undo token header")
+        .to_string();
+    assert!(response.headers.contains_key("x-undo-expires-in"));
+
+    let response = app
+        .post(
+            "/mark-as-read/undo",
+            user_id,
+            &json!({ "undo_token": token }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+
+    // Second undo of the same token is a no-op
+    let response = app
+        .post(
+            "/mark-as-read/undo",
+            user_id,
+            &json!({ "undo_token": token }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    assert_eq!(response.json::<u64>().data, 0);
+
+    // Tokens are scoped to their user
+    let response = app
+        .post(
+            "/mark-as-read/undo",
+            user_id + 1,
+            &json!({ "undo_token": token }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::GONE, "{}", response.text());
+
+    // Unknown or expired token
+    let response = app
+        .post(
+            "/mark-as-read/undo",
+            user_id,
+            &json!({ "undo_token": "expired" }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::GONE, "{}", response.text());
+}
+
+#[tokio::test]
+async fn test_conditional_get_with_etag() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 8;
+
+    for path in ["/interests", "/subscriptions", "/user_rss"] {
+        let response = app.get(path, user_id).await;
+        assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+        let etag = response
+            .headers
+            .get("etag")
+            .unwrap_or_else(|| panic!("{path}: no ETag"))
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        let response = app
+            .send(
+                app.request(Method::GET, path, Some(user_id))
+                    .header("if-none-match", &etag)
+                    .build(),
+            )
+            .await;
+        assert_eq!(response.status, StatusCode::NOT_MODIFIED, "{path}");
+        assert!(response.body.is_empty());
+        assert_eq!(response.headers.get("etag").unwrap(), etag.as_str());
+    }
+
+    // creating a source and subscribing to it changes the ETag of /user_rss
+    let before = app.get("/user_rss", user_id).await.headers["etag"].clone();
+    let response = app
+        .post(
+            "/rss",
+            TEST_ADMIN_ID,
+            &json!({
+                "channel": "test-harness",
+                "skip_validation": true,
+                "name": "Harness|ETag",
+                "url": format!("https://example.com/harness/etag-{user_id}.xml"),
+            }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let source_id = response.json::<i32>().data;
+    let response = app
+        .post(
+            "/subscriptions/one",
+            user_id,
+            &json!({ "source_id": source_id }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let subscription_id = response
+        .json::<SubscriptionCreateResult>()
+        .data
+        .id()
+        .expect("subscribed");
+
+    let response = app
+        .send(
+            app.request(Method::GET, "/user_rss", Some(user_id))
+                .header("if-none-match", before.to_str().unwrap())
+                .build(),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    assert_ne!(response.headers["etag"], before);
+    assert!(
+        response.json::<Value>().data["source_map"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|source| source["id"] == source_id)
+    );
+
+    app.delete(&format!("/subscriptions/{subscription_id}"), user_id)
+        .await;
+    app.delete(&format!("/rss/{source_id}"), TEST_ADMIN_ID)
+        .await;
+}
+
+#[tokio::test]
+async fn test_etag_sees_writes_no_version_saw() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 55;
+    let response = app
+        .post(
+            "/rss",
+            TEST_ADMIN_ID,
+            &json!({
+                "channel": "test-harness",
+                "skip_validation": true,
+                "name": "Harness|ETagWrite",
+                "url": format!("https://example.com/harness/etag-write-{user_id}.xml"),
+            }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let source_id = response.json::<i32>().data;
+    let response = app
+        .post(
+            "/subscriptions/one",
+            user_id,
+            &json!({ "source_id": source_id }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let subscription_id = response
+        .json::<SubscriptionCreateResult>()
+        .data
+        .id()
+        .expect("subscribed");
+
+    let get_with = |etag: String| {
+        app.send(
+            app.request(Method::GET, "/subscriptions", Some(user_id))
+                .header("if-none-match", &etag)
+                .build(),
+        )
+    };
+    let response = app.get("/subscriptions", user_id).await;
+    let etag = response.headers["etag"].to_str().unwrap().to_string();
+    assert_eq!(
+        get_with(etag.clone()).await.status,
+        StatusCode::NOT_MODIFIED
+    );
+
+    // a write after the tag was issued, without any version bump
+    subscription_folders::set(&app.state.conn, user_id, source_id, Some("Harness"))
+        .await
+        .unwrap();
+    let response = get_with(etag.clone()).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let fresh = response.headers["etag"].to_str().unwrap().to_string();
+    assert_ne!(fresh, etag);
+    assert!(
+        response
+            .json::<Vec<Value>>()
+            .data
+            .iter()
+            .any(|s| s["source_id"] == source_id && s["folder"] == "Harness")
+    );
+    assert_eq!(get_with(fresh).await.status, StatusCode::NOT_MODIFIED);
+
+    app.delete(&format!("/subscriptions/{subscription_id}"), user_id)
+        .await;
+    app.delete(&format!("/rss/{source_id}"), TEST_ADMIN_ID)
+        .await;
+}
+
+#[tokio::test]
+async fn test_paper_detail() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 1;
+
+    let response = app.get("/papers/2147483000", user_id).await;
+    assert_eq!(
+        response.status,
+        StatusCode::NOT_FOUND,
+        "{}",
+        response.text()
+    );
+
+    // any paper of the listing has a detail with the user's verifications
+    let response = app
+        .get("/all-verified-papers?page=1&page_size=1", user_id)
+        .await;
+    let papers = response.json::<Value>().data["papers"].clone();
+    let Some(paper_id) = papers[0]["id"].as_i64() else {
+        return;
+    };
+    let response = app.get(&format!("/papers/{paper_id}"), user_id).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let detail = response.json::<Value>().data;
+    assert_eq!(detail["paper"]["id"], paper_id);
+    assert!(!detail["verifications"].as_array().unwrap().is_empty());
+    assert!(detail["interest_map"].is_object());
+
+    // another user neither subscribes to it nor has verified it
+    let response = app
+        .get(&format!("/papers/{paper_id}"), TEST_USER_BASE + 12)
+        .await;
+    assert_eq!(
+        response.status,
+        StatusCode::NOT_FOUND,
+        "{}",
+        response.text()
+    );
+}
+
+#[tokio::test]
+async fn test_mark_unread_round_trip() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 12;
+    let all = json!({ "paper_ids": [], "read_all": true });
+
+    let response = app.get("/unread-count", user_id).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let before = response.json::<u64>().data;
+
+    let response = app.post("/mark-as-read", user_id, &all).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    assert_eq!(
+        app.get("/unread-count", user_id).await.json::<u64>().data,
+        0
+    );
+
+    let response = app.post("/mark-as-unread", user_id, &all).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let changed = response.json::<u64>().data;
+    let after = app.get("/unread-count", user_id).await.json::<u64>().data;
+    assert!(after >= before, "before={before} after={after}");
+    assert!(changed >= after, "changed={changed} after={after}");
+
+    // rows already unread are not counted again
+    let response = app.post("/mark-as-unread", user_id, &all).await;
+    assert_eq!(response.json::<u64>().data, 0);
+    let response = app
+        .post(
+            "/mark-as-unread",
+            user_id,
+            &json!({ "paper_ids": [2147483000], "read_all": false }),
+        )
+        .await;
+    assert_eq!(response.json::<u64>().data, 0);
+}
+
+#[tokio::test]
+async fn test_rss_source_partial_update() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 13;
+    let url = format!("https://example.com/harness/{user_id}.xml");
+    let put = |path: String, uid: i64, body: Value| {
+        let app = &app;
+        async move {
+            app.send(
+                app.request(Method::PUT, &path, Some(uid))
+                    .json(&body)
+                    .build(),
+            )
+            .await
+        }
+    };
+
+    let response = app
+        .post(
+            "/rss",
+            TEST_ADMIN_ID,
+            &json!({
+                "channel": "test-harness",
+                "skip_validation": true,
+                "name": "Harness|Update",
+                "url": url,
+                "description": "before",
+            }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let source_id = response.json::<i32>().data;
+    let path = format!("/rss/{source_id}");
+
+    // sources are shared by every subscriber
+    let response = put(path.clone(), user_id, json!({ "name": "Harness|Mine" })).await;
+    assert_eq!(
+        response.status,
+        StatusCode::FORBIDDEN,
+        "{}",
+        response.text()
+    );
+
+    // only the name changes
+    let response = put(
+        path.clone(),
+        TEST_ADMIN_ID,
+        json!({ "name": "Harness|Updated" }),
+    )
+    .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let source = response.json::<Value>().data;
+    assert_eq!(source["name"], "Harness|Updated");
+    assert_eq!(source["url"], url);
+    assert_eq!(source["description"], "before");
+    assert_eq!(source["channel"], "test-harness");
+
+    let detail = app.get(&path, user_id).await.json::<Value>().data;
+    assert_eq!(detail["name"], "Harness|Updated");
+
+    // a new URL is validated like on creation
+    let response = put(
+        path.clone(),
+        TEST_ADMIN_ID,
+        json!({ "url": "http://169.254.169.254/latest/meta-data/" }),
+    )
+    .await;
+    assert_eq!(
+        response.status,
+        StatusCode::BAD_REQUEST,
+        "{}",
+        response.text()
+    );
+    assert!(response.text().contains("is not allowed"));
+
+    // a new URL forgets the last fetch
+    let response = put(
+        path.clone(),
+        TEST_ADMIN_ID,
+        json!({ "url": format!("{url}?v=2"), "skip_validation": true }),
+    )
+    .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let source = response.json::<Value>().data;
+    assert_eq!(source["url"], format!("{url}?v=2"));
+    assert!(source["last_fetched_at"].is_null());
+    assert_eq!(source["name"], "Harness|Updated");
+
+    // the same URL spelled differently is not a new URL
+    let response = put(
+        path.clone(),
+        TEST_ADMIN_ID,
+        json!({ "url": format!("{url}?v=2").replace("https://example.com", "https://EXAMPLE.com") }),
+    )
+    .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+
+    let response = put(
+        "/rss/2147483000".to_string(),
+        TEST_ADMIN_ID,
+        json!({ "name": "missing" }),
+    )
+    .await;
+    assert_eq!(
+        response.status,
+        StatusCode::NOT_FOUND,
+        "{}",
+        response.text()
+    );
+
+    let response = app.delete(&path, TEST_ADMIN_ID).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+}
+
+#[tokio::test]
+async fn test_rss_source_update_rejects_duplicate_urls() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 13;
+    let mut ids = Vec::new();
+    for name in ["a", "b"] {
+        let response = app
+            .post(
+                "/rss",
+                TEST_ADMIN_ID,
+                &json!({
+                    "channel": "test-harness",
+                    "skip_validation": true,
+                    "name": format!("Harness|Update|{name}"),
+                    "url": format!("https://example.com/harness/{user_id}-{name}.xml"),
+                }),
+            )
+            .await;
+        assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+        ids.push(response.json::<i32>().data);
+    }
+    let path = format!("/rss/{}", ids[1]);
+    let taken = format!("https://Example.com/harness/{user_id}-a.xml/");
+
+    let response = app
+        .send(
+            app.request(Method::PUT, &path, Some(TEST_ADMIN_ID))
+                .json(&json!({ "url": taken, "skip_validation": true }))
+                .build(),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::CONFLICT, "{}", response.text());
+    assert!(response.text().contains(&format!("RSS source {}", ids[0])));
+
+    let response = app
+        .send(
+            app.request(Method::PUT, &path, Some(TEST_ADMIN_ID))
+                .json(&json!({ "url": taken, "skip_validation": true, "force": true }))
+                .build(),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+
+    for id in ids {
+        app.delete(&format!("/rss/{id}"), TEST_ADMIN_ID).await;
+    }
+}
@@ -0,0 +1,158 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TEST_ADMIN_ID, TEST_USER_BASE, TestApp};
+use serde_json::{Value, json};
+use server::routers::feed::rss::{BatchItemResult, BatchItemStatus, MAX_RSS_BATCH};
+
+fn item(name: &str, url: &str) -> Value {
+    json!({
+        "channel": "test-harness",
+        "skip_validation": true,
+        "name": name,
+        "url": url,
+    })
+}
+
+#[tokio::test]
+async fn test_rss_batch_mixed_items() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let tag = TEST_USER_BASE + 23;
+    let url = |name: &str| format!("https://example.com/harness/batch-{tag}-{name}.xml");
+
+    let response = app
+        .post(
+            "/rss",
+            TEST_ADMIN_ID,
+            &item("Harness|Batch|Existing", &url("existing")),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let existing_id = response.json::<i32>().data;
+
+    let response = app
+        .post(
+            "/rss/batch",
+            tag,
+            &json!([item("Harness|Batch|New", &url("new"))]),
+        )
+        .await;
+    assert_eq!(
+        response.status,
+        StatusCode::FORBIDDEN,
+        "{}",
+        response.text()
+    );
+
+    let response = app
+        .post(
+            "/rss/batch",
+            TEST_ADMIN_ID,
+            &json!([
+                item("Harness|Batch|New", &url("new")),
+                // same feed as the existing source, once the host case is normalized
+                item(
+                    "Harness|Batch|Again",
+                    &url("existing").replace("example.com", "EXAMPLE.com")
+                ),
+                item("   ", &url("blank")),
+                item("Harness|Batch|Ftp", "ftp://example.com/feed.xml"),
+                // repeats the first item of the batch
+                item("Harness|Batch|New again", &url("new")),
+            ]),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let results = response.json::<Vec<BatchItemResult>>().data;
+    let statuses: Vec<BatchItemStatus> = results.iter().map(|result| result.status).collect();
+    assert_eq!(
+        statuses,
+        [
+            BatchItemStatus::Created,
+            BatchItemStatus::Duplicate,
+            BatchItemStatus::Invalid,
+            BatchItemStatus::Invalid,
+            BatchItemStatus::Duplicate,
+        ]
+    );
+    assert_eq!(
+        results
+            .iter()
+            .map(|result| result.index)
+            .collect::<Vec<_>>(),
+        [0, 1, 2, 3, 4]
+    );
+    let new_id = results[0].id.expect("created id");
+    assert_eq!(results[1].id, Some(existing_id));
+    assert!(results[2].error.as_deref().unwrap().contains("name"));
+    assert!(results[3].error.is_some());
+    assert_eq!(results[4].id, Some(new_id));
+
+    for id in [existing_id, new_id] {
+        app.delete(&format!("/rss/{id}"), TEST_ADMIN_ID).await;
+    }
+}
+
+#[tokio::test]
+async fn test_rss_batch_rolls_back_on_database_error() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let tag = TEST_USER_BASE + 24;
+    let first = format!("https://example.com/harness/batch-{tag}-first.xml");
+
+    // Postgres rejects NUL characters in text: the second insert fails after the first succeeded
+    let mut broken = item(
+        "Harness|Batch|Broken",
+        &format!("https://example.com/harness/batch-{tag}-broken.xml"),
+    );
+    broken["description"] = json!("nul \u{0} byte");
+    let response = app
+        .post(
+            "/rss/batch",
+            TEST_ADMIN_ID,
+            &json!([item("Harness|Batch|First", &first), broken]),
+        )
+        .await;
+    assert_eq!(
+        response.status,
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "{}",
+        response.text()
+    );
+
+    // the first item was rolled back, so it is created now rather than a duplicate
+    let response = app
+        .post(
+            "/rss/batch",
+            TEST_ADMIN_ID,
+            &json!([item("Harness|Batch|First", &first)]),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let results = response.json::<Vec<BatchItemResult>>().data;
+    assert_eq!(results[0].status, BatchItemStatus::Created);
+
+    app.delete(&format!("/rss/{}", results[0].id.unwrap()), TEST_ADMIN_ID)
+        .await;
+}
+
+#[tokio::test]
+async fn test_rss_batch_is_capped() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let items: Vec<Value> = (0..=MAX_RSS_BATCH)
+        .map(|i| {
+            item(
+                "Harness|Batch|Capped",
+                &format!("https://example.com/capped-{i}.xml"),
+            )
+        })
+        .collect();
+    let response = app.post("/rss/batch", TEST_ADMIN_ID, &items).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert!(response.text().contains(&MAX_RSS_BATCH.to_string()));
+}
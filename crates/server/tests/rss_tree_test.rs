@@ -0,0 +1,262 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TEST_ADMIN_ID, TEST_USER_BASE, TestApp};
+use serde_json::{Value, json};
+use server::routers::feed::subscriptions::SubscriptionCreateResult;
+
+/// The node at `path` (names from the root's children down)
+fn node<'a>(tree: &'a Value, path: &[&str]) -> &'a Value {
+    path.iter().fold(tree, |node, name| {
+        node["children"]
+            .as_array()
+            .expect("branch has children")
+            .iter()
+            .find(|child| child["name"] == *name)
+            .unwrap_or_else(|| panic!("no node {name} in {path:?}"))
+    })
+}
+
+#[tokio::test]
+async fn test_rss_tree_stats_roll_up() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_a = TEST_USER_BASE + 19;
+    let user_b = TEST_USER_BASE + 20;
+    let group = format!("Stats-{user_a}");
+
+    let mut source_ids = Vec::new();
+    for name in ["Deep|A", "Deep|B", "C"] {
+        let response = app
+            .post(
+                "/rss",
+                TEST_ADMIN_ID,
+                &json!({
+                    "channel": "arxiv",
+                    "skip_validation": true,
+                    "name": format!("Harness|{group}|{name}"),
+                    "url": format!("https://example.com/harness/{group}-{}.xml", name.replace('|', "-")),
+                }),
+            )
+            .await;
+        assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+        source_ids.push(response.json::<i32>().data);
+    }
+
+    let mut subscriptions = Vec::new();
+    for (user_id, source_id) in [
+        (user_a, source_ids[0]),
+        (user_a, source_ids[1]),
+        (user_b, source_ids[0]),
+    ] {
+        let response = app
+            .post(
+                "/subscriptions/one",
+                user_id,
+                &json!({ "source_id": source_id }),
+            )
+            .await;
+        assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+        let subscription_id = response
+            .json::<SubscriptionCreateResult>()
+            .data
+            .id()
+            .expect("created");
+        subscriptions.push((user_id, subscription_id));
+    }
+
+    let response = app.get("/rss", user_a).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let tree = response.json::<Value>().data;
+    let branch = node(&tree, &["arxiv", "Harness", &group]);
+    assert!(branch.get("paper_count").is_none());
+    assert!(branch.get("subscriber_count").is_none());
+
+    let response = app.get("/rss?with_stats=true", user_a).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let tree = response.json::<Value>().data;
+
+    let leaf_a = node(&tree, &["arxiv", "Harness", &group, "Deep", "A"]);
+    assert_eq!(leaf_a["subscriber_count"], 2);
+    assert_eq!(leaf_a["paper_count"], 0);
+    let leaf_c = node(&tree, &["arxiv", "Harness", &group, "C"]);
+    assert_eq!(leaf_c["subscriber_count"], 0);
+
+    let deep = node(&tree, &["arxiv", "Harness", &group, "Deep"]);
+    assert_eq!(deep["subscriber_count"], 3);
+    assert!(deep["data"].is_null());
+    let top = node(&tree, &["arxiv", "Harness", &group]);
+    assert_eq!(top["subscriber_count"], 3);
+    assert_eq!(top["paper_count"], 0);
+    // none of the harness sources was ever fetched
+    assert!(top.get("last_fetched_at").is_none());
+
+    for (user_id, subscription_id) in subscriptions {
+        app.delete(&format!("/subscriptions/{subscription_id}"), user_id)
+            .await;
+    }
+    for source_id in source_ids {
+        app.delete(&format!("/rss/{source_id}"), TEST_ADMIN_ID)
+            .await;
+    }
+}
+
+#[tokio::test]
+async fn test_rss_tree_channel_filter() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 21;
+    let channels: Vec<String> = ["alpha", "beta", "gamma"]
+        .iter()
+        .map(|name| format!("harness-{user_id}-{name}"))
+        .collect();
+
+    let mut source_ids = Vec::new();
+    for channel in &channels {
+        let response = app
+            .post(
+                "/rss",
+                TEST_ADMIN_ID,
+                &json!({
+                    "channel": channel,
+                    "skip_validation": true,
+                    "name": "Harness|Channels|Feed",
+                    "url": format!("https://example.com/harness/{channel}.xml"),
+                }),
+            )
+            .await;
+        assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+        source_ids.push(response.json::<i32>().data);
+    }
+
+    let response = app.get("/rss", user_id).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let tree = response.json::<Value>().data;
+    for (channel, source_id) in channels.iter().zip(&source_ids) {
+        let leaf = node(&tree, &[channel.as_str(), "Harness", "Channels", "Feed"]);
+        assert_eq!(leaf["data"]["id"], *source_id);
+        assert_eq!(leaf["data"]["channel"], *channel);
+    }
+
+    let response = app
+        .get(&format!("/rss?channel=%20{}%20", channels[1]), user_id)
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let tree = response.json::<Value>().data;
+    let top: Vec<&str> = tree["children"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|child| child["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(top, [channels[1].as_str()]);
+    let leaf = node(
+        &tree,
+        &[channels[1].as_str(), "Harness", "Channels", "Feed"],
+    );
+    assert_eq!(leaf["data"]["id"], source_ids[1]);
+
+    let response = app.get("/rss/channels", user_id).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let listed = response.json::<Vec<String>>().data;
+    for channel in &channels {
+        assert!(listed.contains(channel), "{channel} not in {listed:?}");
+    }
+
+    for source_id in source_ids {
+        app.delete(&format!("/rss/{source_id}"), TEST_ADMIN_ID)
+            .await;
+    }
+}
+
+#[tokio::test]
+async fn test_rss_tree_subscription_state() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 22;
+    let group = format!("Subscribed-{user_id}");
+    let names = ["Left|One", "Left|Two", "Left|Three", "Right|Four", "Five"];
+
+    let mut source_ids = Vec::new();
+    for name in names {
+        let response = app
+            .post(
+                "/rss",
+                TEST_ADMIN_ID,
+                &json!({
+                    "channel": "arxiv",
+                    "skip_validation": true,
+                    "name": format!("Harness|{group}|{name}"),
+                    "url": format!("https://example.com/harness/{group}-{}.xml", name.replace('|', "-")),
+                }),
+            )
+            .await;
+        assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+        source_ids.push(response.json::<i32>().data);
+    }
+
+    // subscribe to One and Four
+    let mut subscription_ids = Vec::new();
+    for source_id in [source_ids[0], source_ids[3]] {
+        let response = app
+            .post(
+                "/subscriptions/one",
+                user_id,
+                &json!({ "source_id": source_id }),
+            )
+            .await;
+        assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+        subscription_ids.push(
+            response
+                .json::<SubscriptionCreateResult>()
+                .data
+                .id()
+                .expect("created"),
+        );
+    }
+
+    let response = app.get("/rss", user_id).await;
+    let tree = response.json::<Value>().data;
+    let top = node(&tree, &["arxiv", "Harness", &group]);
+    assert!(top.get("subscribed_count").is_none());
+
+    let response = app
+        .get("/rss?include_subscription_state=true", user_id)
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let tree = response.json::<Value>().data;
+
+    let one = node(&tree, &["arxiv", "Harness", &group, "Left", "One"]);
+    assert_eq!(one["subscribed"], true);
+    assert_eq!(one["subscription_id"], subscription_ids[0]);
+    let two = node(&tree, &["arxiv", "Harness", &group, "Left", "Two"]);
+    assert_eq!(two["subscribed"], false);
+    assert!(two.get("subscription_id").is_none());
+    let five = node(&tree, &["arxiv", "Harness", &group, "Five"]);
+    assert_eq!(five["subscribed"], false);
+
+    assert_eq!(
+        node(&tree, &["arxiv", "Harness", &group, "Left"])["subscribed_count"],
+        1
+    );
+    assert_eq!(
+        node(&tree, &["arxiv", "Harness", &group, "Right"])["subscribed_count"],
+        1
+    );
+    assert_eq!(
+        node(&tree, &["arxiv", "Harness", &group])["subscribed_count"],
+        2
+    );
+
+    for subscription_id in subscription_ids {
+        app.delete(&format!("/subscriptions/{subscription_id}"), user_id)
+            .await;
+    }
+    for source_id in source_ids {
+        app.delete(&format!("/rss/{source_id}"), TEST_ADMIN_ID)
+            .await;
+    }
+}
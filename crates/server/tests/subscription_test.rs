@@ -0,0 +1,209 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TEST_ADMIN_ID, TEST_USER_BASE, TestApp};
+use serde_json::{Value, json};
+use server::routers::feed::subscriptions::SubscriptionCreateResult;
+
+/// An id no RSS source will reach in the test database
+const MISSING_SOURCE_ID: i32 = i32::MAX - 7;
+
+#[test]
+fn test_subscription_create_result_shape() {
+    assert_eq!(
+        serde_json::to_value(SubscriptionCreateResult::Created { id: 3 }).unwrap(),
+        json!({ "status": "created", "id": 3 })
+    );
+    assert_eq!(
+        serde_json::to_value(SubscriptionCreateResult::AlreadySubscribed { id: 3 }).unwrap(),
+        json!({ "status": "already_subscribed", "id": 3 })
+    );
+    assert_eq!(
+        serde_json::to_value(SubscriptionCreateResult::UnknownSource).unwrap(),
+        json!({ "status": "unknown_source" })
+    );
+    assert_eq!(SubscriptionCreateResult::UnknownSource.id(), None);
+}
+
+#[tokio::test]
+async fn test_subscriptions_reject_unknown_sources() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 30;
+
+    let response = app
+        .post(
+            "/rss",
+            TEST_ADMIN_ID,
+            &json!({
+                "channel": "test-harness",
+                "skip_validation": true,
+                "name": "Harness|Unknown sources",
+                "url": format!("https://example.com/harness/unknown-{user_id}.xml"),
+            }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let source_id = response.json::<i32>().data;
+
+    let response = app
+        .post(
+            "/subscriptions/one",
+            user_id,
+            &json!({ "source_id": MISSING_SOURCE_ID }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    assert_eq!(
+        response.json::<SubscriptionCreateResult>().data,
+        SubscriptionCreateResult::UnknownSource
+    );
+
+    let response = app
+        .post(
+            "/subscriptions/one",
+            user_id,
+            &json!({ "source_id": source_id }),
+        )
+        .await;
+    let created = response.json::<SubscriptionCreateResult>().data;
+    let SubscriptionCreateResult::Created {
+        id: subscription_id,
+    } = created
+    else {
+        panic!("expected a new subscription, got {created:?}");
+    };
+    let response = app
+        .post(
+            "/subscriptions/one",
+            user_id,
+            &json!({ "source_id": source_id }),
+        )
+        .await;
+    assert_eq!(
+        response.json::<SubscriptionCreateResult>().data,
+        SubscriptionCreateResult::AlreadySubscribed {
+            id: subscription_id
+        }
+    );
+
+    // the batch is rejected as a whole, naming the unknown id
+    let response = app
+        .post(
+            "/subscriptions",
+            user_id,
+            &json!({ "source_ids": [source_id, MISSING_SOURCE_ID] }),
+        )
+        .await;
+    assert_eq!(
+        response.status,
+        StatusCode::BAD_REQUEST,
+        "{}",
+        response.text()
+    );
+    assert!(response.text().contains(&MISSING_SOURCE_ID.to_string()));
+
+    let response = app
+        .post(
+            "/subscriptions",
+            user_id,
+            &json!({ "source_ids": [source_id, MISSING_SOURCE_ID], "ignore_unknown": true }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+
+    // nothing left once the unknown ids are dropped: rejected, not "clear all"
+    let response = app
+        .post(
+            "/subscriptions",
+            user_id,
+            &json!({ "source_ids": [MISSING_SOURCE_ID], "ignore_unknown": true }),
+        )
+        .await;
+    assert_eq!(
+        response.status,
+        StatusCode::BAD_REQUEST,
+        "{}",
+        response.text()
+    );
+
+    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+    let response = app.get("/subscriptions", user_id).await;
+    let subscriptions = response.json::<Vec<Value>>().data;
+    assert!(subscriptions.iter().any(|s| s["source_id"] == source_id));
+    assert!(
+        subscriptions
+            .iter()
+            .all(|s| s["source_id"] != MISSING_SOURCE_ID)
+    );
+
+    app.delete(&format!("/subscriptions/{subscription_id}"), user_id)
+        .await;
+    app.delete(&format!("/rss/{source_id}"), TEST_ADMIN_ID)
+        .await;
+}
+
+#[tokio::test]
+async fn test_subscription_delete_is_scoped_to_owner() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let owner = TEST_USER_BASE + 31;
+    let other = TEST_USER_BASE + 32;
+
+    let response = app
+        .post(
+            "/rss",
+            TEST_ADMIN_ID,
+            &json!({
+                "channel": "test-harness",
+                "skip_validation": true,
+                "name": "Harness|Owned subscription",
+                "url": format!("https://example.com/harness/owned-{owner}.xml"),
+            }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let source_id = response.json::<i32>().data;
+
+    let response = app
+        .post(
+            "/subscriptions/one",
+            owner,
+            &json!({ "source_id": source_id }),
+        )
+        .await;
+    let subscription_id = response
+        .json::<SubscriptionCreateResult>()
+        .data
+        .id()
+        .expect("subscribed");
+
+    let response = app
+        .delete(&format!("/subscriptions/{subscription_id}"), other)
+        .await;
+    assert_eq!(
+        response.status,
+        StatusCode::NOT_FOUND,
+        "{}",
+        response.text()
+    );
+    let response = app.get("/subscriptions", owner).await;
+    let subscriptions = response.json::<Vec<Value>>().data;
+    assert!(subscriptions.iter().any(|s| s["id"] == subscription_id));
+
+    let path = format!("/subscriptions/{subscription_id}");
+    let response = app.delete(&path, owner).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let response = app.delete(&path, owner).await;
+    assert_eq!(
+        response.status,
+        StatusCode::NOT_FOUND,
+        "{}",
+        response.text()
+    );
+
+    app.delete(&format!("/rss/{source_id}"), TEST_ADMIN_ID)
+        .await;
+}
@@ -0,0 +1,164 @@
+mod common;
+
+use axum::http::StatusCode;
+use chrono::{Duration, NaiveDate, Utc};
+use common::{TEST_ADMIN_ID, TEST_USER_BASE, TestApp};
+use serde_json::Value;
+use server::{
+    model::usage::{UsageReport, parse_window, window_bounds},
+    query::usage::{UsageCount, apply_rollup},
+    state::usage::{UsageEvent, UsageKey},
+};
+
+fn day(s: &str) -> NaiveDate {
+    s.parse().unwrap()
+}
+
+#[test]
+fn test_parse_window() {
+    assert_eq!(parse_window(None).unwrap(), 30);
+    assert_eq!(parse_window(Some("")).unwrap(), 30);
+    assert_eq!(parse_window(Some("7d")).unwrap(), 7);
+    assert_eq!(parse_window(Some("366d")).unwrap(), 366);
+    for invalid in ["0d", "367d", "24h", "d", "abc", "-1d"] {
+        assert!(parse_window(Some(invalid)).is_err(), "{invalid}");
+    }
+    assert_eq!(
+        window_bounds(day("2026-10-16"), 7),
+        (day("2026-10-10"), day("2026-10-16"))
+    );
+}
+
+#[test]
+fn test_usage_key_parse() {
+    assert_eq!(
+        UsageKey::parse("2026-10-16"),
+        Some(UsageKey::Day(day("2026-10-16")))
+    );
+    assert_eq!(
+        UsageKey::parse("2026-10-16:rollup:abc"),
+        Some(UsageKey::Rollup(day("2026-10-16"), "abc"))
+    );
+    assert_eq!(UsageKey::parse("2026-10-16:rollup:"), None);
+    assert_eq!(UsageKey::parse("2026-10-16:other"), None);
+    assert_eq!(UsageKey::parse("not-a-day"), None);
+}
+
+fn count(user_id: i64, day: NaiveDate, event: UsageEvent, count: i64) -> UsageCount {
+    UsageCount {
+        user_id,
+        day,
+        event: event.as_str().to_string(),
+        count,
+    }
+}
+
+#[test]
+fn test_report_is_dense_and_sums_sources() {
+    let (from, to) = (day("2026-10-14"), day("2026-10-16"));
+    let counts = [
+        // rolled up and still pending for the same day
+        count(1, day("2026-10-14"), UsageEvent::VerifyRun, 2),
+        count(1, day("2026-10-14"), UsageEvent::VerifyRun, 1),
+        count(1, day("2026-10-16"), UsageEvent::SseSeconds, 90),
+        // another user, never reported
+        count(2, day("2026-10-15"), UsageEvent::VerifyRun, 5),
+    ];
+    let report = UsageReport::build(1, from, to, &counts);
+
+    assert_eq!(report.series.len(), UsageEvent::ALL.len());
+    for series in &report.series {
+        assert_eq!(series.daily.len(), 3);
+        assert_eq!(series.daily[0].day, from);
+        assert_eq!(series.daily[2].day, to);
+    }
+    let verify = &report.series[0];
+    assert_eq!(verify.event, UsageEvent::VerifyRun);
+    assert_eq!(
+        verify.daily.iter().map(|d| d.count).collect::<Vec<_>>(),
+        [3, 0, 0]
+    );
+    assert_eq!(verify.total, 3);
+    assert_eq!(report.series[1].total, 90);
+    assert!(report.series[2..].iter().all(|s| s.total == 0));
+}
+
+fn total(report: &Value, event: &str) -> i64 {
+    report["series"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["event"] == event)
+        .unwrap()["total"]
+        .as_i64()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_usage_survives_rollup_exactly_once() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 10;
+    let usage = &app.state.usage;
+
+    let response = app.get("/usage?window=7d", user_id).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let before = total(&response.json::<Value>().data, "verify_run");
+
+    usage.record(user_id, UsageEvent::VerifyRun, 2);
+    usage.record(user_id, UsageEvent::VerifyRun, 1);
+    usage.flush().await;
+
+    // pending in Redis
+    let response = app.get("/usage?window=7d", user_id).await;
+    assert_eq!(
+        total(&response.json::<Value>().data, "verify_run"),
+        before + 3
+    );
+
+    // roll today up as if the day were over, twice
+    let tomorrow = Utc::now().date_naive() + Duration::days(1);
+    usage.roll_up(&app.state.conn, tomorrow).await.unwrap();
+    usage.roll_up(&app.state.conn, tomorrow).await.unwrap();
+
+    let response = app.get("/usage?window=7d", user_id).await;
+    assert_eq!(
+        total(&response.json::<Value>().data, "verify_run"),
+        before + 3
+    );
+
+    // the admin variant reports the same user
+    let response = app
+        .get(&format!("/admin/usage/{user_id}?window=7d"), TEST_ADMIN_ID)
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    assert_eq!(
+        total(&response.json::<Value>().data, "verify_run"),
+        before + 3
+    );
+
+    let response = app.get("/usage?window=24h", user_id).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_rollup_batch_applied_once() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 10;
+    let day = Utc::now().date_naive() - Duration::days(3);
+    let batch = format!("test-batch-{}", uuid::Uuid::new_v4().simple());
+    let rows = [count(user_id, day, UsageEvent::Export, 4)];
+
+    let response = app.get("/usage?window=7d", user_id).await;
+    let before = total(&response.json::<Value>().data, "export");
+
+    // a retry after a crash between the DB commit and the Redis cleanup
+    assert!(apply_rollup(&app.state.conn, &batch, &rows).await.unwrap());
+    assert!(!apply_rollup(&app.state.conn, &batch, &rows).await.unwrap());
+
+    let response = app.get("/usage?window=7d", user_id).await;
+    assert_eq!(total(&response.json::<Value>().data, "export"), before + 4);
+}
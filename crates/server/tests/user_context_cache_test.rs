@@ -0,0 +1,234 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::{TEST_ADMIN_ID, TEST_USER_BASE, TestApp};
+use redis::AsyncCommands;
+use serde_json::{Value, json};
+use server::{
+    routers::feed::subscriptions::SubscriptionCreateResult, state::user_context::CachedUserContext,
+};
+
+#[tokio::test]
+async fn test_interest_map_cache_invalidation_and_bypass() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 40;
+    let context = CachedUserContext::new(&app.state);
+    let interests = context.interests(user_id).await.unwrap();
+    let version = context.version(user_id).await.expect("redis is up");
+
+    // plant an entry the database does not back, stamped with the current version
+    let stale_id = i64::MAX;
+    assert!(!interests.contains_key(&stale_id));
+    let key = format!(
+        "{}:user-context:{user_id}:interests",
+        app.state.config.rss.feed_redis.redis_prefix
+    );
+    let mut conn = app.state.redis.pool.get().await.unwrap();
+    let _: () = conn
+        .set_ex(
+            &key,
+            json!({ "version": version, "data": [[stale_id, "stale interest"]] }).to_string(),
+            30,
+        )
+        .await
+        .unwrap();
+    let stale_key = stale_id.to_string();
+
+    let response = app.get("/all-verified-papers", user_id).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    assert_eq!(
+        response.json::<Value>().data["interest_map"][&stale_key],
+        "stale interest"
+    );
+
+    let response = app
+        .get("/all-verified-papers?cache_bypass=true", user_id)
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    assert!(
+        response.json::<Value>().data["interest_map"]
+            .get(&stale_key)
+            .is_none()
+    );
+
+    // an interest update invalidates the entry
+    let _: () = conn
+        .set_ex(
+            &key,
+            json!({ "version": version, "data": [[stale_id, "stale interest"]] }).to_string(),
+            30,
+        )
+        .await
+        .unwrap();
+    context.invalidate(user_id).await;
+    let response = app.get("/all-verified-papers", user_id).await;
+    assert!(
+        response.json::<Value>().data["interest_map"]
+            .get(&stale_key)
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_source_map_sees_source_updates() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 41;
+    let response = app
+        .post(
+            "/rss",
+            TEST_ADMIN_ID,
+            &json!({
+                "channel": "test-harness",
+                "skip_validation": true,
+                "name": "Harness|SourceMap|Before",
+                "url": format!("https://example.com/harness/source-map-{user_id}.xml"),
+            }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let source_id = response.json::<i32>().data;
+    let source_key = source_id.to_string();
+
+    let response = app
+        .post(
+            "/subscriptions/one",
+            user_id,
+            &json!({ "source_id": source_id }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let subscription_id = response
+        .json::<SubscriptionCreateResult>()
+        .data
+        .id()
+        .expect("subscribed");
+
+    // warm the cache
+    let response = app.get("/all-verified-papers", user_id).await;
+    assert_eq!(
+        response.json::<Value>().data["source_map"][&source_key]["name"],
+        "Harness|SourceMap|Before"
+    );
+
+    let response = app
+        .send(
+            app.request(
+                Method::PUT,
+                &format!("/rss/{source_id}"),
+                Some(TEST_ADMIN_ID),
+            )
+            .json(&json!({ "name": "Harness|SourceMap|After" }))
+            .build(),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+
+    let response = app.get("/all-verified-papers", user_id).await;
+    assert_eq!(
+        response.json::<Value>().data["source_map"][&source_key]["name"],
+        "Harness|SourceMap|After"
+    );
+
+    app.delete(&format!("/subscriptions/{subscription_id}"), user_id)
+        .await;
+    app.delete(&format!("/rss/{source_id}"), TEST_ADMIN_ID)
+        .await;
+}
+
+#[tokio::test]
+async fn test_pending_update_is_not_cached() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 53;
+    let context = CachedUserContext::new(&app.state);
+    let prefix = &app.state.config.rss.feed_redis.redis_prefix;
+    let key = format!("{prefix}:user-context:{user_id}:interests");
+    let pending_key = format!("{prefix}:user-context:{user_id}:update-pending");
+    let mut conn = app.state.redis.pool.get().await.unwrap();
+
+    context.begin_update(user_id).await;
+    assert_eq!(
+        context.version(user_id).await,
+        None,
+        "no version while pending"
+    );
+    let ttl: i64 = conn.ttl(&pending_key).await.unwrap();
+    assert!(ttl > 0, "the pending window expires on its own: {ttl}");
+
+    // reads during the window neither use nor fill the cache
+    let version: i64 = conn
+        .get(format!("{prefix}:user-context:{user_id}:version"))
+        .await
+        .unwrap();
+    let stale = json!({ "version": version, "data": [[i64::MAX, "stale interest"]] });
+    let _: () = conn.set_ex(&key, stale.to_string(), 30).await.unwrap();
+    let interests = context.interests(user_id).await.unwrap();
+    assert!(!interests.contains_key(&i64::MAX));
+    let cached: Option<String> = conn.get(&key).await.unwrap();
+    assert_eq!(cached.as_deref(), Some(stale.to_string().as_str()));
+
+    // once the window is over, the cache is used again
+    let _: () = conn.del(&pending_key).await.unwrap();
+    assert_eq!(context.version(user_id).await, Some(version));
+    let interests = context.interests(user_id).await.unwrap();
+    assert_eq!(interests[&i64::MAX], "stale interest");
+    let _: () = conn.del(&key).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_source_delete_invalidates_subscribers() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 54;
+    let response = app
+        .post(
+            "/rss",
+            TEST_ADMIN_ID,
+            &json!({
+                "channel": "test-harness",
+                "skip_validation": true,
+                "name": "Harness|SourceDelete",
+                "url": format!("https://example.com/harness/source-delete-{user_id}.xml"),
+            }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let source_id = response.json::<i32>().data;
+    let response = app
+        .post(
+            "/subscriptions/one",
+            user_id,
+            &json!({ "source_id": source_id }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+
+    let context = CachedUserContext::new(&app.state);
+    assert!(
+        context
+            .subscriptions(user_id)
+            .await
+            .unwrap()
+            .contains(&source_id)
+    );
+    let version = context.version(user_id).await.expect("redis is up");
+
+    let response = app
+        .delete(&format!("/rss/{source_id}"), TEST_ADMIN_ID)
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    assert_ne!(context.version(user_id).await, Some(version));
+    assert!(
+        !context
+            .subscriptions(user_id)
+            .await
+            .unwrap()
+            .contains(&source_id)
+    );
+}
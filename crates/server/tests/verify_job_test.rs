@@ -0,0 +1,71 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TEST_USER_BASE, TestApp};
+use feed::workers::verify_user_papers::VerifyAllUserPapersInput;
+use redis::AsyncCommands;
+use serde_json::json;
+use server::{
+    routers::feed::feeds::VerifyJobStatus,
+    state::{app_state::AppState, verify_job::VerifyJobMarker},
+};
+
+/// Jobs for `user_id` in the apalis `RedisStorage` of `VerifyAllUserPapersInput`. Its data
+/// hash (`{namespace}:data`, the namespace defaulting to the type name) keeps a job once a
+/// worker picks it up, so the count only grows while the test runs.
+async fn queued_jobs(state: &AppState, user_id: i64) -> usize {
+    let key = format!("{}:data", std::any::type_name::<VerifyAllUserPapersInput>());
+    let jobs: Vec<String> = redis::cmd("HVALS")
+        .arg(key)
+        .query_async(&mut state.redis.apalis_conn.clone())
+        .await
+        .unwrap();
+    let user = format!("\"user_id\":{user_id}");
+    jobs.iter().filter(|job| job.contains(&user)).count()
+}
+
+#[tokio::test]
+async fn test_verify_queues_one_job_per_user() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 27;
+    let key = VerifyJobMarker::new(&app.state).key(user_id);
+    let mut conn = app.state.redis.pool.get().await.unwrap();
+    let _: () = conn.del(&key).await.unwrap();
+    let before = queued_jobs(&app.state, user_id).await;
+
+    let mut statuses = Vec::new();
+    for _ in 0..2 {
+        let response = app
+            .post("/verify", user_id, &json!({ "channel": "test-harness" }))
+            .await;
+        assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+        statuses.push(response.json::<VerifyJobStatus>().data);
+    }
+    assert_eq!(
+        statuses,
+        [VerifyJobStatus::Queued, VerifyJobStatus::AlreadyQueued]
+    );
+    assert_eq!(queued_jobs(&app.state, user_id).await, before + 1);
+
+    // the marker expires by itself if the worker never clears it
+    let ttl: i64 = conn.ttl(&key).await.unwrap();
+    assert!(ttl > 0, "ttl {ttl}");
+
+    // once cleared, the next call queues again
+    VerifyJobMarker::new(&app.state)
+        .release(user_id)
+        .await
+        .unwrap();
+    let response = app
+        .post("/verify", user_id, &json!({ "channel": "test-harness" }))
+        .await;
+    assert_eq!(
+        response.json::<VerifyJobStatus>().data,
+        VerifyJobStatus::Queued
+    );
+    assert_eq!(queued_jobs(&app.state, user_id).await, before + 2);
+
+    let _: () = conn.del(&key).await.unwrap();
+}
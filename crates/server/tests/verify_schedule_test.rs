@@ -0,0 +1,160 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::{DateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use common::{TEST_USER_BASE, TestApp};
+use serde_json::{Value, json};
+use server::model::schedule::{VerifySchedule, latest_slot, next_slot};
+
+fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+}
+
+fn schedule(hour_of_day: u8, timezone: &str, updated_at: DateTime<Utc>) -> VerifySchedule {
+    VerifySchedule {
+        user_id: 1,
+        enabled: true,
+        hour_of_day,
+        timezone: timezone.to_string(),
+        channel: None,
+        max_match_limit: None,
+        last_run_at: None,
+        updated_at,
+    }
+}
+
+#[test]
+fn test_latest_and_next_slot_in_utc() {
+    let now = utc(2026, 3, 10, 9, 30);
+    assert_eq!(latest_slot(8, Tz::UTC, now), utc(2026, 3, 10, 8, 0));
+    assert_eq!(next_slot(8, Tz::UTC, now), utc(2026, 3, 11, 8, 0));
+    assert_eq!(latest_slot(10, Tz::UTC, now), utc(2026, 3, 9, 10, 0));
+    assert_eq!(next_slot(10, Tz::UTC, now), utc(2026, 3, 10, 10, 0));
+}
+
+#[test]
+fn test_slot_uses_local_hour() {
+    // 8:00 in Shanghai is 0:00 UTC
+    let tz: Tz = "Asia/Shanghai".parse().unwrap();
+    assert_eq!(
+        latest_slot(8, tz, utc(2026, 3, 10, 1, 0)),
+        utc(2026, 3, 10, 0, 0)
+    );
+    // 23:30 UTC is already the next local day
+    assert_eq!(
+        latest_slot(8, tz, utc(2026, 3, 10, 23, 30)),
+        utc(2026, 3, 10, 0, 0)
+    );
+    assert_eq!(
+        next_slot(8, tz, utc(2026, 3, 10, 23, 30)),
+        utc(2026, 3, 11, 0, 0)
+    );
+}
+
+#[test]
+fn test_slot_skipped_by_dst_runs_after_the_gap() {
+    // 2026-03-08 2:00 does not exist in New York, clocks jump from 2:00 EST to 3:00 EDT
+    let tz: Tz = "America/New_York".parse().unwrap();
+    assert_eq!(
+        next_slot(2, tz, utc(2026, 3, 8, 5, 0)),
+        utc(2026, 3, 8, 7, 0)
+    );
+}
+
+#[test]
+fn test_due_slot_fires_once_per_day() {
+    let mut schedule = schedule(8, "UTC", utc(2026, 3, 1, 12, 0));
+
+    // saved after the 8:00 slot: nothing until the next day
+    assert_eq!(schedule.due_slot(utc(2026, 3, 1, 13, 0)), None);
+    assert_eq!(schedule.due_slot(utc(2026, 3, 2, 7, 59)), None);
+    assert_eq!(
+        schedule.due_slot(utc(2026, 3, 2, 8, 5)),
+        Some(utc(2026, 3, 2, 8, 0))
+    );
+
+    // once run, the same slot is not due again
+    schedule.last_run_at = Some(utc(2026, 3, 2, 8, 5));
+    assert_eq!(schedule.due_slot(utc(2026, 3, 2, 8, 10)), None);
+    assert_eq!(schedule.due_slot(utc(2026, 3, 2, 23, 59)), None);
+    assert_eq!(
+        schedule.due_slot(utc(2026, 3, 3, 8, 0)),
+        Some(utc(2026, 3, 3, 8, 0))
+    );
+
+    // a missed slot is still run late
+    assert_eq!(
+        schedule.due_slot(utc(2026, 3, 4, 6, 0)),
+        Some(utc(2026, 3, 3, 8, 0))
+    );
+}
+
+#[test]
+fn test_disabled_schedule_is_never_due() {
+    let mut schedule = schedule(8, "UTC", utc(2026, 3, 1, 0, 0));
+    schedule.enabled = false;
+    assert_eq!(schedule.due_slot(utc(2026, 3, 2, 9, 0)), None);
+    assert_eq!(schedule.next_run_at(utc(2026, 3, 2, 9, 0)), None);
+}
+
+#[test]
+fn test_next_run_at_is_pending_slot_or_next_one() {
+    let schedule = schedule(8, "UTC", utc(2026, 3, 1, 0, 0));
+    assert_eq!(
+        schedule.next_run_at(utc(2026, 3, 1, 7, 0)),
+        Some(utc(2026, 3, 1, 8, 0))
+    );
+    assert_eq!(
+        schedule.next_run_at(utc(2026, 3, 1, 9, 0)),
+        Some(utc(2026, 3, 1, 8, 0))
+    );
+}
+
+#[tokio::test]
+async fn test_verify_schedule_round_trip() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 17;
+
+    let put = |body: Value| {
+        app.send(
+            app.request(Method::PUT, "/verify-schedule", Some(user_id))
+                .json(&body)
+                .build(),
+        )
+    };
+
+    let response = put(json!({ "hour_of_day": 24 })).await;
+    assert_ne!(response.status, StatusCode::OK);
+    let response = put(json!({ "hour_of_day": 7, "timezone": "Mars/Olympus" })).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+
+    let response = put(json!({
+        "hour_of_day": 7,
+        "timezone": "Europe/Paris",
+        "channel": " arxiv ",
+        "max_match_limit": 20,
+    }))
+    .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let saved = response.json::<Value>().data;
+    assert_eq!(saved["enabled"], true);
+    assert_eq!(saved["channel"], "arxiv");
+    assert!(saved["next_run_at"].is_string());
+
+    let response = app.get("/verify-schedule", user_id).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let fetched = response.json::<Value>().data;
+    assert_eq!(fetched["hour_of_day"], 7);
+    assert_eq!(fetched["timezone"], "Europe/Paris");
+    assert_eq!(fetched["max_match_limit"], 20);
+
+    let response = put(json!({ "enabled": false, "hour_of_day": 7 })).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let disabled = response.json::<Value>().data;
+    assert_eq!(disabled["enabled"], false);
+    assert_eq!(disabled["timezone"], "UTC");
+    assert!(disabled["next_run_at"].is_null());
+}
@@ -0,0 +1,133 @@
+mod common;
+
+use std::collections::HashMap;
+
+use axum::http::StatusCode;
+use chrono::{Duration, NaiveDate, Utc};
+use common::{TEST_USER_BASE, TestApp};
+use server::model::verify_stats::{VerifyStats, VerifyStatsRow, verify_stats_window};
+
+fn day(s: &str) -> NaiveDate {
+    s.parse().unwrap()
+}
+
+fn row(day: NaiveDate, interest_id: i64, verified: i64, matched: i64) -> VerifyStatsRow {
+    VerifyStatsRow {
+        day,
+        interest_id,
+        verified,
+        matched,
+    }
+}
+
+#[test]
+fn test_verify_stats_window() {
+    assert_eq!(verify_stats_window(None, 90).unwrap(), 30);
+    assert_eq!(verify_stats_window(Some(7), 90).unwrap(), 7);
+    assert_eq!(verify_stats_window(Some(90), 90).unwrap(), 90);
+    // the default never exceeds a smaller maximum
+    assert_eq!(verify_stats_window(None, 14).unwrap(), 14);
+    for invalid in [0, 91, u32::MAX] {
+        assert!(verify_stats_window(Some(invalid), 90).is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn test_verify_stats_build() {
+    let (from, to) = (day("2026-10-13"), day("2026-10-16"));
+    let rows = [
+        row(day("2026-10-13"), 1, 10, 4),
+        row(day("2026-10-13"), 2, 5, 0),
+        row(day("2026-10-15"), 1, 6, 2),
+        row(day("2026-10-16"), 3, 4, 4),
+        // outside the window
+        row(day("2026-10-12"), 1, 100, 100),
+    ];
+    let names = HashMap::from([(1, "graph learning".to_string()), (2, "llm".to_string())]);
+    let stats = VerifyStats::build(from, to, &rows, &names);
+
+    assert_eq!((stats.from, stats.to), (from, to));
+    assert_eq!((stats.verified, stats.matched), (25, 10));
+    assert_eq!(stats.match_rate, Some(0.4));
+
+    let daily: Vec<_> = stats
+        .daily
+        .iter()
+        .map(|d| (d.day, d.verified, d.matched))
+        .collect();
+    assert_eq!(
+        daily,
+        vec![
+            (day("2026-10-13"), 15, 4),
+            (day("2026-10-14"), 0, 0),
+            (day("2026-10-15"), 6, 2),
+            (day("2026-10-16"), 4, 4),
+        ]
+    );
+
+    let interests: Vec<_> = stats
+        .interests
+        .iter()
+        .map(|i| (i.interest_id, i.interest.as_deref(), i.verified, i.matched))
+        .collect();
+    assert_eq!(
+        interests,
+        vec![
+            (1, Some("graph learning"), 16, 6),
+            (3, None, 4, 4),
+            (2, Some("llm"), 5, 0),
+        ]
+    );
+    assert_eq!(stats.interests[0].match_rate, Some(6.0 / 16.0));
+    assert_eq!(stats.interests[2].match_rate, Some(0.0));
+}
+
+#[test]
+fn test_verify_stats_build_empty() {
+    let (from, to) = (day("2026-10-10"), day("2026-10-16"));
+    let stats = VerifyStats::build(from, to, &[], &HashMap::new());
+    assert_eq!(stats.daily.len(), 7);
+    assert!(
+        stats
+            .daily
+            .iter()
+            .all(|d| d.verified == 0 && d.matched == 0)
+    );
+    assert!(stats.interests.is_empty());
+    assert_eq!(stats.match_rate, None);
+}
+
+#[tokio::test]
+async fn test_verify_stats_endpoint() {
+    let Some(app) = TestApp::try_new().await else {
+        return;
+    };
+    let user_id = TEST_USER_BASE + 52;
+
+    // no verifications: a zero-filled default window ending today (UTC)
+    let response = app.get("/verify-stats", user_id).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    let stats = response.json::<VerifyStats>().data;
+    let today = Utc::now().date_naive();
+    assert_eq!(stats.daily.len(), 30);
+    assert_eq!(stats.to, today);
+    assert_eq!(stats.from, today - Duration::days(29));
+    assert_eq!(stats.verified, 0);
+    assert_eq!(stats.match_rate, None);
+
+    let response = app
+        .get("/verify-stats?days=7&channel=test-harness", user_id)
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.text());
+    assert_eq!(response.json::<VerifyStats>().data.daily.len(), 7);
+
+    for invalid in ["days=0", "days=100000", "days=abc"] {
+        let response = app.get(&format!("/verify-stats?{invalid}"), user_id).await;
+        assert_eq!(
+            response.status,
+            StatusCode::BAD_REQUEST,
+            "{invalid}: {}",
+            response.text()
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use serde_json::{Value, json};
+use server::{consts::FEED_TEMPORARILY_DISABLED, model::verify_stream::VerifyStreamEvent};
+
+fn round_trip(event: &VerifyStreamEvent) -> Value {
+    let value = serde_json::to_value(event).unwrap();
+    assert_eq!(value["type"], event.name());
+    let back: VerifyStreamEvent = serde_json::from_value(value.clone()).unwrap();
+    assert_eq!(&back, event);
+    value
+}
+
+#[test]
+fn test_error_event_shape() {
+    let event = VerifyStreamEvent::error(
+        FEED_TEMPORARILY_DISABLED,
+        "sse_streams is temporarily disabled",
+    );
+    let value = round_trip(&event);
+    let timestamp = value["timestamp"].as_i64().unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "type": "error",
+            "status": "error",
+            "code": 200503,
+            "message": "sse_streams is temporarily disabled",
+            "timestamp": timestamp,
+        })
+    );
+}
+
+#[test]
+fn test_progress_event_shapes() {
+    let verify_info = json!({ "pending_unverify_count": 3, "success_count": 1 });
+    let cases = [
+        (
+            VerifyStreamEvent::Ready {
+                user_id: 7,
+                verify_info: verify_info.clone(),
+                timestamp: 100,
+                status: "ready".to_string(),
+            },
+            json!({ "type": "ready", "user_id": 7, "verify_info": verify_info, "timestamp": 100, "status": "ready" }),
+        ),
+        (
+            VerifyStreamEvent::Processing {
+                user_id: 7,
+                verify_info: verify_info.clone(),
+                timestamp: 100,
+                status: "processing".to_string(),
+            },
+            json!({ "type": "processing", "user_id": 7, "verify_info": verify_info, "timestamp": 100, "status": "processing" }),
+        ),
+        (
+            VerifyStreamEvent::Heartbeat {
+                user_id: 7,
+                verify_info: None,
+                timestamp: 100,
+                status: "heartbeat".to_string(),
+                is_completed: false,
+            },
+            json!({ "type": "heartbeat", "user_id": 7, "timestamp": 100, "status": "heartbeat", "is_completed": false }),
+        ),
+        (
+            VerifyStreamEvent::VerifyPaperSuccess {
+                verification_details: json!({ "paper_id": 1 }),
+                verify_info: verify_info.clone(),
+                statistics: None,
+                timestamp: 100,
+                status: "success".to_string(),
+            },
+            json!({
+                "type": "verify_paper_success",
+                "verification_details": { "paper_id": 1 },
+                "verify_info": verify_info,
+                "timestamp": 100,
+                "status": "success",
+            }),
+        ),
+        (
+            VerifyStreamEvent::VerifyCompleted {
+                verify_info: verify_info.clone(),
+                timestamp: 100,
+                status: "completed".to_string(),
+                is_completed: true,
+            },
+            json!({ "type": "verify_completed", "verify_info": verify_info, "timestamp": 100, "status": "completed", "is_completed": true }),
+        ),
+        (
+            VerifyStreamEvent::MatchLimitReached {
+                user_id: 7,
+                matched: 50,
+                max_limit: 50,
+                timestamp: 100,
+                status: "match_limit_reached".to_string(),
+            },
+            json!({ "type": "match_limit_reached", "user_id": 7, "matched": 50, "max_limit": 50, "timestamp": 100, "status": "match_limit_reached" }),
+        ),
+        (
+            VerifyStreamEvent::EventsDropped {
+                skipped: 12,
+                timestamp: 100,
+            },
+            json!({ "type": "events_dropped", "skipped": 12, "timestamp": 100 }),
+        ),
+    ];
+    for (event, expected) in cases {
+        assert_eq!(round_trip(&event), expected);
+    }
+}
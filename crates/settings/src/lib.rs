@@ -0,0 +1,21 @@
+//! Configuration layering shared by the server and the worker.
+
+use figment::{
+    Figment,
+    providers::{Env, Format, Toml},
+};
+
+/// The same layering as `conf::config::app_config()`: `base.toml`, then the `APP_PROFILE`
+/// override file (`base.dev.toml`, `base.pre.toml`, `base.prod.toml` by default), then
+/// `APP_`-prefixed environment variables.
+pub fn figment() -> Figment {
+    let profile = match std::env::var("APP_PROFILE").as_deref() {
+        Ok("dev") => "dev",
+        Ok("pre") => "pre",
+        _ => "prod",
+    };
+    Figment::new()
+        .merge(Toml::file("base.toml"))
+        .merge(Toml::file(format!("base.{profile}.toml")))
+        .merge(Env::prefixed("APP_").split("."))
+}
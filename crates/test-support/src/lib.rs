@@ -0,0 +1,23 @@
+//! Helpers shared by the integration tests of the workspace crates.
+
+use std::fmt::Display;
+
+/// Whether missing infrastructure fails the tests instead of skipping them: set `CI` (as CI
+/// runners do) or `REQUIRE_TEST_INFRA`
+pub fn infra_required() -> bool {
+    ["CI", "REQUIRE_TEST_INFRA"].into_iter().any(|name| {
+        std::env::var(name)
+            .map(|value| !matches!(value.trim(), "" | "0" | "false"))
+            .unwrap_or(false)
+    })
+}
+
+/// Skip a test whose database or Redis is unavailable: `None` locally, a panic when
+/// `infra_required`
+pub fn skip<T>(reason: impl Display) -> Option<T> {
+    if infra_required() {
+        panic!("test infrastructure unavailable: {reason}");
+    }
+    eprintln!("{reason}, skipping");
+    None
+}
@@ -0,0 +1,35 @@
+use serde::Deserialize;
+use settings::figment;
+
+/// Worker settings that live under `[rss]` but are not part of `conf::config::RssConfig`.
+///
+/// Loaded through `settings::figment()`, with the same layering as `app_config()`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkerRssConfig {
+    /// Papers older than this many days get their `raw_data` pruned. `None` disables pruning.
+    #[serde(default)]
+    pub raw_data_retention_days: Option<u32>,
+    /// Only count what would be pruned, do not write anything
+    #[serde(default)]
+    pub raw_data_retention_dry_run: bool,
+    /// Rows updated per statement
+    #[serde(default = "default_raw_data_retention_batch_size")]
+    pub raw_data_retention_batch_size: u32,
+    /// Seconds between two retention runs
+    #[serde(default = "default_raw_data_retention_interval_secs")]
+    pub raw_data_retention_interval_secs: u64,
+}
+
+fn default_raw_data_retention_batch_size() -> u32 {
+    1000
+}
+
+fn default_raw_data_retention_interval_secs() -> u64 {
+    24 * 60 * 60
+}
+
+pub fn worker_rss_config() -> WorkerRssConfig {
+    figment()
+        .extract_inner::<WorkerRssConfig>("rss")
+        .expect("Invalid [rss] worker configuration")
+}
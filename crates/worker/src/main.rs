@@ -1,7 +1,9 @@
 use conf::config::app_config;
 use dotenvy::dotenv;
 use feed::manager;
+use seaorm_db::connection::get_db;
 use tracing::info;
+use worker::{config, retention};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -14,6 +16,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize and start workers (Monitor registration is completed inside init)
     manager::entry::init().await?;
     info!(target: "feed", "Workers started and running");
+
+    tokio::spawn(retention::run_raw_data_retention(
+        get_db().await.clone(),
+        config::worker_rss_config(),
+    ));
+
     // Blocking run: Apalis Monitor internally managed, current process stays alive
     // If explicit blocking is needed, a pending future can be added here
     futures::future::pending::<()>().await;
@@ -0,0 +1,156 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, DbErr, Statement};
+use serde_json::json;
+use tracing::{error, info};
+
+use crate::config::WorkerRssConfig;
+
+/// Papers are only pruned when:
+/// - they are older than the retention threshold,
+/// - their source is not flagged `keep_raw_data` (sources under debugging),
+/// - parsing produced a description, otherwise `raw_data` is the only copy left to reprocess from.
+const PRUNABLE_FILTER: &str = r#"
+    p.raw_data IS NOT NULL
+    AND p.created_at < $1
+    AND COALESCE(s.keep_raw_data, FALSE) = FALSE
+    AND COALESCE(p.description, '') <> ''
+"#;
+
+/// `rss_job_logs.task_type` of the retention runs
+pub const RETENTION_TASK_TYPE: &str = "raw_data_retention";
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RetentionReport {
+    pub pruned: u64,
+    pub batches: u64,
+    pub dry_run: bool,
+}
+
+/// Run `prune_raw_data` forever, once every `raw_data_retention_interval_secs`.
+pub async fn run_raw_data_retention(conn: DatabaseConnection, config: WorkerRssConfig) {
+    let Some(days) = config.raw_data_retention_days else {
+        info!(target: "feed", "raw_data retention disabled");
+        return;
+    };
+
+    let mut interval =
+        tokio::time::interval(Duration::from_secs(config.raw_data_retention_interval_secs));
+    loop {
+        interval.tick().await;
+        let cutoff = Utc::now() - chrono::Duration::days(days as i64);
+        let result = prune_raw_data(
+            &conn,
+            cutoff,
+            config.raw_data_retention_batch_size,
+            config.raw_data_retention_dry_run,
+        )
+        .await;
+        match &result {
+            Ok(report) => info!(
+                target: "feed",
+                pruned = report.pruned,
+                batches = report.batches,
+                dry_run = report.dry_run,
+                %cutoff,
+                "raw_data retention finished"
+            ),
+            Err(e) => error!(target: "feed", error = %e, "raw_data retention failed"),
+        }
+        if let Err(e) = record_run(&conn, cutoff, &result).await {
+            error!(target: "feed", error = %e, "failed to record raw_data retention run");
+        }
+    }
+}
+
+/// Log one retention run to `rss_job_logs` under `RETENTION_TASK_TYPE`, with the report (or
+/// the error) as the JSON message.
+pub async fn record_run(
+    conn: &DatabaseConnection,
+    cutoff: DateTime<Utc>,
+    result: &Result<RetentionReport, DbErr>,
+) -> Result<(), DbErr> {
+    let (status, message) = match result {
+        Ok(report) => (
+            "success",
+            json!({
+                "cutoff": cutoff,
+                "pruned": report.pruned,
+                "batches": report.batches,
+                "dry_run": report.dry_run,
+            }),
+        ),
+        Err(e) => (
+            "failed",
+            json!({ "cutoff": cutoff, "error": e.to_string() }),
+        ),
+    };
+    conn.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "INSERT INTO rss_job_logs (task_type, status, message) VALUES ($1, $2, $3)",
+        [
+            RETENTION_TASK_TYPE.into(),
+            status.into(),
+            message.to_string().into(),
+        ],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Null out `rss_papers.raw_data` for papers created before `cutoff`, `batch_size` rows at a time.
+///
+/// With `dry_run` nothing is written and `pruned` is the number of rows that would be touched.
+pub async fn prune_raw_data(
+    conn: &DatabaseConnection,
+    cutoff: DateTime<Utc>,
+    batch_size: u32,
+    dry_run: bool,
+) -> Result<RetentionReport, DbErr> {
+    if dry_run {
+        let row = conn
+            .query_one(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                format!(
+                    "SELECT COUNT(*) AS count FROM rss_papers p \
+                     LEFT JOIN rss_sources s ON s.id = p.source_id WHERE {PRUNABLE_FILTER}"
+                ),
+                [cutoff.into()],
+            ))
+            .await?;
+        let pruned = match row {
+            Some(row) => row.try_get::<i64>("", "count")? as u64,
+            None => 0,
+        };
+        return Ok(RetentionReport {
+            pruned,
+            batches: 0,
+            dry_run,
+        });
+    }
+
+    let batch_size = batch_size.max(1);
+    let mut report = RetentionReport::default();
+    loop {
+        let result = conn
+            .execute(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                format!(
+                    "UPDATE rss_papers SET raw_data = NULL WHERE id IN ( \
+                     SELECT p.id FROM rss_papers p \
+                     LEFT JOIN rss_sources s ON s.id = p.source_id \
+                     WHERE {PRUNABLE_FILTER} ORDER BY p.id LIMIT $2)"
+                ),
+                [cutoff.into(), (batch_size as i64).into()],
+            ))
+            .await?;
+        let affected = result.rows_affected();
+        report.pruned += affected;
+        report.batches += 1;
+        if affected < batch_size as u64 {
+            break;
+        }
+    }
+    Ok(report)
+}
@@ -3,6 +3,7 @@ use std::time::{Duration, Instant};
 use conf::config::app_config;
 use feed::parsers::arxiv::convert_rss_paper_model_to_paper;
 use feed::workers::{base::RedisService, verify_user_papers::run_verify_with_input};
+use futures::StreamExt;
 use paper::scholar::paper::Paper;
 use protocol::tasks::verify::Criteria;
 use sea_orm::{EntityTrait, QueryOrder, QuerySelect};
@@ -12,6 +13,9 @@ use seaorm_db::query::feed::user_interests::UserInterestsQuery;
 use tracing::{info, warn};
 use tracing_subscriber::EnvFilter;
 
+/// Interest groups of one paper verified at once by the concurrent strategy
+const GROUP_CONCURRENCY: usize = 5;
+
 #[tokio::test]
 async fn bench_verify_by_order() -> Result<(), Box<dyn std::error::Error>> {
     let _ = tracing_subscriber::fmt()
@@ -170,13 +174,41 @@ async fn bench_verify_by_order() -> Result<(), Box<dyn std::error::Error>> {
     }
     let dur_by_interest = start.elapsed();
 
+    // benchmark 3: by paper, the groups of each paper verified concurrently, repeated
+    let start = Instant::now();
+    for _ in 0..repeats {
+        for paper in &papers {
+            futures::stream::iter(&interest_groups)
+                .map(|group| {
+                    run_verify_with_input(
+                        db.clone(),
+                        RedisService {
+                            pool: redis_service.pool.clone(),
+                            apalis_conn: redis_service.apalis_conn.clone(),
+                        },
+                        search::agent::verify::ToBeVerified::Paper(Box::new(paper.clone())),
+                        group.clone(),
+                        model_name.clone(),
+                        "rss feed verify benchmark",
+                    )
+                })
+                .buffer_unordered(GROUP_CONCURRENCY)
+                .for_each(|_| async {})
+                .await;
+        }
+    }
+    let dur_concurrent_groups = start.elapsed();
+
     let calls = base_calls * repeats;
     info!(
         calls,
+        group_concurrency = GROUP_CONCURRENCY,
         ms_by_paper = %dur_by_paper.as_millis(),
         ms_by_interest = %dur_by_interest.as_millis(),
+        ms_concurrent_groups = %dur_concurrent_groups.as_millis(),
         qps_by_paper = (calls as f64) / (dur_by_paper.as_secs_f64().max(1e-6)),
         qps_by_interest = (calls as f64) / (dur_by_interest.as_secs_f64().max(1e-6)),
+        qps_concurrent_groups = (calls as f64) / (dur_concurrent_groups.as_secs_f64().max(1e-6)),
         "bench finished"
     );
 
@@ -0,0 +1,214 @@
+//! Runs `prune_raw_data` against seeded tables in a fresh schema.
+//!
+//! Needs `DATABASE_URL` pointing at a PostgreSQL server; skipped otherwise, except under CI
+//! (see `test_support::infra_required`).
+
+use chrono::{Duration, Utc};
+use sea_orm::{
+    ConnectOptions, ConnectionTrait, Database, DatabaseConnection, DbErr, FromQueryResult,
+    Statement,
+};
+use test_support::skip;
+use worker::retention::{RETENTION_TASK_TYPE, RetentionReport, prune_raw_data, record_run};
+
+/// Connect with `search_path` set to a new, empty schema, one per test
+async fn fresh_schema(test: &str) -> Option<(DatabaseConnection, String)> {
+    dotenvy::dotenv().ok();
+    let Ok(url) = std::env::var("DATABASE_URL") else {
+        return skip("DATABASE_URL not set");
+    };
+    let schema = format!("retention_test_{test}_{}", std::process::id());
+
+    let admin = match Database::connect(&url).await {
+        Ok(conn) => conn,
+        Err(e) => return skip(format!("database unavailable ({e})")),
+    };
+    admin
+        .execute_unprepared(&format!(
+            "DROP SCHEMA IF EXISTS {schema} CASCADE; CREATE SCHEMA {schema};"
+        ))
+        .await
+        .expect("create test schema");
+
+    let mut options = ConnectOptions::new(url);
+    options
+        .max_connections(1)
+        .set_schema_search_path(schema.clone());
+    let conn = Database::connect(options)
+        .await
+        .expect("connect to test schema");
+    Some((conn, schema))
+}
+
+/// Only the columns the retention job reads and writes
+const SCHEMA: &str = r#"
+    CREATE TABLE rss_sources (
+        id BIGINT PRIMARY KEY,
+        keep_raw_data BOOLEAN NOT NULL DEFAULT FALSE
+    );
+    CREATE TABLE rss_papers (
+        id BIGINT PRIMARY KEY,
+        source_id BIGINT,
+        raw_data TEXT,
+        description TEXT,
+        created_at TIMESTAMPTZ NOT NULL
+    );
+    CREATE TABLE rss_job_logs (
+        id BIGSERIAL PRIMARY KEY,
+        task_type TEXT NOT NULL,
+        status TEXT NOT NULL,
+        message TEXT,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+    );
+    INSERT INTO rss_sources (id, keep_raw_data) VALUES (1, FALSE), (2, TRUE);
+"#;
+
+/// Papers 1-5 are prunable, the others are kept for the reason given
+const PAPERS: &[(i64, i64, Option<&str>, i64)] = &[
+    // (id, source_id, description, age in days)
+    (1, 1, Some("parsed"), 40),
+    (2, 1, Some("parsed"), 40),
+    (3, 1, Some("parsed"), 31),
+    (4, 1, Some("parsed"), 60),
+    (5, 3, Some("parsed"), 40), // source without a row
+    (6, 1, Some("parsed"), 29), // newer than the cutoff
+    (7, 2, Some("parsed"), 40), // source keeps raw_data
+    (8, 1, Some(""), 40),       // enrichment failed
+    (9, 1, None, 40),           // enrichment failed
+];
+
+#[derive(Debug, FromQueryResult)]
+struct IdRow {
+    id: i64,
+}
+
+async fn ids_with_raw_data(conn: &DatabaseConnection) -> Vec<i64> {
+    IdRow::find_by_statement(Statement::from_string(
+        conn.get_database_backend(),
+        "SELECT id FROM rss_papers WHERE raw_data IS NOT NULL ORDER BY id",
+    ))
+    .all(conn)
+    .await
+    .expect("list papers")
+    .into_iter()
+    .map(|row| row.id)
+    .collect()
+}
+
+#[tokio::test]
+async fn test_prune_raw_data() {
+    let Some((conn, schema)) = fresh_schema("prune").await else {
+        return;
+    };
+    conn.execute_unprepared(SCHEMA)
+        .await
+        .expect("create tables");
+    let now = Utc::now();
+    for (id, source_id, description, age) in PAPERS {
+        conn.execute(Statement::from_sql_and_values(
+            conn.get_database_backend(),
+            "INSERT INTO rss_papers (id, source_id, raw_data, description, created_at) \
+             VALUES ($1, $2, '<item/>', $3, $4)",
+            [
+                (*id).into(),
+                (*source_id).into(),
+                description.map(str::to_string).into(),
+                (now - Duration::days(*age)).into(),
+            ],
+        ))
+        .await
+        .expect("seed paper");
+    }
+    // already pruned: never counted again
+    conn.execute(Statement::from_sql_and_values(
+        conn.get_database_backend(),
+        "INSERT INTO rss_papers (id, source_id, raw_data, description, created_at) \
+         VALUES (10, 1, NULL, 'parsed', $1)",
+        [(now - Duration::days(40)).into()],
+    ))
+    .await
+    .expect("seed pruned paper");
+    let cutoff = now - Duration::days(30);
+    let all: Vec<i64> = (1..=9).collect();
+
+    // dry run: counted, nothing written
+    let report = prune_raw_data(&conn, cutoff, 2, true).await.unwrap();
+    assert_eq!(
+        (report.pruned, report.batches, report.dry_run),
+        (5, 0, true)
+    );
+    assert_eq!(ids_with_raw_data(&conn).await, all);
+
+    // batches of 2: 2 + 2 + 1
+    let report = prune_raw_data(&conn, cutoff, 2, false).await.unwrap();
+    assert_eq!(
+        (report.pruned, report.batches, report.dry_run),
+        (5, 3, false)
+    );
+    assert_eq!(ids_with_raw_data(&conn).await, vec![6, 7, 8, 9]);
+
+    // nothing left: one empty batch
+    let report = prune_raw_data(&conn, cutoff, 2, false).await.unwrap();
+    assert_eq!((report.pruned, report.batches), (0, 1));
+    assert_eq!(ids_with_raw_data(&conn).await, vec![6, 7, 8, 9]);
+
+    // a full last batch takes one more statement to notice the end
+    conn.execute_unprepared("UPDATE rss_papers SET raw_data = '<item/>' WHERE id <= 4")
+        .await
+        .unwrap();
+    let report = prune_raw_data(&conn, cutoff, 4, false).await.unwrap();
+    assert_eq!((report.pruned, report.batches), (4, 2));
+
+    conn.execute_unprepared(&format!("DROP SCHEMA {schema} CASCADE"))
+        .await
+        .unwrap();
+}
+
+#[derive(Debug, FromQueryResult)]
+struct JobLogRow {
+    status: String,
+    message: String,
+}
+
+#[tokio::test]
+async fn test_record_run() {
+    let Some((conn, schema)) = fresh_schema("record").await else {
+        return;
+    };
+    conn.execute_unprepared(SCHEMA)
+        .await
+        .expect("create tables");
+    let cutoff = Utc::now() - Duration::days(30);
+
+    let report = RetentionReport {
+        pruned: 5,
+        batches: 3,
+        dry_run: false,
+    };
+    record_run(&conn, cutoff, &Ok(report)).await.unwrap();
+    record_run(&conn, cutoff, &Err(DbErr::Custom("boom".into())))
+        .await
+        .unwrap();
+
+    let rows = JobLogRow::find_by_statement(Statement::from_sql_and_values(
+        conn.get_database_backend(),
+        "SELECT status, message FROM rss_job_logs WHERE task_type = $1 ORDER BY id",
+        [RETENTION_TASK_TYPE.into()],
+    ))
+    .all(&conn)
+    .await
+    .unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].status, "success");
+    let message: serde_json::Value = serde_json::from_str(&rows[0].message).unwrap();
+    assert_eq!(message["pruned"], 5);
+    assert_eq!(message["batches"], 3);
+    assert_eq!(message["dry_run"], false);
+    assert_eq!(rows[1].status, "failed");
+    let message: serde_json::Value = serde_json::from_str(&rows[1].message).unwrap();
+    assert!(message["error"].as_str().unwrap().contains("boom"));
+
+    conn.execute_unprepared(&format!("DROP SCHEMA {schema} CASCADE"))
+        .await
+        .unwrap();
+}
@@ -0,0 +1,21 @@
+--- audit_log: who ran which destructive operation, on what, and when
+CREATE TABLE IF NOT EXISTS audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    actor_id BIGINT NOT NULL,
+    actor_open_id TEXT NULL,
+    action VARCHAR(64) NOT NULL,
+    target_type VARCHAR(64) NOT NULL,
+    target_ids BIGINT[] NOT NULL DEFAULT '{}',
+    metadata JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_created_at
+    ON audit_log (created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_log_actor
+    ON audit_log (actor_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_log_action
+    ON audit_log (action, created_at DESC);
+
+COMMENT ON COLUMN audit_log.target_ids IS 'Ids of the affected rows; empty when the operation cleared everything of the actor';
+COMMENT ON COLUMN audit_log.metadata IS 'Request metadata (request_id) and operation details';
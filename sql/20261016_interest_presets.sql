@@ -0,0 +1,13 @@
+--- interest_presets: admin-curated interest sets users can apply in one click
+CREATE TABLE IF NOT EXISTS interest_presets (
+    id BIGSERIAL PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT NULL,
+    interests JSONB NOT NULL DEFAULT '[]'::JSONB,
+    channel_hints JSONB NOT NULL DEFAULT '[]'::JSONB,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON COLUMN interest_presets.interests IS 'Ordered JSON array of interest strings';
+COMMENT ON COLUMN interest_presets.channel_hints IS 'JSON array of channels the preset is meant for';
@@ -0,0 +1,11 @@
+--- paper_notes: a user's short note on a paper
+CREATE TABLE IF NOT EXISTS paper_notes (
+    user_id BIGINT NOT NULL,
+    paper_id INTEGER NOT NULL,
+    note TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, paper_id)
+);
+
+COMMENT ON COLUMN paper_notes.note IS 'Free text, at most 4 KiB (checked by the server)';
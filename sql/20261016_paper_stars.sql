@@ -0,0 +1,7 @@
+--- paper_stars: papers a user starred to build a shortlist
+CREATE TABLE IF NOT EXISTS paper_stars (
+    user_id BIGINT NOT NULL,
+    paper_id INTEGER NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, paper_id)
+);
@@ -0,0 +1,6 @@
+--- rss_sources: opt a source out of raw_data retention (e.g. while debugging its parser)
+ALTER TABLE rss_sources ADD COLUMN IF NOT EXISTS keep_raw_data BOOLEAN NOT NULL DEFAULT FALSE;
+
+--- rss_papers: support the retention scan (created_at range on rows that still carry raw_data)
+CREATE INDEX IF NOT EXISTS idx_rss_papers_raw_data_created_at
+    ON rss_papers (created_at) WHERE raw_data IS NOT NULL;
@@ -0,0 +1,10 @@
+--- subscription_folders: user-defined folder of each subscribed source
+CREATE TABLE IF NOT EXISTS subscription_folders (
+    user_id BIGINT NOT NULL,
+    source_id INTEGER NOT NULL,
+    folder VARCHAR(255) NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, source_id)
+);
+
+COMMENT ON TABLE subscription_folders IS 'Keyed by (user, source) rather than subscription id, so folders survive the batch subscription update recreating rows';
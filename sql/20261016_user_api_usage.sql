@@ -0,0 +1,17 @@
+--- user_api_usage: per-user daily API usage, rolled up from the Redis counters
+CREATE TABLE IF NOT EXISTS user_api_usage (
+    user_id BIGINT NOT NULL,
+    day DATE NOT NULL,
+    event TEXT NOT NULL,
+    count BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (user_id, day, event)
+);
+
+--- user_api_usage_rollups: rollup batches already applied, so a batch is never counted twice
+CREATE TABLE IF NOT EXISTS user_api_usage_rollups (
+    batch TEXT PRIMARY KEY,
+    applied_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON COLUMN user_api_usage.event IS 'Usage event type, e.g. verify_run, sse_seconds';
+COMMENT ON COLUMN user_api_usage.count IS 'Occurrences (or seconds for sse_seconds) on that day';
@@ -0,0 +1,15 @@
+--- verify_schedules: opt-in daily verification run of each user
+CREATE TABLE IF NOT EXISTS verify_schedules (
+    user_id BIGINT PRIMARY KEY,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    hour_of_day SMALLINT NOT NULL CHECK (hour_of_day BETWEEN 0 AND 23),
+    timezone VARCHAR(64) NOT NULL DEFAULT 'UTC',
+    channel VARCHAR(255),
+    max_match_limit INTEGER,
+    last_run_at TIMESTAMP WITH TIME ZONE,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_verify_schedules_enabled ON verify_schedules (enabled) WHERE enabled;
+
+COMMENT ON COLUMN verify_schedules.last_run_at IS 'When the scheduler last queued the user; claimed with a compare-and-set so a slot fires once across server replicas';